@@ -0,0 +1,47 @@
+use soapus_codegen::SoapClientGenerator;
+use std::path::PathBuf;
+
+/// WSDLs in `testdata/wsdl` whose generated clients are compiled, one module each
+const WSDLS: &[&str] = &[
+    "collisions",
+    "orders",
+    "payments",
+    "reservations",
+    "wrapped",
+];
+
+/// WSDLs with security policies, compiled with the `ws-security` feature
+const WS_SECURITY_WSDLS: &[&str] = &["ledger"];
+
+/// WSDLs using WS-Addressing, compiled with the `ws-addressing` feature
+const WS_ADDRESSING_WSDLS: &[&str] = &["tickets"];
+
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR not set"));
+
+    let ws_security = std::env::var_os("CARGO_FEATURE_WS_SECURITY").is_some();
+    let ws_security_wsdls = WS_SECURITY_WSDLS.iter().filter(|_| ws_security);
+    let ws_addressing = std::env::var_os("CARGO_FEATURE_WS_ADDRESSING").is_some();
+    let ws_addressing_wsdls = WS_ADDRESSING_WSDLS.iter().filter(|_| ws_addressing);
+
+    for name in WSDLS
+        .iter()
+        .chain(ws_security_wsdls)
+        .chain(ws_addressing_wsdls)
+    {
+        let wsdl_path =
+            PathBuf::from(&manifest_dir).join(format!("../testdata/wsdl/{}.wsdl", name));
+        println!("cargo:rerun-if-changed={}", wsdl_path.display());
+
+        SoapClientGenerator::builder()
+            .wsdl_path(&wsdl_path)
+            .out_dir(out_dir.join(name))
+            // Opt-in, generated here so that they are compiled as well
+            .unwrapped_methods(true)
+            .ws_security(ws_security)
+            .ws_addressing(ws_addressing)
+            .generate()
+            .unwrap_or_else(|e| panic!("Failed to generate SOAP client from {}: {}", name, e));
+    }
+}
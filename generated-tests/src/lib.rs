@@ -0,0 +1,43 @@
+//! Clients generated from the WSDLs in `testdata/wsdl`
+//!
+//! The build script generates them like a user's build script would, with
+//! unwrapped methods, so this crate only compiles if the generated code does. The tests in `tests/`
+//! exercise the generated types against the runtime. Clients applying security policies or using
+//! WS-Addressing are only generated with the `ws-security` or `ws-addressing` feature.
+
+/// Generated from `collisions.wsdl`: schema types named like generated enums
+pub mod collisions {
+    include!(concat!(env!("OUT_DIR"), "/collisions/soap_client.rs"));
+}
+
+/// Generated from `ledger.wsdl`: operations with security policies
+#[cfg(feature = "ws-security")]
+pub mod ledger {
+    include!(concat!(env!("OUT_DIR"), "/ledger/soap_client.rs"));
+}
+
+/// Generated from `orders.wsdl`: operations with typed faults
+pub mod orders {
+    include!(concat!(env!("OUT_DIR"), "/orders/soap_client.rs"));
+}
+
+/// Generated from `payments.wsdl`: choices nested in sequences and repeated
+pub mod payments {
+    include!(concat!(env!("OUT_DIR"), "/payments/soap_client.rs"));
+}
+
+/// Generated from `reservations.wsdl`: content assembled from named groups, one of them repeated
+pub mod reservations {
+    include!(concat!(env!("OUT_DIR"), "/reservations/soap_client.rs"));
+}
+
+/// Generated from `tickets.wsdl`: a binding using WS-Addressing with the actions of the portType
+#[cfg(feature = "ws-addressing")]
+pub mod tickets {
+    include!(concat!(env!("OUT_DIR"), "/tickets/soap_client.rs"));
+}
+
+/// Generated from `wrapped.wsdl`: operations following the wrapped convention or not
+pub mod wrapped {
+    include!(concat!(env!("OUT_DIR"), "/wrapped/soap_client.rs"));
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "ws-addressing")]
+
+mod common;
+
+use common::serve_once;
+use generated_tests::tickets;
+
+#[tokio::test]
+async fn test_action_sent_as_soap_action_and_wsa_action() {
+    let (endpoint, server) = serve_once(
+        "200 OK",
+        r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <CloseTicketResponse xmlns="http://tempuri.org/"/>
+  </soap:Body>
+</soap:Envelope>"#,
+    );
+    let client = tickets::TicketService::new(endpoint);
+
+    client
+        .close_ticket(tickets::CloseTicket { ticket_id: Some(7) })
+        .await
+        .unwrap();
+
+    // The binding declares no soapAction, both headers carry the wsam:Action
+    let request = server.join().unwrap();
+    assert!(request.contains("soapaction: \"urn:tickets:close\""));
+    assert!(request.contains(">urn:tickets:close</wsa:Action>"));
+    assert!(request.contains("<wsa:MessageID"));
+}
@@ -0,0 +1,64 @@
+use generated_tests::payments::*;
+use soapus_runtime::SoapEnvelope;
+
+#[test]
+fn test_repeated_choice_round_trip() {
+    let payer = Payer {
+        name: "Jane".to_string(),
+        choice: vec![
+            PayerChoice::Email("jane@example.com".to_string()),
+            PayerChoice::CustomerNumber(42),
+            PayerChoice::Email("j.doe@example.com".to_string()),
+        ],
+        reference: Some("R-1".to_string()),
+    };
+
+    let envelope = SoapEnvelope::build_soap11(&payer, None, false).unwrap();
+    // Alternatives keep their order and repetitions
+    assert!(envelope.contains(
+        "<name>Jane</name><email>jane@example.com</email><customerNumber>42</customerNumber><email>j.doe@example.com</email><reference>R-1</reference>"
+    ));
+
+    let parsed: Payer = SoapEnvelope::parse_response(&envelope).unwrap();
+    assert_eq!(parsed, payer);
+}
+
+#[test]
+fn test_choice_content_round_trip() {
+    let request = SubmitPayment {
+        amount: 12.5,
+        method: PaymentMethod {
+            choice: PaymentMethodChoice::Card(Card {
+                number: "4111".to_string(),
+                expiry: "12/30".to_string(),
+            }),
+        },
+        payer: Payer {
+            name: "Jane".to_string(),
+            choice: vec![PayerChoice::Phone("+49 30 1234".to_string())],
+            reference: Some("R-2".to_string()),
+        },
+    };
+
+    let envelope = SoapEnvelope::build_soap11(&request, Some(TARGET_NAMESPACE), true).unwrap();
+    assert!(envelope
+        .contains("<method><card><number>4111</number><expiry>12/30</expiry></card></method>"));
+
+    let parsed: SubmitPayment = SoapEnvelope::parse_response(&envelope).unwrap();
+    assert_eq!(parsed, request);
+}
+
+#[test]
+fn test_optional_choice_absent() {
+    let xml = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <SubmitPaymentResponse xmlns="http://example.com/payments">
+      <transactionId>T-1</transactionId>
+    </SubmitPaymentResponse>
+  </soap:Body>
+</soap:Envelope>"#;
+
+    let response: SubmitPaymentResponse = SoapEnvelope::parse_response(xml).unwrap();
+    assert_eq!(response.transaction_id, "T-1");
+    assert_eq!(response.choice, None);
+}
@@ -0,0 +1,50 @@
+use generated_tests::collisions::*;
+use soapus_runtime::SoapEnvelope;
+
+#[test]
+fn test_generated_enums_keep_clear_of_schema_types() {
+    // The schema types keep their names, the generated enums are renamed
+    let response = GetPayerResponse {
+        payer: Payer {
+            name: "Jane".to_string(),
+            choice: CollisionsPayerChoice::Email("jane@example.com".to_string()),
+        },
+        payer_choice: PayerChoice {
+            label: "private".to_string(),
+        },
+        party: CollisionsAnyParty::Company(Company {
+            name: "ACME".to_string(),
+            vat_id: "DE123".to_string(),
+        }),
+        any_party: AnyParty {
+            note: "none".to_string(),
+        },
+    };
+
+    let envelope = SoapEnvelope::build_soap11(&response, Some(TARGET_NAMESPACE), true).unwrap();
+    let parsed: GetPayerResponse = SoapEnvelope::parse_response(&envelope).unwrap();
+    assert_eq!(parsed, response);
+}
+
+#[test]
+fn test_fault_enum_named_apart_from_fault_detail() {
+    let xml = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <soap:Fault>
+      <faultcode>soap:Client</faultcode>
+      <faultstring>Unknown payer</faultstring>
+      <detail>
+        <GetPayerError xmlns="http://example.com/collisions"><reason>unknown</reason></GetPayerError>
+      </detail>
+    </soap:Fault>
+  </soap:Body>
+</soap:Envelope>"#;
+
+    let error = CollisionsGetPayerError::from(SoapEnvelope::check_for_fault(xml).unwrap_err());
+    match error {
+        CollisionsGetPayerError::NotFound(GetPayerError { reason }) => {
+            assert_eq!(reason, "unknown")
+        }
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
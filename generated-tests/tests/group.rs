@@ -0,0 +1,71 @@
+use generated_tests::reservations::*;
+use soapus_runtime::SoapEnvelope;
+
+fn guest() -> AnyGuest {
+    AnyGuest::Guest(Guest {
+        id: "G-1".to_string(),
+        given_name: "Jane".to_string(),
+        surname: "Doe".to_string(),
+        birth_date: Some("1990-01-01".to_string()),
+        email: Some("jane@example.com".to_string()),
+        phone: Some("+49 30 1234".to_string()),
+    })
+}
+
+#[test]
+fn test_repeated_group_round_trip() {
+    let reservation = Reservation {
+        created_by: None,
+        hotel_code: "BER-1".to_string(),
+        card_token: Some("tok_1".to_string()),
+        voucher: Some("V-7".to_string()),
+        guest: vec![guest()],
+        choice: vec![
+            ReservationChoice::Arrival("2026-11-01".to_string()),
+            ReservationChoice::Nights(2),
+            ReservationChoice::Arrival("2026-12-24".to_string()),
+            ReservationChoice::Nights(3),
+        ],
+    };
+
+    let envelope = SoapEnvelope::build_soap11(&reservation, None, false).unwrap();
+    // Each repetition of the group keeps its elements together
+    assert!(envelope.contains(
+        "<arrival>2026-11-01</arrival><nights>2</nights><arrival>2026-12-24</arrival><nights>3</nights>"
+    ));
+
+    let parsed: Reservation = SoapEnvelope::parse_response(&envelope).unwrap();
+    assert_eq!(parsed, reservation);
+}
+
+#[test]
+fn test_repeated_group_interleaved_response() {
+    let xml = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <FindReservationResponse xmlns="http://example.com/reservations">
+      <reservation>
+        <hotelCode>BER-1</hotelCode>
+        <cardToken>tok_1</cardToken>
+        <guest><id>G-1</id><givenName>Jane</givenName><surname>Doe</surname></guest>
+        <arrival>2026-11-01</arrival>
+        <nights>2</nights>
+        <arrival>2026-12-24</arrival>
+        <nights>3</nights>
+      </reservation>
+    </FindReservationResponse>
+  </soap:Body>
+</soap:Envelope>"#;
+
+    let response: FindReservationResponse = SoapEnvelope::parse_response(xml).unwrap();
+    let reservation = response.reservation.unwrap();
+    assert_eq!(reservation.card_token.as_deref(), Some("tok_1"));
+    assert_eq!(
+        reservation.choice,
+        vec![
+            ReservationChoice::Arrival("2026-11-01".to_string()),
+            ReservationChoice::Nights(2),
+            ReservationChoice::Arrival("2026-12-24".to_string()),
+            ReservationChoice::Nights(3),
+        ]
+    );
+}
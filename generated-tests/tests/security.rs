@@ -0,0 +1,68 @@
+#![cfg(feature = "ws-security")]
+
+mod common;
+
+use common::serve_once;
+use generated_tests::ledger;
+use soapus_runtime::security::{PasswordType, UsernameToken, WsSecurity};
+use soapus_runtime::{SoapClient, SoapError};
+
+#[tokio::test]
+async fn test_policy_applied_to_call() {
+    let (endpoint, server) = serve_once(
+        "200 OK",
+        r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <GetBalanceResponse xmlns="http://example.com/ledger">
+      <Balance>12.5</Balance>
+    </GetBalanceResponse>
+  </soap:Body>
+</soap:Envelope>"#,
+    );
+    let client = ledger::LedgerService::with_client(
+        SoapClient::builder(endpoint)
+            .ws_security(WsSecurity::new().with_username_token(UsernameToken::new(
+                "admin",
+                "secret",
+                PasswordType::Text,
+            )))
+            .build(),
+    );
+
+    let response = client
+        .get_balance(ledger::GetBalance {
+            account: "4711".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.balance, 12.5);
+
+    // The policy asks for a password digest and a timestamp
+    let request = server.join().unwrap();
+    assert!(request.contains("#PasswordDigest\">"));
+    assert!(!request.contains(">secret<"));
+    assert!(request.contains("<wsu:Timestamp"));
+}
+
+#[tokio::test]
+async fn test_policy_requires_credentials() {
+    // Fails before sending, the endpoint is never reached
+    let client = ledger::LedgerService::new("http://127.0.0.1:9/service");
+
+    let error = client
+        .get_balance(ledger::GetBalance {
+            account: "4711".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SoapError::SecurityError(_)));
+
+    let error = client
+        .post_entry(ledger::PostEntry {
+            account: "4711".to_string(),
+            amount: 12.5,
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SoapError::SecurityError(_)));
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::serve_once;
+use generated_tests::{orders, wrapped};
+
+#[tokio::test]
+async fn test_unwrapped_arguments_and_result() {
+    let (endpoint, server) = serve_once(
+        "200 OK",
+        r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <SearchResponse xmlns="http://example.com/search">
+      <hit>first</hit>
+      <hit>second</hit>
+    </SearchResponse>
+  </soap:Body>
+</soap:Envelope>"#,
+    );
+    let client = wrapped::SearchService::new(endpoint);
+
+    // A child named like a Rust keyword becomes a raw identifier argument
+    let hits = client
+        .search_unwrapped(
+            "news".to_string(),
+            "rust".to_string(),
+            Some(5),
+            Some(vec!["a".to_string(), "b".to_string()]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(hits, Some(vec!["first".to_string(), "second".to_string()]));
+
+    let request = server.join().unwrap();
+    assert!(request.contains("soapaction: \"http://example.com/search/Search\""));
+    assert!(request.contains(
+        "<Search xmlns=\"http://example.com/search\"><type>news</type><query>rust</query><limit>5</limit><tag>a</tag><tag>b</tag></Search>"
+    ));
+}
+
+#[tokio::test]
+async fn test_unwrapped_empty_elements() {
+    let (endpoint, server) = serve_once(
+        "200 OK",
+        r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <PingResponse xmlns="http://example.com/search"/>
+  </soap:Body>
+</soap:Envelope>"#,
+    );
+    let client = wrapped::SearchService::new(endpoint);
+
+    // Without arguments, and without a child to return the response element itself
+    let response: wrapped::PingResponse = client.ping_unwrapped().await.unwrap();
+    assert_eq!(response, wrapped::PingResponse {});
+
+    let request = server.join().unwrap();
+    assert!(request.contains("<Ping xmlns=\"http://example.com/search\"/>"));
+}
+
+#[tokio::test]
+async fn test_unwrapped_declared_fault() {
+    let (endpoint, server) = serve_once(
+        "500 Internal Server Error",
+        r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <soap:Fault>
+      <faultcode>soap:Client</faultcode>
+      <faultstring>Order already shipped</faultstring>
+      <detail>
+        <AlreadyShippedFault xmlns="http://example.com/orders">2026-10-01</AlreadyShippedFault>
+      </detail>
+    </soap:Fault>
+  </soap:Body>
+</soap:Envelope>"#,
+    );
+    let client = orders::OrderService::new(endpoint);
+
+    // Declared faults keep their variants in the unwrapped method
+    match client.cancel_order_unwrapped("42".to_string()).await {
+        Err(orders::CancelOrderError::AlreadyShipped(date)) => assert_eq!(date, "2026-10-01"),
+        other => panic!("Expected AlreadyShipped, got {:?}", other),
+    }
+
+    let request = server.join().unwrap();
+    assert!(request.contains("<orderId>42</orderId>"));
+}
@@ -4,8 +4,8 @@
 //! and generate Rust code without needing to set up a build.rs.
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
-use soapus_codegen::parser::parse_wsdl;
+use clap::{Args, Parser, Subcommand};
+use soapus_codegen::parser::{parse_wsdl_with_imports, FileSystemLoader};
 use soapus_codegen::SoapClientGenerator;
 use std::fs;
 use std::path::PathBuf;
@@ -49,6 +49,9 @@ enum Commands {
         /// SOAP version (1.1 or 1.2)
         #[arg(short, long, value_name = "VERSION")]
         soap_version: Option<String>,
+
+        #[command(flatten)]
+        options: GeneratorOptions,
     },
 
     /// Show information about a WSDL file
@@ -59,6 +62,26 @@ enum Commands {
     },
 }
 
+/// Opt-in features of the generated code
+#[derive(Args)]
+struct GeneratorOptions {
+    /// Use SOAP encoding for operations bound with use="encoded"
+    #[arg(long)]
+    encoded: bool,
+
+    /// Also generate methods taking the children of wrapped request elements
+    #[arg(long)]
+    unwrapped: bool,
+
+    /// Apply the WS-SecurityPolicy of the WSDL (requires soapus-runtime/ws-security)
+    #[arg(long)]
+    ws_security: bool,
+
+    /// Send WS-Addressing headers and the actions of the WSDL (requires soapus-runtime/ws-addressing)
+    #[arg(long)]
+    ws_addressing: bool,
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
@@ -71,8 +94,9 @@ fn main() -> Result<()> {
             output,
             client_name,
             soap_version,
+            options,
         } => {
-            generate_command(wsdl_path, output, client_name, soap_version)?;
+            generate_command(wsdl_path, output, client_name, soap_version, options)?;
         }
         Commands::Info { wsdl_path } => {
             info_command(wsdl_path)?;
@@ -88,8 +112,12 @@ fn parse_command(wsdl_path: PathBuf, verbose: bool) -> Result<()> {
     let wsdl_content = fs::read_to_string(&wsdl_path)
         .with_context(|| format!("Failed to read WSDL file: {}", wsdl_path.display()))?;
 
-    let model =
-        parse_wsdl(&wsdl_content).map_err(|e| anyhow::anyhow!("Failed to parse WSDL: {}", e))?;
+    let model = parse_wsdl_with_imports(
+        &wsdl_content,
+        &wsdl_path.to_string_lossy(),
+        &FileSystemLoader,
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to parse WSDL: {}", e))?;
 
     println!("✅ WSDL parsed successfully!");
 
@@ -104,7 +132,9 @@ fn parse_command(wsdl_path: PathBuf, verbose: bool) -> Result<()> {
         println!("  Bindings: {}", model.bindings().len());
         println!("  Services: {}", model.services().len());
 
-        if let Some(schema) = model.schema() {
+        let mut schemas: Vec<_> = model.schemas().collect();
+        schemas.sort_by_key(|schema| schema.target_namespace.clone());
+        for schema in schemas {
             println!("\n📐 XSD Schema:");
             println!(
                 "  Target Namespace: {}",
@@ -124,6 +154,7 @@ fn generate_command(
     output: PathBuf,
     client_name: Option<String>,
     soap_version: Option<String>,
+    options: GeneratorOptions,
 ) -> Result<()> {
     println!("🔨 Generating code from WSDL: {}", wsdl_path.display());
     println!("📂 Output directory: {}", output.display());
@@ -152,6 +183,26 @@ fn generate_command(
         println!("📌 SOAP version: {}", version);
     }
 
+    if options.encoded {
+        builder = builder.encoded(true);
+        println!("🔤 SOAP encoding enabled for encoded operations");
+    }
+
+    if options.unwrapped {
+        builder = builder.unwrapped_methods(true);
+        println!("📦 Unwrapped methods enabled for wrapped operations");
+    }
+
+    if options.ws_security {
+        builder = builder.ws_security(true);
+        println!("🔐 WS-SecurityPolicy enforcement enabled");
+    }
+
+    if options.ws_addressing {
+        builder = builder.ws_addressing(true);
+        println!("📨 WS-Addressing enabled for bindings using it");
+    }
+
     // Generate code
     builder.generate().context("Failed to generate code")?;
 
@@ -169,8 +220,12 @@ fn info_command(wsdl_path: PathBuf) -> Result<()> {
     let wsdl_content = fs::read_to_string(&wsdl_path)
         .with_context(|| format!("Failed to read WSDL file: {}", wsdl_path.display()))?;
 
-    let model =
-        parse_wsdl(&wsdl_content).map_err(|e| anyhow::anyhow!("Failed to parse WSDL: {}", e))?;
+    let model = parse_wsdl_with_imports(
+        &wsdl_content,
+        &wsdl_path.to_string_lossy(),
+        &FileSystemLoader,
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to parse WSDL: {}", e))?;
 
     // Service information
     println!("🌐 Services:");
@@ -201,8 +256,16 @@ fn info_command(wsdl_path: PathBuf) -> Result<()> {
     }
 
     // Type information
-    if let Some(schema) = model.schema() {
-        println!("\n📐 Types:");
+    let mut schemas: Vec<_> = model.schemas().collect();
+    schemas.sort_by_key(|schema| schema.target_namespace.clone());
+    for schema in schemas {
+        println!(
+            "\n📐 Types ({}):",
+            schema
+                .target_namespace
+                .as_deref()
+                .unwrap_or("<no namespace>")
+        );
         if !schema.complex_types.is_empty() {
             println!("  Complex Types: {}", schema.complex_types.len());
             for (name, _) in schema.complex_types.iter().take(5) {
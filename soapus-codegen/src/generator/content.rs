@@ -0,0 +1,305 @@
+//! Content of complex types with inherited and referenced parts resolved
+
+use super::type_mapper::is_repeating;
+use super::{CodeGenerator, MAX_INHERITANCE_DEPTH};
+use crate::parser::{
+    Attribute, AttributeUse, Choice, ComplexType, Derivation, GroupRef, QName, Sequence,
+    SequenceElement, XSD_NAMESPACE,
+};
+use std::borrow::Cow;
+
+impl<'a> CodeGenerator<'a> {
+    /// Get a complex type with the content and attributes it inherits or references
+    pub(super) fn flattened<'t>(&self, complex_type: &'t ComplexType) -> Cow<'t, ComplexType> {
+        let has_references = has_content_references(complex_type);
+        if complex_type.base_type.is_none()
+            && complex_type.attribute_groups.is_empty()
+            && !has_references
+        {
+            return Cow::Borrowed(complex_type);
+        }
+
+        let mut flattened = ComplexType {
+            attributes: self.effective_attributes(complex_type, 0),
+            attribute_groups: Vec::new(),
+            ..complex_type.clone()
+        };
+        if complex_type.derivation == Some(Derivation::Extension) || has_references {
+            flattened.sequence = Some(self.effective_content(complex_type, 0));
+            flattened.choice = None;
+        }
+        if complex_type.simple_content.is_some() {
+            flattened.simple_content = Some(
+                self.text_type(complex_type, 0)
+                    .unwrap_or_else(|| QName::with_namespace(XSD_NAMESPACE, "string")),
+            );
+        }
+        Cow::Owned(flattened)
+    }
+
+    /// Resolve the simple type of the text content of a `<simpleContent>` type
+    ///
+    /// The base may itself be a complex type with simple content, whose text
+    /// type is used then.
+    fn text_type(&self, complex_type: &ComplexType, depth: usize) -> Option<QName> {
+        let base = complex_type.simple_content.as_ref()?;
+        match self.wsdl.find_complex_type(base) {
+            Some(base) if depth < MAX_INHERITANCE_DEPTH => self.text_type(base, depth + 1),
+            Some(_) => None,
+            None => Some(base.clone()),
+        }
+    }
+
+    /// Get the attributes of a complex type, including inherited and referenced ones
+    ///
+    /// Extensions and restrictions both inherit the attributes of their base.
+    /// A restriction may redeclare an inherited attribute or remove it with
+    /// `use="prohibited"`.
+    fn effective_attributes(&self, complex_type: &ComplexType, depth: usize) -> Vec<Attribute> {
+        let mut attributes = match &complex_type.base_type {
+            Some(base) if depth < MAX_INHERITANCE_DEPTH => self
+                .wsdl
+                .find_complex_type(base)
+                .map(|base| self.effective_attributes(base, depth + 1))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+
+        let own = complex_type
+            .attributes
+            .iter()
+            .cloned()
+            .chain(self.group_attributes(&complex_type.attribute_groups, 0));
+        for attribute in own {
+            let existing = attributes.iter().position(|a| a.name == attribute.name);
+            match (existing, attribute.use_) {
+                (Some(index), AttributeUse::Prohibited) => {
+                    attributes.remove(index);
+                }
+                (None, AttributeUse::Prohibited) => {}
+                (Some(index), _) => attributes[index] = attribute,
+                (None, _) => attributes.push(attribute),
+            }
+        }
+        attributes
+    }
+
+    /// Collect the attributes of referenced attribute groups, following nested references
+    fn group_attributes(&self, references: &[QName], depth: usize) -> Vec<Attribute> {
+        let mut attributes = Vec::new();
+        for group in references
+            .iter()
+            .filter_map(|reference| self.wsdl.find_attribute_group(reference))
+        {
+            attributes.extend(group.attributes.iter().cloned());
+            if depth < MAX_INHERITANCE_DEPTH {
+                attributes.extend(self.group_attributes(&group.attribute_groups, depth + 1));
+            }
+        }
+        attributes
+    }
+
+    /// Get the content of a complex type as one sequence, including inherited content
+    ///
+    /// For extensions the (recursively resolved) base content comes first,
+    /// followed by the type's own content. Restrictions restate their content
+    /// in full, so nothing is inherited. Group references are expanded and
+    /// element references resolved.
+    pub(super) fn effective_content(&self, complex_type: &ComplexType, depth: usize) -> Sequence {
+        let own = match (&complex_type.sequence, &complex_type.choice) {
+            (Some(sequence), _) => sequence.clone(),
+            (None, Some(choice)) => Sequence {
+                choices: vec![Choice {
+                    position: 0,
+                    ..choice.clone()
+                }],
+                ..Default::default()
+            },
+            (None, None) => Sequence::default(),
+        };
+        let mut own = self.expand_groups(own, 0);
+        let own_elements = own
+            .elements
+            .iter_mut()
+            .chain(own.choices.iter_mut().flat_map(|c| c.elements.iter_mut()));
+        for element in own_elements {
+            self.resolve_element_reference(element);
+        }
+
+        let base = match (&complex_type.derivation, &complex_type.base_type) {
+            (Some(Derivation::Extension), Some(base)) if depth < MAX_INHERITANCE_DEPTH => {
+                self.wsdl.find_complex_type(base)
+            }
+            _ => None,
+        };
+        let Some(base) = base else {
+            return own;
+        };
+
+        let mut content = self.effective_content(base, depth + 1);
+        let offset = content.elements.len();
+        content
+            .choices
+            .extend(own.choices.into_iter().map(|mut choice| {
+                choice.position += offset;
+                choice
+            }));
+        content.elements.extend(own.elements);
+        content
+    }
+
+    /// Replace the group references of a sequence by the content of the groups
+    ///
+    /// The content of each group is inserted where the reference appears,
+    /// between the elements and choices of the sequence. Groups referenced
+    /// with `minOccurs="0"` make their content optional, repeated groups
+    /// make it repeated (see [`apply_group_occurs`]).
+    fn expand_groups(&self, sequence: Sequence, depth: usize) -> Sequence {
+        let mut expanded = Sequence::default();
+        let mut elements = sequence.elements.into_iter();
+        let mut choices = sequence.choices.into_iter().enumerate().peekable();
+        let mut groups = sequence.groups.into_iter().peekable();
+        let mut position = 0;
+        loop {
+            // Choices and groups preceding the next element, in document order
+            loop {
+                let next_choice = choices
+                    .next_if(|(index, choice)| {
+                        choice.position == position
+                            && groups.peek().is_none_or(|group| {
+                                group.position != position || *index < group.choice_index
+                            })
+                    })
+                    .map(|(_, choice)| choice);
+                if let Some(mut choice) = next_choice {
+                    choice.position = expanded.elements.len();
+                    expanded.choices.push(self.expand_choice(choice, depth));
+                    continue;
+                }
+                let Some(group) = groups.next_if(|group| group.position == position) else {
+                    break;
+                };
+                if let Some(content) = self.group_content(&group.reference, depth) {
+                    let content = apply_group_occurs(content, &group);
+                    let offset = expanded.elements.len();
+                    expanded
+                        .choices
+                        .extend(content.choices.into_iter().map(|mut choice| {
+                            choice.position += offset;
+                            choice
+                        }));
+                    expanded.elements.extend(content.elements);
+                }
+            }
+
+            let Some(element) = elements.next() else {
+                break;
+            };
+            expanded.elements.push(element);
+            position += 1;
+        }
+        expanded
+    }
+
+    /// Add the alternatives of the groups referenced in a choice
+    ///
+    /// A group contributes the alternatives of its choice, or its element if
+    /// it consists of a single one. Alternatives made of several elements are
+    /// not supported yet.
+    fn expand_choice(&self, mut choice: Choice, depth: usize) -> Choice {
+        for group in std::mem::take(&mut choice.groups) {
+            let Some(content) = self.group_content(&group.reference, depth) else {
+                continue;
+            };
+            match (content.elements.as_slice(), content.choices.as_slice()) {
+                ([], nested) => {
+                    for alternatives in nested {
+                        choice
+                            .elements
+                            .extend(alternatives.elements.iter().cloned());
+                    }
+                }
+                ([element], []) => choice.elements.push(element.clone()),
+                _ => {}
+            }
+        }
+        choice
+    }
+
+    /// Take the type of an element referencing a global element from its declaration
+    fn resolve_element_reference(&self, element: &mut SequenceElement) {
+        let Some(reference) = &element.reference else {
+            return;
+        };
+        if let Some(declaration) = self.wsdl.find_element(reference) {
+            element.type_ = declaration.type_.clone();
+            element.nillable |= declaration.nillable;
+        } else if self.wsdl.find_complex_type(reference).is_some() {
+            // Declared with an inline complexType, registered under the element's name
+            element.type_ = reference.clone();
+        }
+    }
+
+    /// Get the content of a referenced group, with its own group references expanded
+    fn group_content(&self, reference: &QName, depth: usize) -> Option<Sequence> {
+        if depth >= MAX_INHERITANCE_DEPTH {
+            return None;
+        }
+        let definition = self.wsdl.find_group(reference)?;
+        Some(self.expand_groups(definition.clone(), depth + 1))
+    }
+}
+
+/// Apply the occurrence of a group reference to the content of the group
+///
+/// A repeated group of several elements or choices becomes a repeating
+/// choice between all of their elements: only a list of enum values keeps
+/// the order of interleaved repetitions like `<a/><b/><a/><b/>`.
+fn apply_group_occurs(mut content: Sequence, group: &GroupRef) -> Sequence {
+    if is_repeating(&group.max_occurs) && content.elements.len() + content.choices.len() > 1 {
+        let alternatives = content
+            .choices
+            .into_iter()
+            .flat_map(|choice| choice.elements);
+        let elements = content.elements.into_iter().chain(alternatives).collect();
+        return Sequence {
+            choices: vec![Choice {
+                elements,
+                min_occurs: group.min_occurs,
+                max_occurs: group.max_occurs.clone(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+    }
+    if group.min_occurs == 0 {
+        content.elements.iter_mut().for_each(|e| e.min_occurs = 0);
+        content.choices.iter_mut().for_each(|c| c.min_occurs = 0);
+    }
+    if let Some(max) = group.max_occurs.as_ref().filter(|max| *max != "1") {
+        let occurs = content
+            .elements
+            .iter_mut()
+            .map(|e| &mut e.max_occurs)
+            .chain(content.choices.iter_mut().map(|c| &mut c.max_occurs));
+        for max_occurs in occurs {
+            if max_occurs.as_deref().unwrap_or("1") == "1" {
+                *max_occurs = Some(max.clone());
+            }
+        }
+    }
+    content
+}
+
+/// Check whether the content of a complex type references named groups or global elements
+fn has_content_references(complex_type: &ComplexType) -> bool {
+    let element_refs =
+        |elements: &[SequenceElement]| elements.iter().any(|e| e.reference.is_some());
+    let choice_refs = |choice: &Choice| !choice.groups.is_empty() || element_refs(&choice.elements);
+    complex_type.choice.iter().any(choice_refs)
+        || complex_type.sequence.iter().any(|sequence| {
+            !sequence.groups.is_empty()
+                || element_refs(&sequence.elements)
+                || sequence.choices.iter().any(choice_refs)
+        })
+}
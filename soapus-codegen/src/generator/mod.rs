@@ -2,16 +2,26 @@
 //!
 //! This module transforms parsed WSDL and XSD models into Rust code.
 
+mod content;
+mod naming;
+mod polymorphic;
+mod registry;
 pub mod rust_codegen;
 pub mod type_mapper;
 
+pub use naming::{sanitize_identifier, to_pascal_case, to_snake_case};
+
 use crate::error::Result;
-use crate::parser::{WsdlModel, XmlSchema};
+use crate::parser::{QName, WsdlModel, XmlSchema};
 use crate::SoapClientGenerator;
+use std::collections::HashMap;
 
 #[cfg(feature = "tracing")]
 use tracing::{debug, info};
 
+/// Maximum number of base types or nested group references followed, guards against cycles
+const MAX_INHERITANCE_DEPTH: usize = 32;
+
 /// Generate complete SOAP client code from WSDL model
 pub fn generate_client_code(wsdl: &WsdlModel, config: &SoapClientGenerator) -> Result<String> {
     #[cfg(feature = "tracing")]
@@ -45,10 +55,22 @@ pub struct CodeGenerator<'a> {
 impl<'a> CodeGenerator<'a> {
     /// Create a new code generator
     pub fn new(wsdl: &'a WsdlModel, config: &'a SoapClientGenerator) -> Self {
+        // Types only carry encoding annotations when an encoded operation needs them
+        let mut type_mapper = type_mapper::TypeMapper::new();
+        type_mapper.set_encoded(
+            config.encoded()
+                && wsdl
+                    .bindings()
+                    .iter()
+                    .flat_map(|b| &b.operations)
+                    .any(|op| op.is_rpc() && op.is_encoded()),
+        );
+        type_mapper.set_ws_security(config.ws_security());
+        type_mapper.set_ws_addressing(config.ws_addressing());
         Self {
             wsdl,
             config,
-            type_mapper: type_mapper::TypeMapper::new(),
+            type_mapper,
         }
     }
 
@@ -64,11 +86,12 @@ impl<'a> CodeGenerator<'a> {
         output.push_str(&self.generate_header(schema));
         output.push_str("\n\n");
 
-        // Generate types from XSD schema
-        if let Some(schema) = schema {
+        // Generate types from all XSD schemas
+        if schema.is_some() {
             #[cfg(feature = "tracing")]
-            debug!("Generating types from XSD schema");
-            output.push_str(&self.generate_types(schema)?);
+            debug!("Generating types from XSD schemas");
+            self.register_schema_types();
+            output.push_str(&self.generate_types()?);
             output.push_str("\n\n");
         }
 
@@ -110,12 +133,22 @@ impl<'a> CodeGenerator<'a> {
             .map(|form| form != "unqualified")
             .unwrap_or(true); // Default to qualified if not specified
 
+        // Operations with a fault enum return `Result<_, {Op}Error>` instead
+        let runtime_imports =
+            if self.wsdl.operations().any(|operation| {
+                !rust_codegen::has_fault_enum(operation, self.wsdl, &self.type_mapper)
+            }) {
+                "{SoapClient, SoapResult}"
+            } else {
+                "SoapClient"
+            };
+
         format!(
             r#"// This file is automatically generated from WSDL.
 // DO NOT EDIT - your changes will be overwritten!
 
 use serde::{{Serialize, Deserialize}};
-use soapus_runtime::{{SoapClient, SoapResult}};
+use soapus_runtime::{};
 
 /// Target namespace from WSDL
 pub const TARGET_NAMESPACE: &str = "{}";
@@ -125,43 +158,64 @@ pub const TARGET_NAMESPACE: &str = "{}";
 pub const ELEMENT_FORM_QUALIFIED: bool = {};
 
 "#,
-            namespace, element_form_qualified
+            runtime_imports, namespace, element_form_qualified
         )
     }
 
-    /// Generate Rust structs from XSD schema
-    /// Generate types from XSD schema
-    fn generate_types(&self, schema: &XmlSchema) -> Result<String> {
+    /// Generate types from all XSD schemas
+    fn generate_types(&self) -> Result<String> {
         let mut output = String::new();
 
-        // Generate ComplexTypes as structs
-        #[cfg(feature = "tracing")]
-        debug!(
-            complex_type_count = schema.complex_types.len(),
-            "Generating complex types"
-        );
-        for (name, complex_type) in &schema.complex_types {
-            output.push_str(&rust_codegen::generate_complex_type(
-                name,
-                complex_type,
-                &self.type_mapper,
-            )?);
-            output.push_str("\n\n");
-        }
-
-        // Generate SimpleTypes as enums (if they have enumerations)
-        #[cfg(feature = "tracing")]
-        debug!(
-            simple_type_count = schema.simple_types.len(),
-            "Generating simple types"
-        );
-        for (name, simple_type) in &schema.simple_types {
-            if let Some(enum_code) = rust_codegen::generate_simple_type_enum(name, simple_type)? {
-                output.push_str(&enum_code);
+        // Emit types in a fixed order, so generated code is reproducible
+        for schema in self.ordered_schemas() {
+            // Generate ComplexTypes as structs
+            #[cfg(feature = "tracing")]
+            debug!(
+                target_namespace = ?schema.target_namespace,
+                complex_type_count = schema.complex_types.len(),
+                "Generating complex types"
+            );
+            for (name, complex_type) in sorted_by_name(&schema.complex_types) {
+                let qname = QName::with_namespace(
+                    schema.target_namespace.clone().unwrap_or_default(),
+                    name.clone(),
+                );
+                if self.type_mapper.array_item_type(&qname).is_some() {
+                    continue;
+                }
+                let complex_type = self.flattened(complex_type);
+                output.push_str(&rust_codegen::generate_complex_type(
+                    name,
+                    &complex_type,
+                    schema,
+                    &self.type_mapper,
+                )?);
                 output.push_str("\n\n");
+                output.push_str(&self.generate_base_conversions(name, &complex_type, schema));
+            }
+
+            // Generate SimpleTypes as enums (if they have enumerations)
+            #[cfg(feature = "tracing")]
+            debug!(
+                target_namespace = ?schema.target_namespace,
+                simple_type_count = schema.simple_types.len(),
+                "Generating simple types"
+            );
+            for (name, simple_type) in sorted_by_name(&schema.simple_types) {
+                if let Some(enum_code) = rust_codegen::generate_simple_type_enum(
+                    name,
+                    simple_type,
+                    schema,
+                    &self.type_mapper,
+                )? {
+                    output.push_str(&enum_code);
+                    output.push_str("\n\n");
+                }
             }
         }
 
+        output.push_str(&self.generate_polymorphic_types());
+
         Ok(output)
     }
 
@@ -229,10 +283,40 @@ impl {} {{
             let method_code = method_code.replace("{{service_name}}", service_name);
             output.push_str(&method_code);
             output.push('\n');
+
+            if !self.config.unwrapped_methods() {
+                continue;
+            }
+            if let Some(method_code) =
+                rust_codegen::generate_unwrapped_method(operation, self.wsdl, &self.type_mapper)
+            {
+                output.push_str(&method_code);
+                output.push('\n');
+            }
         }
 
         output.push_str("}\n");
 
+        // Structs holding the parts of RPC and multi-part operations
+        for operation in self.wsdl.operations() {
+            if let Some(part_types) =
+                rust_codegen::generate_part_types(operation, self.wsdl, &self.type_mapper)
+            {
+                output.push('\n');
+                output.push_str(&part_types);
+            }
+        }
+
+        // Error enums of operations with typed faults
+        for operation in self.wsdl.operations() {
+            if let Some(enum_code) =
+                rust_codegen::generate_fault_enum(operation, self.wsdl, &self.type_mapper)
+            {
+                output.push('\n');
+                output.push_str(&enum_code);
+            }
+        }
+
         Ok(output)
     }
 
@@ -268,107 +352,9 @@ impl {} {{
     }
 }
 
-/// Helper to sanitize names for Rust identifiers
-pub fn sanitize_identifier(name: &str) -> String {
-    let mut result = String::new();
-
-    for ch in name.chars() {
-        if ch.is_alphanumeric() || ch == '_' {
-            result.push(ch);
-        } else if ch == '-' || ch == '.' {
-            // Replace invalid chars with underscore
-            result.push('_');
-        }
-        // Skip other invalid characters
-    }
-
-    // Ensure it doesn't start with a number
-    if result.is_empty() {
-        result.push('_');
-    } else if result.chars().next().is_some_and(|c| c.is_numeric()) {
-        result.insert(0, '_');
-    }
-
-    // Check if it's a Rust keyword and escape if needed
-    match result.as_str() {
-        "type" | "mod" | "fn" | "struct" | "enum" | "trait" | "impl" | "use" | "as" | "ref"
-        | "mut" | "const" | "static" | "let" | "match" | "if" | "else" | "while" | "for"
-        | "loop" | "return" | "break" | "continue" | "async" | "await" | "move" | "self"
-        | "Self" | "super" | "crate" | "pub" | "in" | "where" => {
-            format!("r#{}", result)
-        }
-        _ => result,
-    }
-}
-
-/// Convert snake_case or kebab-case to PascalCase
-pub fn to_pascal_case(name: &str) -> String {
-    let mut result = String::new();
-    let mut capitalize_next = true;
-
-    for ch in name.chars() {
-        if ch.is_alphanumeric() {
-            if capitalize_next {
-                result.push_str(&ch.to_uppercase().to_string());
-                capitalize_next = false;
-            } else {
-                result.push(ch);
-            }
-        } else {
-            capitalize_next = true;
-        }
-    }
-
-    result
-}
-
-/// Convert PascalCase or kebab-case to snake_case
-pub fn to_snake_case(name: &str) -> String {
-    let mut result = String::new();
-    let mut prev_is_lower = false;
-
-    for (i, ch) in name.chars().enumerate() {
-        if ch == '-' || ch == '.' {
-            result.push('_');
-            prev_is_lower = false;
-        } else if ch.is_uppercase() {
-            if i > 0 && prev_is_lower {
-                result.push('_');
-            }
-            result.push_str(&ch.to_lowercase().to_string());
-            prev_is_lower = false;
-        } else {
-            result.push(ch);
-            prev_is_lower = ch.is_lowercase();
-        }
-    }
-
-    result
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_sanitize_identifier() {
-        assert_eq!(sanitize_identifier("my-field"), "my_field");
-        assert_eq!(sanitize_identifier("my_field"), "my_field");
-        assert_eq!(sanitize_identifier("123field"), "_123field");
-        assert_eq!(sanitize_identifier("type"), "r#type");
-    }
-
-    #[test]
-    fn test_to_pascal_case() {
-        assert_eq!(to_pascal_case("my_type"), "MyType");
-        assert_eq!(to_pascal_case("my-type"), "MyType");
-        assert_eq!(to_pascal_case("myType"), "MyType");
-    }
-
-    #[test]
-    fn test_to_snake_case() {
-        assert_eq!(to_snake_case("MyType"), "my_type");
-        assert_eq!(to_snake_case("myType"), "my_type");
-        assert_eq!(to_snake_case("my-type"), "my_type");
-    }
+/// Get the entries of a map of schema components ordered by name
+fn sorted_by_name<T>(components: &HashMap<String, T>) -> Vec<(&String, &T)> {
+    let mut components: Vec<_> = components.iter().collect();
+    components.sort_by_key(|(name, _)| *name);
+    components
 }
@@ -0,0 +1,153 @@
+//! Rust names for WSDL and XSD names
+
+use std::collections::HashSet;
+
+/// Pick a Rust name for a schema type that doesn't collide with already used names
+///
+/// On collision the name is prefixed with the last segment of the namespace URI
+/// (e.g. `http://example.com/common` gives `CommonAddress`), then numbered.
+pub(super) fn unique_type_name(
+    used_names: &mut HashSet<String>,
+    namespace: &str,
+    name: &str,
+) -> String {
+    let plain = to_pascal_case(name);
+    let mut candidate = plain.clone();
+    if used_names.contains(&candidate) {
+        let label = namespace
+            .rsplit(['/', ':', '#'])
+            .find(|segment| !segment.is_empty())
+            .map(to_pascal_case)
+            .unwrap_or_default();
+        candidate = format!("{}{}", label, plain);
+        let mut counter = 2;
+        while used_names.contains(&candidate) {
+            candidate = format!("{}{}{}", label, plain, counter);
+            counter += 1;
+        }
+    }
+    used_names.insert(candidate.clone());
+    candidate
+}
+
+/// Helper to sanitize names for Rust identifiers
+pub fn sanitize_identifier(name: &str) -> String {
+    let mut result = String::new();
+
+    for ch in name.chars() {
+        if ch.is_alphanumeric() || ch == '_' {
+            result.push(ch);
+        } else if ch == '-' || ch == '.' {
+            // Replace invalid chars with underscore
+            result.push('_');
+        }
+        // Skip other invalid characters
+    }
+
+    // Ensure it doesn't start with a number
+    if result.is_empty() {
+        result.push('_');
+    } else if result.chars().next().is_some_and(|c| c.is_numeric()) {
+        result.insert(0, '_');
+    }
+
+    // Check if it's a Rust keyword and escape if needed
+    match result.as_str() {
+        "type" | "mod" | "fn" | "struct" | "enum" | "trait" | "impl" | "use" | "as" | "ref"
+        | "mut" | "const" | "static" | "let" | "match" | "if" | "else" | "while" | "for"
+        | "loop" | "return" | "break" | "continue" | "async" | "await" | "move" | "self"
+        | "Self" | "super" | "crate" | "pub" | "in" | "where" => {
+            format!("r#{}", result)
+        }
+        _ => result,
+    }
+}
+
+/// Convert snake_case or kebab-case to PascalCase
+pub fn to_pascal_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut capitalize_next = true;
+
+    for ch in name.chars() {
+        if ch.is_alphanumeric() {
+            if capitalize_next {
+                result.push_str(&ch.to_uppercase().to_string());
+                capitalize_next = false;
+            } else {
+                result.push(ch);
+            }
+        } else {
+            capitalize_next = true;
+        }
+    }
+
+    result
+}
+
+/// Convert PascalCase or kebab-case to snake_case
+pub fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut prev_is_lower = false;
+
+    for (i, ch) in name.chars().enumerate() {
+        if ch == '-' || ch == '.' {
+            result.push('_');
+            prev_is_lower = false;
+        } else if ch.is_uppercase() {
+            if i > 0 && prev_is_lower {
+                result.push('_');
+            }
+            result.push_str(&ch.to_lowercase().to_string());
+            prev_is_lower = false;
+        } else {
+            result.push(ch);
+            prev_is_lower = ch.is_lowercase();
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_identifier() {
+        assert_eq!(sanitize_identifier("my-field"), "my_field");
+        assert_eq!(sanitize_identifier("my_field"), "my_field");
+        assert_eq!(sanitize_identifier("123field"), "_123field");
+        assert_eq!(sanitize_identifier("type"), "r#type");
+    }
+
+    #[test]
+    fn test_to_pascal_case() {
+        assert_eq!(to_pascal_case("my_type"), "MyType");
+        assert_eq!(to_pascal_case("my-type"), "MyType");
+        assert_eq!(to_pascal_case("myType"), "MyType");
+    }
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("MyType"), "my_type");
+        assert_eq!(to_snake_case("myType"), "my_type");
+        assert_eq!(to_snake_case("my-type"), "my_type");
+    }
+
+    #[test]
+    fn test_unique_type_name() {
+        let mut used = HashSet::new();
+        assert_eq!(
+            unique_type_name(&mut used, "http://example.com/orders", "Address"),
+            "Address"
+        );
+        assert_eq!(
+            unique_type_name(&mut used, "http://example.com/common/", "Address"),
+            "CommonAddress"
+        );
+        assert_eq!(
+            unique_type_name(&mut used, "urn:common", "address"),
+            "CommonAddress2"
+        );
+    }
+}
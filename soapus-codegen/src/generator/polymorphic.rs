@@ -0,0 +1,104 @@
+//! Enums and conversions generated for base types with derived types
+
+use super::{rust_codegen, CodeGenerator, MAX_INHERITANCE_DEPTH};
+use crate::parser::{ComplexType, Derivation, QName, XmlSchema};
+
+impl<'a> CodeGenerator<'a> {
+    /// Generate the enums standing in for base types with derived types
+    pub(super) fn generate_polymorphic_types(&self) -> String {
+        let mut output = String::new();
+        for ((namespace, name), derived) in self.derived_types() {
+            let base_name = QName::with_namespace(namespace.clone(), name.clone());
+            let Some(base) = self.wsdl.find_complex_type(&base_name) else {
+                continue;
+            };
+
+            let mut variants = Vec::new();
+            if !base.is_abstract {
+                variants.push(self.polymorphic_variant(&namespace, &name, base, true));
+            }
+            for (derived_namespace, derived_name) in &derived {
+                let qname = QName::with_namespace(derived_namespace.clone(), derived_name.clone());
+                if let Some(complex_type) = self.wsdl.find_complex_type(&qname) {
+                    variants.push(self.polymorphic_variant(
+                        derived_namespace,
+                        derived_name,
+                        complex_type,
+                        false,
+                    ));
+                }
+            }
+
+            output.push_str(&rust_codegen::generate_polymorphic_enum(
+                &self.type_mapper.map_type(&base_name),
+                &name,
+                &variants,
+            ));
+            output.push_str("\n\n");
+        }
+        output
+    }
+
+    /// Describe a complex type as variant of a polymorphic enum
+    fn polymorphic_variant(
+        &self,
+        namespace: &str,
+        name: &str,
+        complex_type: &ComplexType,
+        is_base: bool,
+    ) -> rust_codegen::PolymorphicVariant {
+        rust_codegen::PolymorphicVariant {
+            rust_name: self.type_mapper.type_name(Some(namespace), name),
+            type_name: name.to_string(),
+            namespace: Some(namespace)
+                .filter(|ns| !ns.is_empty())
+                .map(str::to_string),
+            fields: rust_codegen::serde_field_names(&self.flattened(complex_type)),
+            is_base,
+        }
+    }
+
+    /// Generate `From` conversions from a type derived by extension into its base types
+    ///
+    /// Every base type reached through extensions gets a conversion, since all
+    /// of its fields are part of the derived struct. Base types with choices
+    /// are skipped, their choice enums are specific to each struct.
+    pub(super) fn generate_base_conversions(
+        &self,
+        name: &str,
+        complex_type: &ComplexType,
+        schema: &XmlSchema,
+    ) -> String {
+        let mut output = String::new();
+        let derived_name = self
+            .type_mapper
+            .type_name(schema.target_namespace.as_deref(), name);
+
+        let mut current = complex_type;
+        for _ in 0..MAX_INHERITANCE_DEPTH {
+            if current.derivation != Some(Derivation::Extension) {
+                break;
+            }
+            let Some(base_name) = &current.base_type else {
+                break;
+            };
+            let Some(base) = self.wsdl.find_complex_type(base_name) else {
+                break;
+            };
+            if !self.effective_content(base, 0).choices.is_empty() {
+                break;
+            }
+
+            output.push_str(&rust_codegen::generate_from_impl(
+                &derived_name,
+                &self
+                    .type_mapper
+                    .type_name(base_name.namespace(), base_name.local_name()),
+                &self.flattened(base),
+            ));
+            output.push_str("\n\n");
+            current = base;
+        }
+        output
+    }
+}
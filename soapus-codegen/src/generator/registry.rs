@@ -0,0 +1,226 @@
+//! Registration of the Rust names of schema types
+
+use super::naming::unique_type_name;
+use super::{rust_codegen, sorted_by_name, to_pascal_case, CodeGenerator, MAX_INHERITANCE_DEPTH};
+use crate::parser::{
+    ComplexType, QName, Restriction, SimpleType, XmlSchema, SOAP_ENCODING_NAMESPACE,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+impl<'a> CodeGenerator<'a> {
+    /// Register the types of all schemas with the type mapper
+    ///
+    /// This lets field types be resolved by namespace URI, independent of the
+    /// prefix the referencing schema uses. Types with the same name in different
+    /// namespaces get distinct Rust names: the primary schema keeps the plain
+    /// name, the others are prefixed with a label derived from their namespace.
+    /// SimpleTypes without enumerations generate no code and are mapped to
+    /// their (resolved) base type instead.
+    pub(super) fn register_schema_types(&mut self) {
+        let wsdl = self.wsdl;
+        let mut used_names = HashSet::new();
+        let mut restrictions = Vec::new();
+        for schema in self.ordered_schemas() {
+            let namespace = schema.target_namespace.clone().unwrap_or_default();
+
+            let mut named_types: Vec<&String> = schema
+                .complex_types
+                .keys()
+                .chain(
+                    schema
+                        .simple_types
+                        .iter()
+                        .filter(|(_, simple_type)| is_enumeration(simple_type))
+                        .map(|(name, _)| name),
+                )
+                .collect();
+            named_types.sort();
+            for name in named_types {
+                let rust_name = unique_type_name(&mut used_names, &namespace, name);
+                self.type_mapper
+                    .register_type(namespace.clone(), name.clone(), rust_name);
+            }
+
+            for (name, simple_type) in &schema.simple_types {
+                match simple_type {
+                    _ if is_enumeration(simple_type) => {}
+                    SimpleType::Restriction { base, .. } => {
+                        restrictions.push((namespace.clone(), name, base));
+                    }
+                    SimpleType::List { .. } | SimpleType::Union { .. } => self
+                        .type_mapper
+                        .register_type(namespace.clone(), name.clone(), "String"),
+                }
+            }
+        }
+
+        // Restrictions may be based on other restrictions, so resolve until no progress is made
+        while !restrictions.is_empty() {
+            let pending: HashSet<(String, String)> = restrictions
+                .iter()
+                .map(|(namespace, name, _)| (namespace.clone(), (*name).clone()))
+                .collect();
+            let before = restrictions.len();
+            restrictions.retain(|(namespace, name, base)| {
+                let base_key = (
+                    base.namespace().unwrap_or_default().to_string(),
+                    base.local_name().to_string(),
+                );
+                if pending.contains(&base_key) {
+                    return true;
+                }
+                let rust_type = self.type_mapper.map_type(base);
+                self.type_mapper
+                    .register_type(namespace.clone(), (*name).clone(), rust_type);
+                false
+            });
+            if restrictions.len() == before {
+                break;
+            }
+        }
+        for (namespace, name, _) in restrictions {
+            self.type_mapper
+                .register_type(namespace, name.clone(), "String");
+        }
+
+        // SOAP encoded arrays are mapped to a Vec of their items instead of a struct
+        if self.type_mapper.is_encoded() {
+            for schema in wsdl.schemas() {
+                let namespace = schema.target_namespace.clone().unwrap_or_default();
+                for (name, complex_type) in &schema.complex_types {
+                    let Some(item_type) = encoded_array_item_type(complex_type) else {
+                        continue;
+                    };
+                    let is_recursive = item_type.namespace() == Some(namespace.as_str())
+                        && item_type.local_name() == name;
+                    if !is_recursive {
+                        self.type_mapper.register_array_type(
+                            namespace.clone(),
+                            name.clone(),
+                            item_type,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Base types with derived types are referenced through an enum of all of them
+        for (namespace, name) in self.derived_types().into_keys() {
+            let rust_name = self.type_mapper.type_name(Some(&namespace), &name);
+            let enum_name =
+                unique_type_name(&mut used_names, &namespace, &format!("Any{}", rust_name));
+            self.type_mapper
+                .register_polymorphic_type(namespace, name, enum_name);
+        }
+
+        // Enums of choices and of typed faults must not collide with schema types either
+        let mut choice_types = Vec::new();
+        for schema in self.ordered_schemas() {
+            for (name, complex_type) in sorted_by_name(&schema.complex_types) {
+                let flattened = self.flattened(complex_type);
+                let has_choices = flattened.choice.is_some()
+                    || flattened.sequence.iter().any(|s| !s.choices.is_empty());
+                if has_choices {
+                    choice_types.push((schema.target_namespace.clone(), name.clone()));
+                }
+            }
+        }
+        for (namespace, name) in choice_types {
+            let rust_name = self.type_mapper.type_name(namespace.as_deref(), &name);
+            let namespace = namespace.unwrap_or_default();
+            let enum_name =
+                unique_type_name(&mut used_names, &namespace, &format!("{}Choice", rust_name));
+            self.type_mapper
+                .register_choice_enum(namespace, name, enum_name);
+        }
+        let namespace = wsdl.target_namespace().unwrap_or_default();
+        for operation in wsdl.operations() {
+            if rust_codegen::has_fault_enum(operation, wsdl, &self.type_mapper) {
+                let enum_name = unique_type_name(
+                    &mut used_names,
+                    namespace,
+                    &format!("{}Error", to_pascal_case(&operation.name)),
+                );
+                self.type_mapper
+                    .register_fault_enum(operation.name.clone(), enum_name);
+            }
+        }
+    }
+
+    /// Get all schemas, the primary one first and the others by target namespace
+    pub(super) fn ordered_schemas(&self) -> Vec<&'a XmlSchema> {
+        let wsdl = self.wsdl;
+        let mut schemas: Vec<&XmlSchema> = wsdl.schemas().collect();
+        schemas.sort_by_key(|s| {
+            (
+                s.target_namespace.as_deref() != wsdl.target_namespace(),
+                s.target_namespace.clone(),
+            )
+        });
+        schemas
+    }
+
+    /// Collect the types derived from each base type, directly or indirectly
+    ///
+    /// Types are identified by (namespace URI, local name).
+    pub(super) fn derived_types(&self) -> BTreeMap<(String, String), BTreeSet<(String, String)>> {
+        let mut derived: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+        for schema in self.wsdl.schemas() {
+            let namespace = schema.target_namespace.clone().unwrap_or_default();
+            for (name, complex_type) in &schema.complex_types {
+                let key = (namespace.clone(), name.clone());
+                let mut current = complex_type;
+                for _ in 0..MAX_INHERITANCE_DEPTH {
+                    let Some(base_name) = &current.base_type else {
+                        break;
+                    };
+                    let Some(base) = self.wsdl.find_complex_type(base_name) else {
+                        break;
+                    };
+                    let base_key = (
+                        base_name.namespace().unwrap_or_default().to_string(),
+                        base_name.local_name().to_string(),
+                    );
+                    if base_key == key {
+                        break;
+                    }
+                    derived.entry(base_key).or_default().insert(key.clone());
+                    current = base;
+                }
+            }
+        }
+        derived
+    }
+}
+
+/// Check whether a simpleType is an enumeration (generated as a Rust enum)
+fn is_enumeration(simple_type: &SimpleType) -> bool {
+    matches!(
+        simple_type,
+        SimpleType::Restriction { restrictions, .. }
+            if restrictions.iter().any(|r| matches!(r, Restriction::Enumeration(_)))
+    )
+}
+
+/// Get the item type of a SOAP encoded array type
+///
+/// Such types restrict `soapenc:Array`, declaring the item type either with
+/// `wsdl:arrayType` on the `soapenc:arrayType` attribute or as the type of
+/// the only element of their sequence.
+fn encoded_array_item_type(complex_type: &ComplexType) -> Option<QName> {
+    let base = complex_type.base_type.as_ref()?;
+    if base.namespace() != Some(SOAP_ENCODING_NAMESPACE) || base.local_name() != "Array" {
+        return None;
+    }
+    complex_type
+        .attributes
+        .iter()
+        .find_map(|attribute| attribute.array_type.clone())
+        .or_else(|| {
+            complex_type
+                .sequence
+                .as_ref()
+                .and_then(|sequence| sequence.elements.first())
+                .map(|element| element.type_.clone())
+        })
+}
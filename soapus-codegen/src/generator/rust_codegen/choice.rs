@@ -0,0 +1,210 @@
+//! Enums generated for `<choice>`s of complex types
+
+use crate::generator::to_pascal_case;
+use crate::generator::type_mapper::{is_repeating, TypeMapper};
+use crate::parser::{Choice, ComplexType};
+
+/// Get the choices of a complex type and the index of the one held in the `$value` field
+///
+/// This is the first repeating choice, whose order and repetitions only an
+/// enum list can keep, or the first choice if none repeats.
+pub(super) fn content_choices(complex_type: &ComplexType) -> (Vec<&Choice>, Option<usize>) {
+    let choices: Vec<&Choice> = complex_type
+        .choice
+        .iter()
+        .chain(complex_type.sequence.iter().flat_map(|s| &s.choices))
+        .collect();
+    let value_choice = choices
+        .iter()
+        .position(|choice| is_repeated(choice))
+        .or(if choices.is_empty() { None } else { Some(0) });
+    (choices, value_choice)
+}
+
+/// Whether a choice repeats, by itself or through any of its alternatives
+pub(super) fn is_repeated(choice: &Choice) -> bool {
+    is_repeating(&choice.max_occurs) || choice.elements.iter().any(|e| is_repeating(&e.max_occurs))
+}
+
+/// Generate the `$value` field holding a choice
+///
+/// The choice repeats if it or any of its alternatives has maxOccurs > 1.
+pub(super) fn generate_choice_field(choice: &Choice, enum_name: &str) -> String {
+    if is_repeated(choice) {
+        format!(
+            "    #[serde(rename = \"$value\", default)]\n    pub choice: Vec<{}>,\n",
+            enum_name
+        )
+    } else if choice.min_occurs == 0 {
+        format!(
+            "    #[serde(rename = \"$value\")]\n    pub choice: Option<{}>,\n",
+            enum_name
+        )
+    } else {
+        format!(
+            "    #[serde(rename = \"$value\")]\n    pub choice: {},\n",
+            enum_name
+        )
+    }
+}
+
+/// Generate the enum for a choice, with one variant per alternative element
+pub(super) fn generate_choice_enum(
+    type_name: &str,
+    choice: &Choice,
+    enum_name: &str,
+    type_mapper: &TypeMapper,
+) -> String {
+    let mut output = String::new();
+    output.push_str(&format!(
+        "/// Generated from XSD choice in complexType: {}\n",
+        type_name
+    ));
+    output.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+    output.push_str(&format!("pub enum {} {{\n", enum_name));
+    for elem in &choice.elements {
+        output.push_str(&format!("    #[serde(rename = \"{}\")]\n", elem.name));
+        output.push_str(&format!(
+            "    {}({}),\n",
+            to_pascal_case(&elem.name),
+            type_mapper.map_type(&elem.type_)
+        ));
+    }
+    output.push_str("}\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CodegenError;
+    use crate::generator::rust_codegen::generate_complex_type;
+    use crate::parser::{ComplexType, QName, Sequence, SequenceElement, XmlSchema};
+
+    #[test]
+    fn test_generate_struct_with_choice() {
+        let element = |name: &str, type_: &str| SequenceElement {
+            name: name.to_string(),
+            type_: QName::new(type_),
+            min_occurs: 1,
+            max_occurs: None,
+            nillable: false,
+            reference: None,
+        };
+        let complex_type = ComplexType {
+            sequence: Some(Sequence {
+                elements: vec![element("name", "xs:string"), element("note", "xs:string")],
+                choices: vec![
+                    Choice {
+                        elements: vec![
+                            element("email", "xs:string"),
+                            element("phone", "xs:string"),
+                        ],
+                        min_occurs: 1,
+                        max_occurs: Some("unbounded".to_string()),
+                        position: 1,
+                        ..Default::default()
+                    },
+                    Choice {
+                        elements: vec![element("fax", "xs:string")],
+                        min_occurs: 1,
+                        max_occurs: None,
+                        position: 2,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let type_mapper = TypeMapper::new();
+        let code =
+            generate_complex_type("Payer", &complex_type, &XmlSchema::default(), &type_mapper)
+                .unwrap();
+
+        // The first choice becomes a `$value` field at its position in the sequence
+        let name = code.find("pub name: String").unwrap();
+        let choice = code
+            .find("#[serde(rename = \"$value\", default)]\n    pub choice: Vec<PayerChoice>,")
+            .unwrap();
+        let note = code.find("pub note: String").unwrap();
+        assert!(name < choice && choice < note);
+
+        // Further choices fall back to optional fields
+        assert!(code.contains("pub fax: Option<String>"));
+
+        assert!(code.contains("pub enum PayerChoice {"));
+        assert!(code.contains("    #[serde(rename = \"email\")]\n    Email(String),"));
+        assert!(code.contains("    #[serde(rename = \"phone\")]\n    Phone(String),"));
+    }
+
+    #[test]
+    fn test_generate_struct_with_later_repeating_choice() {
+        let element = |name: &str| SequenceElement {
+            name: name.to_string(),
+            type_: QName::new("xs:string"),
+            min_occurs: 1,
+            max_occurs: None,
+            nillable: false,
+            reference: None,
+        };
+        let choice = |names: &[&str], max_occurs: Option<&str>| Choice {
+            elements: names.iter().map(|name| element(name)).collect(),
+            min_occurs: 1,
+            max_occurs: max_occurs.map(str::to_string),
+            ..Default::default()
+        };
+        let complex_type = |choices: Vec<Choice>| ComplexType {
+            sequence: Some(Sequence {
+                elements: vec![element("name")],
+                choices,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let generate = |complex_type: &ComplexType| {
+            generate_complex_type(
+                "Contact",
+                complex_type,
+                &XmlSchema::default(),
+                &TypeMapper::new(),
+            )
+        };
+
+        // The repeating choice is held in the enum list, not the first one
+        let code = generate(&complex_type(vec![
+            choice(&["fax"], None),
+            choice(&["email", "phone"], Some("unbounded")),
+        ]))
+        .unwrap();
+        assert!(code.contains("pub fax: Option<String>"));
+        assert!(code.contains("pub choice: Vec<ContactChoice>,"));
+        assert!(code.contains("    Email(String),\n"));
+        assert!(!code.contains("    Fax(String),\n"));
+
+        // Two repeating choices can't both keep their order and repetitions
+        let error = generate(&complex_type(vec![
+            choice(&["fax"], Some("2")),
+            choice(&["email", "phone"], Some("unbounded")),
+        ]))
+        .unwrap_err();
+        assert!(
+            matches!(&error, CodegenError::UnsupportedFeature { feature } if feature.contains("more than one repeating")),
+            "{:?}",
+            error
+        );
+
+        // An alternative named like a sequence element would need two fields of the same name
+        let error = generate(&complex_type(vec![
+            choice(&["email", "phone"], Some("unbounded")),
+            choice(&["name", "alias"], None),
+        ]))
+        .unwrap_err();
+        assert!(
+            matches!(&error, CodegenError::UnsupportedFeature { feature } if feature.contains("element 'name'")),
+            "{:?}",
+            error
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Error enums generated for operations with typed faults
+
+use super::parts::element_type;
+use crate::generator::to_pascal_case;
+use crate::generator::type_mapper::TypeMapper;
+use crate::parser::{PortTypeOperation, WsdlModel};
+
+/// A `<wsdl:fault>` of an operation whose message has an element part
+pub(super) struct OperationFault {
+    /// Name of the enum variant
+    pub(super) variant: String,
+    /// Local name of the detail element
+    pub(super) element: String,
+    /// Rust type of the detail element
+    pub(super) rust_type: String,
+}
+
+/// Check whether an error enum is generated for an operation, see [`generate_fault_enum`]
+pub fn has_fault_enum(
+    operation: &PortTypeOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> bool {
+    !operation_faults(operation, wsdl, type_mapper).is_empty()
+}
+
+/// Collect the faults of an operation that can be mapped to a type
+///
+/// Faults whose message cannot be found or has no element part are left out,
+/// they surface as plain `SoapError::SoapFault`.
+pub(super) fn operation_faults(
+    operation: &PortTypeOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> Vec<OperationFault> {
+    let mut faults: Vec<OperationFault> = Vec::new();
+    for fault in &operation.faults {
+        let Some(element) = wsdl
+            .find_message(&fault.message)
+            .and_then(|m| m.parts.first())
+            .and_then(|p| p.element.as_ref())
+        else {
+            continue;
+        };
+
+        let rust_type = element_type(element, wsdl, type_mapper);
+        let variant = to_pascal_case(&fault.name);
+        if faults.iter().all(|f| f.variant != variant) {
+            faults.push(OperationFault {
+                variant,
+                element: element.local_name().to_string(),
+                rust_type,
+            });
+        }
+    }
+    faults
+}
+
+/// Generate the error enum of an operation with typed faults
+///
+/// Each declared fault becomes a variant holding the deserialized fault detail,
+/// any other error is kept as `Soap`. Returns `None` if the operation declares
+/// no faults that can be mapped to a type.
+///
+/// Example:
+/// ```ignore
+/// pub enum GetOrderError {
+///     OrderNotFound(OrderNotFoundFault),
+///     Soap(soapus_runtime::SoapError),
+/// }
+/// ```
+pub fn generate_fault_enum(
+    operation: &PortTypeOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> Option<String> {
+    let faults = operation_faults(operation, wsdl, type_mapper);
+    if faults.is_empty() {
+        return None;
+    }
+    let enum_name = type_mapper.fault_enum_name(&operation.name);
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "/// Error of the {} operation\n///\n/// SOAP faults declared for the operation are returned as their own variants.\n",
+        operation.name
+    ));
+    output.push_str("#[derive(Debug)]\n");
+    output.push_str(&format!("pub enum {} {{\n", enum_name));
+    for fault in &faults {
+        output.push_str(&format!(
+            "    /// The {} fault\n    {}({}),\n",
+            fault.variant, fault.variant, fault.rust_type
+        ));
+    }
+    output.push_str("    /// Any other error, including undeclared SOAP faults\n");
+    output.push_str("    Soap(soapus_runtime::SoapError),\n");
+    output.push_str("}\n\n");
+
+    // Map SOAP faults by the element of their detail
+    output.push_str(&format!(
+        "impl From<soapus_runtime::SoapError> for {} {{\n",
+        enum_name
+    ));
+    output.push_str("    fn from(error: soapus_runtime::SoapError) -> Self {\n");
+    for fault in &faults {
+        output.push_str(&format!(
+            "        if let Some(detail) = error.fault_detail(\"{}\") {{\n            return Self::{}(detail);\n        }}\n",
+            fault.element, fault.variant
+        ));
+    }
+    output.push_str("        Self::Soap(error)\n");
+    output.push_str("    }\n}\n\n");
+
+    output.push_str(&format!("impl std::fmt::Display for {} {{\n", enum_name));
+    output.push_str(
+        "    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n        match self {\n",
+    );
+    for fault in &faults {
+        output.push_str(&format!(
+            "            Self::{}(_) => write!(f, \"SOAP fault: {}\"),\n",
+            fault.variant, fault.variant
+        ));
+    }
+    output.push_str("            Self::Soap(error) => write!(f, \"{}\", error),\n");
+    output.push_str("        }\n    }\n}\n\n");
+
+    output.push_str(&format!("impl std::error::Error for {} {{\n", enum_name));
+    output.push_str(
+        "    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {\n        match self {\n            Self::Soap(error) => Some(error),\n            _ => None,\n        }\n    }\n}\n",
+    );
+
+    Some(output)
+}
@@ -0,0 +1,247 @@
+//! Struct fields generated for elements and attributes
+
+use crate::generator::type_mapper::TypeMapper;
+use crate::generator::{sanitize_identifier, to_snake_case};
+use crate::parser::{Attribute, AttributeUse, ComplexType, QName, SequenceElement, XmlSchema};
+
+/// Get the Rust field name for a sequence element
+pub(super) fn field_name(elem: &SequenceElement) -> String {
+    sanitize_identifier(&to_snake_case(&elem.name))
+}
+
+/// Generate a struct field for a sequence element
+///
+/// Elements in a namespace other than the one of the enclosing element are
+/// serialized with a namespace declaration, through a function returned
+/// separately to be placed in an `impl` block of the struct. With SOAP
+/// encoding, that function annotates the value with its `xsi:type` instead.
+pub(super) fn generate_field(
+    elem: &SequenceElement,
+    min_occurs: Option<u32>,
+    struct_name: &str,
+    schema: &XmlSchema,
+    type_mapper: &TypeMapper,
+) -> (String, Option<String>) {
+    let mut output = String::new();
+    let sanitized_field_name = field_name(elem);
+    let rust_type =
+        type_mapper.map_type_with_occurs(&elem.type_, min_occurs, &elem.max_occurs, elem.nillable);
+
+    let mut serde_args = Vec::new();
+    // Add serde rename if needed (always rename if we had to sanitize)
+    if sanitized_field_name != elem.name {
+        serde_args.push(format!("rename = \"{}\"", elem.name));
+    }
+    let function = format!(
+        "serialize_{}",
+        sanitized_field_name.trim_start_matches("r#")
+    );
+    let serialize_fn = if type_mapper.is_encoded() {
+        let (serialize_fn, deserialize_args) =
+            encoded_serializer(&function, &elem.type_, &rust_type, type_mapper);
+        serde_args.push(format!(
+            "serialize_with = \"{}::{}\"",
+            struct_name, function
+        ));
+        serde_args.extend(deserialize_args);
+        Some(serialize_fn)
+    } else {
+        declared_namespace(elem, schema).map(|namespace| {
+            serde_args.push(format!(
+                "serialize_with = \"{}::{}\"",
+                struct_name, function
+            ));
+            format!(
+                "    fn {}<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>\n    where\n        T: Serialize + ?Sized,\n        S: serde::Serializer,\n    {{\n        soapus_runtime::namespace::serialize_qualified(value, \"{}\", serializer)\n    }}\n",
+                function, namespace
+            )
+        })
+    };
+    if !serde_args.is_empty() {
+        output.push_str(&format!("    #[serde({})]\n", serde_args.join(", ")));
+    }
+
+    // Field definition
+    output.push_str(&format!(
+        "    pub {}: {},\n",
+        sanitized_field_name, rust_type
+    ));
+    (output, serialize_fn)
+}
+
+/// Generate the serialization function of a field with SOAP encoding
+///
+/// Values of `type_` are written with their `xsi:type`, encoded array types
+/// as `soapenc:Array`. Returns the function and the serde arguments reading
+/// the field back.
+pub(super) fn encoded_serializer(
+    function: &str,
+    type_: &QName,
+    rust_type: &str,
+    type_mapper: &TypeMapper,
+) -> (String, Vec<String>) {
+    if let Some(item_type) = type_mapper.array_item_type(type_) {
+        let item_rust_type = type_mapper.map_type(item_type);
+        let namespace = item_type.namespace().unwrap_or_default();
+        let type_name = item_type.local_name();
+        if rust_type == format!("Option<Vec<{}>>", item_rust_type) {
+            return (
+                format!(
+                    "    fn {}<S: serde::Serializer>(\n        value: &Option<Vec<{}>>,\n        serializer: S,\n    ) -> Result<S::Ok, S::Error> {{\n        soapus_runtime::encoding::serialize_optional_array(value, \"{}\", \"{}\", serializer)\n    }}\n",
+                    function, item_rust_type, namespace, type_name
+                ),
+                vec![
+                    "deserialize_with = \"soapus_runtime::encoding::deserialize_optional_array\""
+                        .to_string(),
+                    "default".to_string(),
+                ],
+            );
+        }
+        if rust_type == format!("Vec<{}>", item_rust_type) {
+            return (
+                format!(
+                    "    fn {}<S: serde::Serializer>(\n        value: &[{}],\n        serializer: S,\n    ) -> Result<S::Ok, S::Error> {{\n        soapus_runtime::encoding::serialize_array(value, \"{}\", \"{}\", serializer)\n    }}\n",
+                    function, item_rust_type, namespace, type_name
+                ),
+                vec![
+                    "deserialize_with = \"soapus_runtime::encoding::deserialize_array\"".to_string(),
+                ],
+            );
+        }
+    }
+
+    (
+        format!(
+            "    fn {}<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>\n    where\n        T: Serialize + ?Sized,\n        S: serde::Serializer,\n    {{\n        soapus_runtime::encoding::serialize_typed(value, \"{}\", \"{}\", serializer)\n    }}\n",
+            function,
+            type_.namespace().unwrap_or_default(),
+            type_.local_name()
+        ),
+        Vec::new(),
+    )
+}
+
+/// Get the namespace an element has to declare, if it differs from the enclosing one
+///
+/// Referenced global elements are qualified with the target namespace of
+/// their schema, while local elements of `schema` are in its target
+/// namespace only if its element form is qualified.
+fn declared_namespace<'e>(elem: &'e SequenceElement, schema: &XmlSchema) -> Option<&'e str> {
+    let namespace = elem
+        .reference
+        .as_ref()?
+        .namespace()
+        .filter(|ns| !ns.is_empty())?;
+    let local_namespace = match schema.element_form_default.as_deref() {
+        Some("unqualified") => None,
+        _ => schema.target_namespace.as_deref(),
+    };
+    (Some(namespace) != local_namespace).then_some(namespace)
+}
+
+/// Get the Rust field name for an attribute
+///
+/// Attributes sharing their name with an element, or with the `value` field
+/// of simple content, get an `_attr` suffix.
+pub(super) fn attribute_field_name(attribute: &Attribute, complex_type: &ComplexType) -> String {
+    let name = sanitize_identifier(&to_snake_case(&attribute.name));
+    let elements = complex_type
+        .sequence
+        .iter()
+        .flat_map(|s| &s.elements)
+        .chain(complex_type.choice.iter().flat_map(|c| &c.elements));
+    let is_taken = |name: &str| {
+        (complex_type.simple_content.is_some() && name == "value")
+            || elements.clone().any(|e| field_name(e) == name)
+    };
+    if is_taken(&name) {
+        format!("{}_attr", name.trim_start_matches("r#"))
+    } else {
+        name
+    }
+}
+
+/// Generate a struct field for an attribute, serialized as XML attribute via an `@` rename
+///
+/// Required attributes are plain fields, optional ones are `Option`s that are
+/// left out when `None`. Attributes with a default or fixed value expressible
+/// as a literal fall back to that value when absent; the function providing
+/// it is returned separately, to be placed in an `impl` block of the struct.
+pub(super) fn generate_attribute_field(
+    attribute: &Attribute,
+    struct_name: &str,
+    complex_type: &ComplexType,
+    type_mapper: &TypeMapper,
+) -> (String, Option<String>) {
+    let mut output = String::new();
+    let name = attribute_field_name(attribute, complex_type);
+
+    let rust_type = attribute
+        .type_
+        .as_ref()
+        .map(|t| type_mapper.map_type(t))
+        .unwrap_or_else(|| "String".to_string());
+
+    let value = attribute.fixed.as_ref().or(attribute.default.as_ref());
+    if let Some(value) = value {
+        let kind = if attribute.fixed.is_some() {
+            "Fixed value"
+        } else {
+            "Default"
+        };
+        output.push_str(&format!("    /// {}: `{}`\n", kind, value));
+    }
+
+    let literal = value.and_then(|v| default_literal(&rust_type, v));
+    match (attribute.use_, literal) {
+        (AttributeUse::Required, _) => {
+            output.push_str(&format!("    #[serde(rename = \"@{}\")]\n", attribute.name));
+            output.push_str(&format!("    pub {}: {},\n", name, rust_type));
+            (output, None)
+        }
+        (_, Some(literal)) => {
+            let default_fn = format!("default_{}", name.trim_start_matches("r#"));
+            output.push_str(&format!(
+                "    #[serde(rename = \"@{}\", default = \"{}::{}\")]\n",
+                attribute.name, struct_name, default_fn
+            ));
+            output.push_str(&format!("    pub {}: {},\n", name, rust_type));
+            let function = format!(
+                "    fn {}() -> {} {{\n        {}\n    }}\n",
+                default_fn, rust_type, literal
+            );
+            (output, Some(function))
+        }
+        (_, None) => {
+            output.push_str(&format!(
+                "    #[serde(rename = \"@{}\", skip_serializing_if = \"Option::is_none\")]\n",
+                attribute.name
+            ));
+            output.push_str(&format!("    pub {}: Option<{}>,\n", name, rust_type));
+            (output, None)
+        }
+    }
+}
+
+/// Get a Rust expression for a default value of a primitive type
+fn default_literal(rust_type: &str, value: &str) -> Option<String> {
+    let value = value.trim();
+    match rust_type {
+        "String" => Some(format!("{:?}.to_string()", value)),
+        "bool" => match value {
+            "true" | "1" => Some("true".to_string()),
+            "false" | "0" => Some("false".to_string()),
+            _ => None,
+        },
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" => value
+            .parse::<i128>()
+            .ok()
+            .map(|v| format!("{}_{}", v, rust_type)),
+        "f32" | "f64" => value
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(|v| format!("{:?}_{}", v, rust_type)),
+        _ => None,
+    }
+}
@@ -0,0 +1,115 @@
+//! Arguments generated for the header parts of operations
+
+use super::parts::element_type;
+use crate::generator::type_mapper::TypeMapper;
+use crate::generator::{sanitize_identifier, to_snake_case};
+use crate::parser::{BindingOperation, WsdlModel};
+
+/// A message part bound to a `<soap:header>` of an operation's input
+pub(super) struct HeaderPart {
+    pub(super) name: String,
+    pub(super) field: String,
+    pub(super) rust_type: String,
+    /// Name of the header element
+    pub(super) element_name: String,
+    /// Namespace of the header element
+    pub(super) namespace: Option<String>,
+}
+
+/// Collect the parts bound to the headers of an operation's input
+///
+/// Parts declared with `element` are sent as their global element, typed
+/// parts as an element named after the part in the namespace of the
+/// `<soap:header>`. Fields clashing with one of `taken` are suffixed with
+/// `_header`. Headers whose message or part cannot be found are left out.
+pub(super) fn header_parts(
+    binding: Option<&BindingOperation>,
+    taken: &[&str],
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> Vec<HeaderPart> {
+    let Some(binding) = binding else {
+        return Vec::new();
+    };
+    binding
+        .input_headers
+        .iter()
+        .filter_map(|header| {
+            let message = wsdl.find_message(&header.message)?;
+            let part = message.parts.iter().find(|part| part.name == header.part)?;
+            let (rust_type, element_name, namespace) = match (&part.type_, &part.element) {
+                (None, Some(element)) => (
+                    element_type(element, wsdl, type_mapper),
+                    element.local_name().to_string(),
+                    element.namespace().map(str::to_string),
+                ),
+                (type_, _) => (
+                    type_
+                        .as_ref()
+                        .map(|type_| type_mapper.map_type(type_))
+                        .unwrap_or_else(|| "String".to_string()),
+                    part.name.clone(),
+                    header.namespace.clone(),
+                ),
+            };
+            let mut field = sanitize_identifier(&to_snake_case(&part.name));
+            if taken.contains(&field.as_str()) {
+                field = format!("{}_header", field.trim_start_matches("r#"));
+            }
+            Some(HeaderPart {
+                name: part.name.clone(),
+                field,
+                rust_type,
+                element_name,
+                namespace,
+            })
+        })
+        .collect()
+}
+
+/// Generate the method arguments taking the header parts
+pub(super) fn header_arguments(headers: &[HeaderPart]) -> String {
+    headers
+        .iter()
+        .map(|header| format!(", {}: {}", header.field, header.rust_type))
+        .collect()
+}
+
+/// Generate the doc comment lines of the arguments taking the header parts
+pub(super) fn header_arguments_doc(headers: &[HeaderPart]) -> String {
+    headers
+        .iter()
+        .map(|header| {
+            format!(
+                "    /// * `{}` - The {} header\n",
+                header.field.trim_start_matches("r#"),
+                header.name
+            )
+        })
+        .collect()
+}
+
+/// Generate the statement serializing the header parts into header blocks
+///
+/// Returns an empty string for operations without headers.
+pub(super) fn header_blocks_statement(headers: &[HeaderPart]) -> String {
+    if headers.is_empty() {
+        return String::new();
+    }
+    let mut output = String::from("        let headers = [\n");
+    for header in headers {
+        output.push_str(&format!(
+            "            soapus_runtime::HeaderBlock::new(\"{}\", &{})?",
+            header.element_name, header.field
+        ));
+        if let Some(namespace) = &header.namespace {
+            output.push_str(&format!(
+                "\n                .with_namespace(\"{}\", ELEMENT_FORM_QUALIFIED)",
+                namespace
+            ));
+        }
+        output.push_str(",\n");
+    }
+    output.push_str("        ];\n");
+    output
+}
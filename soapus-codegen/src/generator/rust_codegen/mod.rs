@@ -0,0 +1,16 @@
+//! Rust code generation from WSDL/XSD models
+
+mod choice;
+mod faults;
+mod fields;
+mod headers;
+mod operations;
+mod parts;
+mod polymorphic;
+mod types;
+
+pub use faults::{generate_fault_enum, has_fault_enum};
+pub use operations::{generate_operation_method, generate_unwrapped_method};
+pub use parts::generate_part_types;
+pub use polymorphic::{generate_from_impl, generate_polymorphic_enum, PolymorphicVariant};
+pub use types::{generate_complex_type, generate_simple_type_enum, serde_field_names};
@@ -0,0 +1,445 @@
+//! Client methods generated for WSDL operations
+
+use super::faults::{has_fault_enum, operation_faults};
+use super::fields::field_name;
+use super::headers::{
+    header_arguments, header_arguments_doc, header_blocks_statement, header_parts, HeaderPart,
+};
+use super::parts::{bound_parts, generate_parts_operation_method, has_part_types};
+use crate::error::Result;
+use crate::generator::type_mapper::TypeMapper;
+use crate::generator::{sanitize_identifier, to_snake_case};
+use crate::parser::{PortTypeOperation, QName, SequenceElement, SoapBody, WsdlModel};
+
+/// Get the Rust method name for an operation
+pub(super) fn method_name(operation: &PortTypeOperation) -> String {
+    sanitize_identifier(&to_snake_case(&operation.name))
+}
+
+/// Generate a client method for a WSDL operation
+pub fn generate_operation_method(
+    operation: &PortTypeOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> Result<String> {
+    let binding = wsdl.find_binding_operation(&operation.name);
+    if let Some(binding) = binding.filter(|b| has_part_types(operation, b, wsdl)) {
+        return Ok(generate_parts_operation_method(
+            operation,
+            binding,
+            wsdl,
+            type_mapper,
+        ));
+    }
+
+    let mut output = String::new();
+
+    // Method name
+    let method_name = method_name(operation);
+
+    // Find input and output message types
+    let input_msg = operation
+        .input
+        .as_ref()
+        .and_then(|qname| wsdl.find_message(qname));
+    let output_msg = operation
+        .output
+        .as_ref()
+        .and_then(|qname| wsdl.find_message(qname));
+
+    // For now, use generic types if we can't resolve
+    let input_body = binding.and_then(|b| b.input.as_ref());
+    let input_type = input_msg
+        .and_then(|m| bound_parts(&m.parts, input_body).next())
+        .and_then(|p| p.element.as_ref())
+        .map(|e| type_mapper.type_name(e.namespace(), e.local_name()))
+        .unwrap_or_else(|| "()".to_string());
+
+    let output_body = binding.and_then(|b| b.output.as_ref());
+    let output_type = output_msg
+        .and_then(|m| bound_parts(&m.parts, output_body).next())
+        .and_then(|p| p.element.as_ref())
+        .map(|e| type_mapper.type_name(e.namespace(), e.local_name()))
+        .unwrap_or_else(|| "()".to_string());
+
+    // Operations with typed faults return their own error enum
+    let faults = operation_faults(operation, wsdl, type_mapper);
+    let error_type = (!faults.is_empty()).then(|| type_mapper.fault_enum_name(&operation.name));
+
+    // Headers declared in the binding become further arguments
+    let headers = header_parts(binding, &["request"], wsdl, type_mapper);
+
+    // Find SOAPAction from WSDL bindings
+    let soap_action = wsdl.find_soap_action(&operation.name);
+
+    // Generate method with better documentation
+    output.push_str(&operation_doc_comment(operation));
+
+    // Add doc comment for parameters if we have type info
+    if input_type != "()" || !headers.is_empty() {
+        output.push_str("    ///\n    /// # Arguments\n");
+    }
+    if input_type != "()" {
+        output.push_str(&format!(
+            "    /// * `request` - The {} request\n",
+            input_type
+        ));
+    }
+    output.push_str(&header_arguments_doc(&headers));
+
+    if let Some(error_type) = &error_type {
+        output.push_str(&format!(
+            "    ///\n    /// # Errors\n    /// Declared SOAP faults are returned as variants of [`{}`]\n",
+            error_type
+        ));
+    }
+
+    // Add tracing instrument attribute for Send compatibility with async
+    let skipped: String = headers
+        .iter()
+        .map(|header| format!(", {}", header.field))
+        .collect();
+    output.push_str(&format!(
+        "    #[cfg_attr(feature = \"tracing\", tracing::instrument(skip(self, request{})))]\n",
+        skipped
+    ));
+
+    let (return_type, map_error) = match &error_type {
+        Some(error_type) => (
+            format!("Result<{}, {}>", output_type, error_type),
+            format!(".map_err({}::from)", error_type),
+        ),
+        None => (format!("SoapResult<{}>", output_type), String::new()),
+    };
+    output.push_str(&format!(
+        "    pub async fn {}(&self, request: {}{}) -> {} {{\n",
+        method_name,
+        input_type,
+        header_arguments(&headers),
+        return_type
+    ));
+    output.push_str(&header_blocks_statement(&headers));
+    let policy = security_policy_statement(operation, wsdl, type_mapper);
+    output.push_str(&policy);
+    let client = client_expression(operation, wsdl, type_mapper, &headers, !policy.is_empty());
+
+    // Use call_with_soap_action with namespace and optional SOAPAction
+    // Pass ELEMENT_FORM_QUALIFIED to control namespace handling for child elements
+    if let Some(action) = soap_action {
+        output.push_str(&format!(
+            "        {}.call_with_soap_action(\"{}\", Some(\"{}\"), Some(TARGET_NAMESPACE), ELEMENT_FORM_QUALIFIED, &request).await{}\n",
+            client, operation.name, action, map_error
+        ));
+    } else {
+        output.push_str(&format!(
+            "        {}.call_with_soap_action(\"{}\", None, Some(TARGET_NAMESPACE), ELEMENT_FORM_QUALIFIED, &request).await{}\n",
+            client, operation.name, map_error
+        ));
+    }
+
+    output.push_str("    }\n");
+
+    Ok(output)
+}
+
+/// Generate a method taking the children of a wrapped request element as arguments
+///
+/// Document/literal operations following the wrapped convention have a
+/// single body part whose element is named after the operation and holds a
+/// plain sequence of local elements. The method builds that element from its
+/// arguments and calls the method of [`generate_operation_method`]. A single
+/// child of the response element is returned instead of the element.
+///
+/// Returns `None` for operations not following the convention.
+pub fn generate_unwrapped_method(
+    operation: &PortTypeOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> Option<String> {
+    let binding = wsdl.find_binding_operation(&operation.name);
+    if binding.is_some_and(|b| has_part_types(operation, b, wsdl)) {
+        return None;
+    }
+
+    let input_element = body_element(
+        operation.input.as_ref(),
+        binding.and_then(|b| b.input.as_ref()),
+        wsdl,
+    )
+    .filter(|element| element.local_name() == operation.name)?;
+    let children = wrapper_children(input_element, wsdl)?;
+    let input_type = type_mapper.type_name(input_element.namespace(), input_element.local_name());
+
+    let output_element = body_element(
+        operation.output.as_ref(),
+        binding.and_then(|b| b.output.as_ref()),
+        wsdl,
+    );
+    let output_child = match output_element.and_then(|element| wrapper_children(element, wsdl)) {
+        Some([child]) => Some(child),
+        _ => None,
+    };
+    // Children are typed like the fields of the element's struct
+    let child_type = |child: &SequenceElement| {
+        type_mapper.map_type_with_occurs(
+            &child.type_,
+            Some(child.min_occurs),
+            &child.max_occurs,
+            child.nillable,
+        )
+    };
+    let output_type = match (output_child, output_element) {
+        (Some(child), _) => child_type(child),
+        (None, Some(element)) => type_mapper.type_name(element.namespace(), element.local_name()),
+        (None, None) => "()".to_string(),
+    };
+
+    let error_type = has_fault_enum(operation, wsdl, type_mapper)
+        .then(|| type_mapper.fault_enum_name(&operation.name));
+    let return_type = match &error_type {
+        Some(error_type) => format!("Result<{}, {}>", output_type, error_type),
+        None => format!("SoapResult<{}>", output_type),
+    };
+
+    let fields: Vec<String> = children.iter().map(field_name).collect();
+    let taken: Vec<&str> = fields.iter().map(String::as_str).collect();
+    let headers = header_parts(binding, &taken, wsdl, type_mapper);
+
+    let method_name = method_name(operation);
+    let mut output = operation_doc_comment(operation);
+    output.push_str(&format!(
+        "    ///\n    /// Takes the children of the `{}` element as arguments, see [`Self::{}`].\n",
+        input_element.local_name(),
+        method_name
+    ));
+    if !children.is_empty() || !headers.is_empty() {
+        output.push_str("    ///\n    /// # Arguments\n");
+        for child in children {
+            output.push_str(&format!(
+                "    /// * `{}` - The {} element\n",
+                field_name(child).trim_start_matches("r#"),
+                child.name
+            ));
+        }
+        output.push_str(&header_arguments_doc(&headers));
+    }
+    output.push_str("    #[cfg_attr(feature = \"tracing\", tracing::instrument(skip_all))]\n");
+
+    let arguments: String = children
+        .iter()
+        .map(|child| format!(", {}: {}", field_name(child), child_type(child)))
+        .collect();
+    output.push_str(&format!(
+        "    pub async fn {}_unwrapped(&self{}{}) -> {} {{\n",
+        to_snake_case(&operation.name),
+        arguments,
+        header_arguments(&headers),
+        return_type
+    ));
+
+    let request = if fields.is_empty() {
+        format!("{} {{}}", input_type)
+    } else {
+        format!("{} {{ {} }}", input_type, fields.join(", "))
+    };
+    let header_values: String = headers
+        .iter()
+        .map(|header| format!(", {}", header.field))
+        .collect();
+    let call = format!("self.{}({}{}).await", method_name, request, header_values);
+    match output_child {
+        Some(child) => output.push_str(&format!(
+            "        {}.map(|response| response.{})\n",
+            call,
+            field_name(child)
+        )),
+        None => output.push_str(&format!("        {}\n", call)),
+    }
+    output.push_str("    }\n");
+    Some(output)
+}
+
+/// Get the element of the single body part of a message
+fn body_element<'m>(
+    message: Option<&QName>,
+    body: Option<&'m SoapBody>,
+    wsdl: &'m WsdlModel,
+) -> Option<&'m QName> {
+    let message = wsdl.find_message(message?)?;
+    match bound_parts(&message.parts, body)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [part] => part.element.as_ref(),
+        _ => None,
+    }
+}
+
+/// Get the children of a wrapper element
+///
+/// Returns `None` unless the type of the element is a plain sequence of
+/// local elements, without attributes, choices, groups, references or a base
+/// type.
+fn wrapper_children<'m>(element: &'m QName, wsdl: &'m WsdlModel) -> Option<&'m [SequenceElement]> {
+    // Elements declared with an inline complexType have a type of the same name
+    let type_ = match wsdl.find_element(element) {
+        Some(declaration) if !declaration.type_.local_name().is_empty() => &declaration.type_,
+        _ => element,
+    };
+    let complex_type = wsdl.find_complex_type(type_)?;
+    let is_plain = complex_type.base_type.is_none()
+        && complex_type.choice.is_none()
+        && complex_type.all.is_none()
+        && complex_type.attributes.is_empty()
+        && complex_type.attribute_groups.is_empty()
+        && complex_type.simple_content.is_none();
+    if !is_plain {
+        return None;
+    }
+    match &complex_type.sequence {
+        Some(sequence)
+            if sequence.choices.is_empty()
+                && sequence.groups.is_empty()
+                && sequence.elements.iter().all(|e| e.reference.is_none()) =>
+        {
+            Some(&sequence.elements)
+        }
+        Some(_) => None,
+        None => Some(&[]),
+    }
+}
+
+/// Generate the doc comment shared by all methods of an operation
+pub(super) fn operation_doc_comment(operation: &PortTypeOperation) -> String {
+    let mut output = format!("    /// Call the {} operation\n", operation.name);
+
+    // Add WSDL documentation if available
+    if let Some(doc) = &operation.documentation {
+        output.push_str("    ///\n");
+        // Split documentation into lines and add as doc comments
+        for line in doc.lines() {
+            let trimmed = line.trim();
+            if !trimmed.is_empty() {
+                output.push_str(&format!("    /// {}\n", trimmed));
+            }
+        }
+    }
+    output
+}
+
+/// The client an operation is called with
+///
+/// The client sends the header blocks if any and meets the security policy
+/// of [`security_policy_statement`] if `secured`. If the type mapper sends
+/// WS-Addressing actions, it sends WS-Addressing headers if the binding uses
+/// them and the `wsam:Action` of the input if the WSDL declares one.
+pub(super) fn client_expression(
+    operation: &PortTypeOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+    headers: &[HeaderPart],
+    secured: bool,
+) -> String {
+    let mut client = String::from("self.client");
+    if !headers.is_empty() {
+        client.push_str(".with_headers(headers)");
+    }
+    if secured {
+        client.push_str(".with_security_policy(&policy)?");
+    }
+    if type_mapper.ws_addressing() {
+        if wsdl.uses_addressing(&operation.name) {
+            client.push_str(".with_addressing_required()");
+        }
+        if let Some(action) = &operation.input_action {
+            client.push_str(&format!(".with_addressing_action(\"{}\")", action));
+        }
+    }
+    client
+}
+
+/// Generate the statement describing the WS-SecurityPolicy of an operation
+///
+/// Returns an empty string unless the type mapper applies security policies,
+/// and for operations without requirements. Signing and encryption are only
+/// required with an `sp:AsymmetricBinding`; the algorithm suite selects the
+/// AES key size.
+pub(super) fn security_policy_statement(
+    operation: &PortTypeOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> String {
+    let Some(policy) = wsdl
+        .security_policy(&operation.name)
+        .filter(|_| type_mapper.ws_security())
+    else {
+        return String::new();
+    };
+    let asymmetric = policy.asymmetric_binding;
+    let mut fields: String = [
+        ("username_token", policy.username_token),
+        (
+            "password_digest",
+            policy.username_token && policy.hash_password,
+        ),
+        ("timestamp", policy.include_timestamp),
+        ("sign_body", asymmetric && policy.sign_body),
+        ("encrypt_body", asymmetric && policy.encrypt_body),
+        ("signed_response", asymmetric && policy.signed_response),
+    ]
+    .iter()
+    .filter(|(_, required)| *required)
+    .map(|(field, _)| format!("            {}: true,\n", field))
+    .collect();
+    let algorithm = match policy.algorithm_suite.as_deref() {
+        Some(suite) if suite.starts_with("Basic256") => Some("Aes256Cbc"),
+        Some(suite) if suite.starts_with("Basic128") => Some("Aes128Cbc"),
+        _ => None,
+    };
+    if let Some(algorithm) = algorithm.filter(|_| asymmetric && policy.encrypt_body) {
+        fields.push_str(&format!(
+            "            encryption_algorithm: Some(soapus_runtime::security::EncryptionAlgorithm::{}),\n",
+            algorithm
+        ));
+    }
+    if fields.is_empty() {
+        return String::new();
+    }
+    format!(
+        "        let policy = soapus_runtime::security::SecurityPolicy {{\n{}            ..Default::default()\n        }};\n",
+        fields
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::QName;
+
+    #[test]
+    fn test_generate_operation_method() {
+        let operation = PortTypeOperation {
+            name: "getAllVersions".to_string(),
+            input: Some(QName::new("tns:getAllVersions")),
+            output: Some(QName::new("tns:getAllVersionsResponse")),
+            input_action: None,
+            output_action: None,
+            faults: vec![],
+            documentation: None,
+        };
+
+        // Create a minimal WsdlModel - we don't need messages for this test
+        // since we're testing method signature generation
+        let wsdl = crate::parser::WsdlModel::default();
+        let type_mapper = TypeMapper::new();
+
+        let code = generate_operation_method(&operation, &wsdl, &type_mapper).unwrap();
+
+        assert!(code.contains("pub async fn get_all_versions"));
+        // When messages aren't found, it falls back to type names from QName
+        assert!(code.contains("GetAllVersions") || code.contains("()"));
+        assert!(code.contains("GetAllVersionsResponse") || code.contains("()"));
+        assert!(code.contains("SoapResult"));
+        assert!(code.contains("/// Call the getAllVersions operation"));
+    }
+}
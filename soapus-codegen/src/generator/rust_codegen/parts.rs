@@ -0,0 +1,368 @@
+//! Structs and methods generated for operations with several message parts
+
+use super::faults::operation_faults;
+use super::fields::encoded_serializer;
+use super::headers::{
+    header_arguments, header_arguments_doc, header_blocks_statement, header_parts,
+};
+use super::operations::{
+    client_expression, method_name, operation_doc_comment, security_policy_statement,
+};
+use crate::generator::type_mapper::TypeMapper;
+use crate::generator::{sanitize_identifier, to_pascal_case, to_snake_case};
+use crate::parser::{BindingOperation, MessagePart, PortTypeOperation, QName, SoapBody, WsdlModel};
+
+/// The parts of a message bound to the SOAP body, all of them without binding
+pub(super) fn bound_parts<'m>(
+    parts: &'m [MessagePart],
+    body: Option<&'m SoapBody>,
+) -> impl Iterator<Item = &'m MessagePart> {
+    parts
+        .iter()
+        .filter(move |part| body.is_none_or(|body| body.binds_part(&part.name)))
+}
+
+/// A part of a message bound to the SOAP body
+///
+/// RPC style parts are child elements of the operation element, document
+/// style parts are the children of the body.
+struct BodyPart {
+    name: String,
+    field: String,
+    rust_type: String,
+    /// XSD type of a part declared with `type`
+    type_: Option<QName>,
+    /// Global element of a part declared with `element`
+    element: Option<QName>,
+}
+
+impl BodyPart {
+    /// Name of the element holding the part
+    ///
+    /// RPC style and typed parts are named after the part, others after
+    /// their global element.
+    fn element_name(&self, rpc: bool) -> &str {
+        match &self.element {
+            Some(element) if !rpc => element.local_name(),
+            _ => &self.name,
+        }
+    }
+}
+
+/// Collect the body parts of an input or output message
+fn body_parts(
+    message: Option<&QName>,
+    body: Option<&SoapBody>,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> Vec<BodyPart> {
+    let Some(message) = message.and_then(|qname| wsdl.find_message(qname)) else {
+        return Vec::new();
+    };
+    bound_parts(&message.parts, body)
+        .map(|part| {
+            let rust_type = match (&part.type_, &part.element) {
+                (Some(type_), _) => type_mapper.map_type(type_),
+                (None, Some(element)) => element_type(element, wsdl, type_mapper),
+                (None, None) => "String".to_string(),
+            };
+            BodyPart {
+                name: part.name.clone(),
+                field: sanitize_identifier(&to_snake_case(&part.name)),
+                rust_type,
+                type_: part.type_.clone(),
+                element: part.element.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Whether the parts of an operation's messages are held by generated structs
+///
+/// This is the case for RPC style operations, and for document style
+/// operations with more than one body part in their input or output.
+pub(super) fn has_part_types(
+    operation: &PortTypeOperation,
+    binding: &BindingOperation,
+    wsdl: &WsdlModel,
+) -> bool {
+    let part_count = |message: Option<&QName>, body: Option<&SoapBody>| {
+        message
+            .and_then(|qname| wsdl.find_message(qname))
+            .map_or(0, |message| bound_parts(&message.parts, body).count())
+    };
+    binding.is_rpc()
+        || part_count(operation.input.as_ref(), binding.input.as_ref()) > 1
+        || part_count(operation.output.as_ref(), binding.output.as_ref()) > 1
+}
+
+/// Names of the structs holding the input and output parts of an operation
+///
+/// These are `<Operation>Request` and `<Operation>Response`, unless a schema
+/// type already has the name. RPC style structs then become
+/// `<Operation>RpcRequest`, document style ones `<Operation>PartsRequest`.
+fn part_struct_names(
+    operation: &PortTypeOperation,
+    rpc: bool,
+    type_mapper: &TypeMapper,
+) -> (String, String) {
+    let base = to_pascal_case(&operation.name);
+    let infix = if rpc { "Rpc" } else { "Parts" };
+    let name = |suffix: &str| {
+        let name = format!("{}{}", base, suffix);
+        if type_mapper.is_type_name_taken(&name) {
+            format!("{}{}{}", base, infix, suffix)
+        } else {
+            name
+        }
+    };
+    (name("Request"), name("Response"))
+}
+
+/// Generate a client method for an operation whose parts are held by structs
+///
+/// The body parts of the input message become arguments of the method. With
+/// the RPC style, they are serialized as unqualified child elements of an
+/// element named after the operation, in the namespace of the binding's
+/// `<soap:body>`. With the document style, each part is a child of the SOAP
+/// body in the namespace of its element. A single output part is returned as
+/// is, several parts as the response struct. RPC operations bound with
+/// `use="encoded"` are called with SOAP encoding if the type mapper
+/// generates encoded types.
+pub(super) fn generate_parts_operation_method(
+    operation: &PortTypeOperation,
+    binding: &BindingOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> String {
+    let method_name = method_name(operation);
+    let input_parts = body_parts(
+        operation.input.as_ref(),
+        binding.input.as_ref(),
+        wsdl,
+        type_mapper,
+    );
+    let output_parts = body_parts(
+        operation.output.as_ref(),
+        binding.output.as_ref(),
+        wsdl,
+        type_mapper,
+    );
+    let (request_name, response_name) = part_struct_names(operation, binding.is_rpc(), type_mapper);
+    let taken: Vec<&str> = input_parts.iter().map(|part| part.field.as_str()).collect();
+    let headers = header_parts(Some(binding), &taken, wsdl, type_mapper);
+
+    let faults = operation_faults(operation, wsdl, type_mapper);
+    let error_type = (!faults.is_empty()).then(|| type_mapper.fault_enum_name(&operation.name));
+
+    let output_type = match output_parts.as_slice() {
+        _ if operation.output.is_none() => "()".to_string(),
+        [part] => part.rust_type.clone(),
+        _ => response_name.clone(),
+    };
+
+    let mut output = operation_doc_comment(operation);
+    if !input_parts.is_empty() || !headers.is_empty() {
+        output.push_str("    ///\n    /// # Arguments\n");
+        for part in &input_parts {
+            output.push_str(&format!(
+                "    /// * `{}` - The {} part\n",
+                part.field.trim_start_matches("r#"),
+                part.name
+            ));
+        }
+        output.push_str(&header_arguments_doc(&headers));
+    }
+    if let Some(error_type) = &error_type {
+        output.push_str(&format!(
+            "    ///\n    /// # Errors\n    /// Declared SOAP faults are returned as variants of [`{}`]\n",
+            error_type
+        ));
+    }
+    output.push_str("    #[cfg_attr(feature = \"tracing\", tracing::instrument(skip_all))]\n");
+
+    let arguments: String = input_parts
+        .iter()
+        .map(|part| format!(", {}: {}", part.field, part.rust_type))
+        .collect();
+    let return_type = match &error_type {
+        Some(error_type) => format!("Result<{}, {}>", output_type, error_type),
+        None => format!("SoapResult<{}>", output_type),
+    };
+    output.push_str(&format!(
+        "    pub async fn {}(&self{}{}) -> {} {{\n",
+        method_name,
+        arguments,
+        header_arguments(&headers),
+        return_type
+    ));
+
+    output.push_str(&format!(
+        "        let request = {} {{ {} }};\n",
+        request_name,
+        taken.join(", ")
+    ));
+    output.push_str(&header_blocks_statement(&headers));
+    let policy = security_policy_statement(operation, wsdl, type_mapper);
+    output.push_str(&policy);
+    let client = client_expression(operation, wsdl, type_mapper, &headers, !policy.is_empty());
+
+    let soap_action = wsdl
+        .find_soap_action(&operation.name)
+        .map(|action| format!("Some(\"{}\")", action))
+        .unwrap_or_else(|| "None".to_string());
+    let map_error = match &error_type {
+        Some(error_type) => format!(".map_err({}::from)", error_type),
+        None => String::new(),
+    };
+    let call = if binding.is_rpc() {
+        // Parts are unqualified, only the operation element is in the body namespace
+        let namespace = binding
+            .input
+            .as_ref()
+            .and_then(|body| body.namespace.as_deref())
+            .map(|namespace| format!("\"{}\"", namespace))
+            .unwrap_or_else(|| "TARGET_NAMESPACE".to_string());
+        if type_mapper.is_encoded() && binding.is_encoded() {
+            format!(
+                "{}.call_encoded(\"{}\", {}, {}, &request).await{}",
+                client, operation.name, soap_action, namespace, map_error
+            )
+        } else {
+            format!(
+                "{}.call_with_soap_action(\"{}\", {}, Some({}), false, &request).await{}",
+                client, operation.name, soap_action, namespace, map_error
+            )
+        }
+    } else {
+        let namespaces: Vec<String> = input_parts
+            .iter()
+            .map(|part| {
+                let namespace = part.element.as_ref().and_then(|e| e.namespace());
+                format!("\"{}\"", namespace.unwrap_or_default())
+            })
+            .collect();
+        format!(
+            "{}.call_parts(\"{}\", {}, &[{}], ELEMENT_FORM_QUALIFIED, &request).await{}",
+            client,
+            operation.name,
+            soap_action,
+            namespaces.join(", "),
+            map_error
+        )
+    };
+
+    match output_parts.as_slice() {
+        _ if operation.output.is_none() => {
+            output.push_str(&format!("        {}\n", call));
+        }
+        [part] => {
+            output.push_str(&format!(
+                "        let response: {} = {}?;\n        Ok(response.{})\n",
+                response_name, call, part.field
+            ));
+        }
+        _ => output.push_str(&format!("        {}\n", call)),
+    }
+    output.push_str("    }\n");
+    output
+}
+
+/// Generate the structs holding the input and output parts of an operation
+///
+/// Returns `None` unless the operation is bound with the RPC style or has
+/// several document style body parts. RPC structs are the request and
+/// response elements wrapping the parts. Parts of encoded operations are
+/// annotated with their type if the type mapper generates encoded types.
+pub fn generate_part_types(
+    operation: &PortTypeOperation,
+    wsdl: &WsdlModel,
+    type_mapper: &TypeMapper,
+) -> Option<String> {
+    let binding = wsdl
+        .find_binding_operation(&operation.name)
+        .filter(|b| has_part_types(operation, b, wsdl))?;
+    let rpc = binding.is_rpc();
+    let encoded = type_mapper.is_encoded() && binding.is_encoded();
+    let (request_name, response_name) = part_struct_names(operation, rpc, type_mapper);
+
+    let mut output = String::new();
+    let messages = [
+        (
+            operation.input.as_ref(),
+            binding.input.as_ref(),
+            request_name,
+            operation.name.clone(),
+        ),
+        (
+            operation.output.as_ref(),
+            binding.output.as_ref(),
+            response_name,
+            format!("{}Response", operation.name),
+        ),
+    ];
+    for (message, body, struct_name, element_name) in messages {
+        let Some(message) = message else {
+            continue;
+        };
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        if rpc {
+            output.push_str(&format!(
+                "/// Element `{}` of the {} RPC operation\n",
+                element_name, operation.name
+            ));
+        } else {
+            output.push_str(&format!(
+                "/// Body parts of the `{}` message of the {} operation\n",
+                message.local_name(),
+                operation.name
+            ));
+        }
+        output.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        if rpc {
+            output.push_str(&format!("#[serde(rename = \"{}\")]\n", element_name));
+        }
+        output.push_str(&format!("pub struct {} {{\n", struct_name));
+        let mut functions = String::new();
+        for part in body_parts(Some(message), body, wsdl, type_mapper) {
+            let mut serde_args = Vec::new();
+            if part.field != part.element_name(rpc) {
+                serde_args.push(format!("rename = \"{}\"", part.element_name(rpc)));
+            }
+            if let Some(type_) = part.type_.as_ref().filter(|_| encoded) {
+                let function = format!("serialize_{}", part.field.trim_start_matches("r#"));
+                let (serialize_fn, deserialize_args) =
+                    encoded_serializer(&function, type_, &part.rust_type, type_mapper);
+                serde_args.push(format!(
+                    "serialize_with = \"{}::{}\"",
+                    struct_name, function
+                ));
+                serde_args.extend(deserialize_args);
+                functions.push_str(&serialize_fn);
+            }
+            if !serde_args.is_empty() {
+                output.push_str(&format!("    #[serde({})]\n", serde_args.join(", ")));
+            }
+            output.push_str(&format!("    pub {}: {},\n", part.field, part.rust_type));
+        }
+        output.push_str("}\n");
+        if !functions.is_empty() {
+            output.push_str(&format!("\nimpl {} {{\n{}}}\n", struct_name, functions));
+        }
+    }
+    Some(output)
+}
+
+/// Get the Rust type of a global element
+///
+/// Elements declared with an inline complexType have a type of the same name.
+pub(super) fn element_type(element: &QName, wsdl: &WsdlModel, type_mapper: &TypeMapper) -> String {
+    match wsdl.find_element(element) {
+        Some(declaration) if !declaration.type_.local_name().is_empty() => {
+            type_mapper.map_type(&declaration.type_)
+        }
+        _ => type_mapper.type_name(element.namespace(), element.local_name()),
+    }
+}
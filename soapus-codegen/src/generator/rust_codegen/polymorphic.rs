@@ -0,0 +1,177 @@
+//! Enums generated for base types with derived types
+
+use super::fields::{attribute_field_name, field_name};
+use crate::parser::ComplexType;
+
+/// Generate a `From` conversion from a derived struct into its base struct
+///
+/// `base` is the flattened base type without choices; all of its fields are
+/// fields of the derived struct as well.
+pub fn generate_from_impl(derived_name: &str, base_name: &str, base: &ComplexType) -> String {
+    let mut output = String::new();
+    output.push_str(&format!(
+        "/// Convert `{}` into its base type `{}`\n",
+        derived_name, base_name
+    ));
+    output.push_str(&format!(
+        "impl From<{}> for {} {{\n",
+        derived_name, base_name
+    ));
+    let elements = base
+        .sequence
+        .as_ref()
+        .map(|s| s.elements.as_slice())
+        .unwrap_or_default();
+    let fields: Vec<String> = base
+        .attributes
+        .iter()
+        .map(|a| attribute_field_name(a, base))
+        .chain(base.simple_content.iter().map(|_| "value".to_string()))
+        .chain(elements.iter().map(field_name))
+        .collect();
+    if fields.is_empty() {
+        output.push_str(&format!(
+            "    fn from(_: {}) -> Self {{\n        Self {{}}\n    }}\n",
+            derived_name
+        ));
+    } else {
+        output.push_str(&format!(
+            "    fn from(value: {}) -> Self {{\n        Self {{\n",
+            derived_name
+        ));
+        for field in fields {
+            output.push_str(&format!("            {}: value.{},\n", field, field));
+        }
+        output.push_str("        }\n    }\n");
+    }
+    output.push_str("}\n");
+    output
+}
+
+/// A variant of the enum generated for a base type with derived types
+#[derive(Debug, Clone)]
+pub struct PolymorphicVariant {
+    /// Rust name of the variant's struct
+    pub rust_name: String,
+    /// XML name of the type, as given in `xsi:type`
+    pub type_name: String,
+    /// Namespace URI of the type
+    pub namespace: Option<String>,
+    /// Serde names of the struct's fields
+    pub fields: Vec<String>,
+    /// Whether this is the base type itself, which is sent without `xsi:type`
+    pub is_base: bool,
+}
+
+/// Generate the enum standing in for a base type and its derived types
+///
+/// The runtime selects the variant from the `xsi:type` attribute when
+/// deserializing; derived variants are serialized with `xsi:type`.
+pub fn generate_polymorphic_enum(
+    enum_name: &str,
+    base_type: &str,
+    variants: &[PolymorphicVariant],
+) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "/// Generated from XSD complexType: {} and its derived types\n",
+        base_type
+    ));
+    output.push_str("///\n/// The variant is selected by the `xsi:type` attribute.\n");
+    output.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+    output.push_str(&format!("pub enum {} {{\n", enum_name));
+    for variant in variants {
+        output.push_str(&format!(
+            "    {}({}),\n",
+            variant.rust_name, variant.rust_name
+        ));
+    }
+    output.push_str("}\n\n");
+
+    // Field names of all variants, in order of first appearance
+    let mut fields: Vec<&str> = Vec::new();
+    for field in variants.iter().flat_map(|v| &v.fields) {
+        if !fields.contains(&field.as_str()) {
+            fields.push(field);
+        }
+    }
+    let fields: Vec<String> = fields.iter().map(|f| format!("\"{}\"", f)).collect();
+
+    output.push_str(&format!(
+        "impl soapus_runtime::xsi::XsiTypeDispatch for {} {{\n",
+        enum_name
+    ));
+    output.push_str(&format!(
+        "    const BASE_TYPE: &'static str = \"{}\";\n",
+        base_type
+    ));
+    output.push_str(&format!(
+        "    const FIELDS: &'static [&'static str] = &[{}];\n\n",
+        fields.join(", ")
+    ));
+    output.push_str("    fn deserialize_variant<'de, D>(\n");
+    output.push_str("        type_name: Option<&str>,\n");
+    output.push_str("        deserializer: D,\n");
+    output.push_str("    ) -> Result<Self, D::Error>\n");
+    output.push_str("    where\n        D: serde::Deserializer<'de>,\n    {\n");
+    output.push_str("        match type_name {\n");
+    for variant in variants {
+        let pattern = if variant.is_base {
+            format!("None | Some(\"{}\")", variant.type_name)
+        } else {
+            format!("Some(\"{}\")", variant.type_name)
+        };
+        output.push_str(&format!(
+            "            {} => {}::deserialize(deserializer).map(Self::{}),\n",
+            pattern, variant.rust_name, variant.rust_name
+        ));
+    }
+    output.push_str(
+        "            _ => Err(soapus_runtime::xsi::unknown_type(type_name, Self::BASE_TYPE)),\n",
+    );
+    output.push_str("        }\n    }\n}\n\n");
+
+    output.push_str(&format!("impl Serialize for {} {{\n", enum_name));
+    output.push_str(
+        "    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {\n",
+    );
+    output.push_str("        match self {\n");
+    for variant in variants {
+        if variant.is_base {
+            output.push_str(&format!(
+                "            Self::{}(value) => value.serialize(serializer),\n",
+                variant.rust_name
+            ));
+        } else {
+            let namespace = match &variant.namespace {
+                Some(namespace) => format!("Some(\"{}\")", namespace),
+                None => "None".to_string(),
+            };
+            output.push_str(&format!(
+                "            Self::{}(value) => soapus_runtime::xsi::XsiTyped::new(\"{}\", {}, value)\n                .serialize(serializer),\n",
+                variant.rust_name, variant.type_name, namespace
+            ));
+        }
+    }
+    output.push_str("        }\n    }\n}\n\n");
+
+    output.push_str(&format!(
+        "impl<'de> Deserialize<'de> for {} {{\n",
+        enum_name
+    ));
+    output.push_str(
+        "    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {\n",
+    );
+    output.push_str("        soapus_runtime::xsi::deserialize_typed(deserializer)\n");
+    output.push_str("    }\n}\n");
+
+    for variant in variants {
+        output.push_str(&format!(
+            "\nimpl From<{}> for {} {{\n    fn from(value: {}) -> Self {{\n        Self::{}(value)\n    }}\n}}\n",
+            variant.rust_name, enum_name, variant.rust_name, variant.rust_name
+        ));
+    }
+
+    output
+}
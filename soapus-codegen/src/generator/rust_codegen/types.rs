@@ -0,0 +1,469 @@
+//! Structs and enums generated for XSD types
+
+use super::choice::{content_choices, generate_choice_enum, generate_choice_field, is_repeated};
+use super::fields::{generate_attribute_field, generate_field};
+use crate::error::{CodegenError, Result};
+use crate::generator::to_pascal_case;
+use crate::generator::type_mapper::TypeMapper;
+use crate::parser::{ComplexType, SimpleType, XmlSchema};
+
+/// Generate a Rust struct from XSD complexType
+///
+/// The struct name is the one registered with `type_mapper` for the type in
+/// `schema`'s target namespace.
+///
+/// One `<choice>` of the type becomes a field of a generated enum type
+/// (`<Struct>Choice`), which quick-xml serializes and deserializes by element
+/// name (`$value`). A struct can only hold one such field, so the alternatives
+/// of any further choice become optional fields.
+///
+/// # Errors
+///
+/// Returns [`CodegenError::UnsupportedFeature`] if more than one choice of the
+/// type repeats, or if an element name is used for more than one field, as
+/// such content can't be represented without losing elements.
+pub fn generate_complex_type(
+    name: &str,
+    complex_type: &ComplexType,
+    schema: &XmlSchema,
+    type_mapper: &TypeMapper,
+) -> Result<String> {
+    let mut output = String::new();
+
+    let elements = complex_type
+        .sequence
+        .as_ref()
+        .map(|s| s.elements.as_slice())
+        .unwrap_or_default();
+    let (choices, value_choice) = content_choices(complex_type);
+    if choices
+        .iter()
+        .enumerate()
+        .any(|(index, choice)| Some(index) != value_choice && is_repeated(choice))
+    {
+        return Err(CodegenError::UnsupportedFeature {
+            feature: format!(
+                "more than one repeating xsd:choice in complexType '{}'",
+                name
+            ),
+        });
+    }
+    let mut field_names = serde_field_names(complex_type);
+    field_names.sort();
+    if let Some(pair) = field_names.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(CodegenError::UnsupportedFeature {
+            feature: format!(
+                "element '{}' used more than once in complexType '{}'",
+                pair[0], name
+            ),
+        });
+    }
+
+    // Doc comment
+    output.push_str(&format!("/// Generated from XSD complexType: {}\n", name));
+
+    // Derives - add Default for empty types
+    let is_empty = elements.is_empty()
+        && choices.is_empty()
+        && complex_type.attributes.is_empty()
+        && complex_type.simple_content.is_none();
+
+    // Derives: Always use PartialEq (not Eq) to avoid issues with floats
+    // in nested types that we might not detect recursively
+    if is_empty {
+        output.push_str("#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]\n");
+    } else {
+        output.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+    }
+
+    // Add serde rename if the Rust struct name differs from XML name
+    let struct_name = type_mapper.type_name(schema.target_namespace.as_deref(), name);
+    if struct_name != name {
+        output.push_str(&format!("#[serde(rename = \"{}\")]\n", name));
+    }
+
+    // Struct definition
+    output.push_str(&format!("pub struct {} {{\n", struct_name));
+
+    // Attributes first, as they are written into the start tag
+    let mut functions = String::new();
+    for attribute in &complex_type.attributes {
+        let (field, default_fn) =
+            generate_attribute_field(attribute, &struct_name, complex_type, type_mapper);
+        output.push_str(&field);
+        functions.push_str(&default_fn.unwrap_or_default());
+    }
+
+    // Text content of simple content types
+    if let Some(text_type) = &complex_type.simple_content {
+        let rust_type = type_mapper.map_type(text_type);
+        if rust_type == "String" {
+            // An empty element has no text
+            output.push_str("    #[serde(rename = \"$text\", default)]\n");
+        } else {
+            output.push_str("    #[serde(rename = \"$text\")]\n");
+        }
+        output.push_str(&format!("    pub value: {},\n", rust_type));
+    }
+
+    // Fields from sequence, with choices at their position
+    let choice_enum = type_mapper.choice_enum_name(schema.target_namespace.as_deref(), name);
+    for position in 0..=elements.len() {
+        for (index, choice) in choices
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.position == position)
+        {
+            if Some(index) == value_choice {
+                output.push_str(&generate_choice_field(choice, &choice_enum));
+            } else {
+                // Only one `$value` field per struct, fall back to optional fields
+                for elem in &choice.elements {
+                    let (field, serialize_fn) =
+                        generate_field(elem, Some(0), &struct_name, schema, type_mapper);
+                    output.push_str(&field);
+                    functions.push_str(&serialize_fn.unwrap_or_default());
+                }
+            }
+        }
+        if let Some(elem) = elements.get(position) {
+            let (field, serialize_fn) = generate_field(
+                elem,
+                Some(elem.min_occurs),
+                &struct_name,
+                schema,
+                type_mapper,
+            );
+            output.push_str(&field);
+            functions.push_str(&serialize_fn.unwrap_or_default());
+        }
+    }
+
+    // If no fields, we already added Default derive above
+
+    output.push_str("}\n");
+
+    if !functions.is_empty() {
+        output.push_str(&format!("\nimpl {} {{\n{}}}\n", struct_name, functions));
+    }
+
+    if let Some(choice) = value_choice.map(|index| choices[index]) {
+        output.push('\n');
+        output.push_str(&generate_choice_enum(
+            name,
+            choice,
+            &choice_enum,
+            type_mapper,
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Get the serde names of the fields generated for a complex type
+///
+/// Mirrors [`generate_complex_type`]: `@`-prefixed attribute names, `$text`
+/// for simple content, element names, `$value` for the choice held in an enum
+/// and the element names of further choices.
+pub fn serde_field_names(complex_type: &ComplexType) -> Vec<String> {
+    let elements = complex_type
+        .sequence
+        .as_ref()
+        .map(|s| s.elements.as_slice())
+        .unwrap_or_default();
+    let (choices, value_choice) = content_choices(complex_type);
+
+    let mut names: Vec<String> = complex_type
+        .attributes
+        .iter()
+        .map(|a| format!("@{}", a.name))
+        .chain(
+            complex_type
+                .simple_content
+                .iter()
+                .map(|_| "$text".to_string()),
+        )
+        .chain(elements.iter().map(|e| e.name.clone()))
+        .collect();
+    if value_choice.is_some() {
+        names.push("$value".to_string());
+        names.extend(
+            choices
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| Some(*index) != value_choice)
+                .flat_map(|(_, c)| &c.elements)
+                .map(|e| e.name.clone()),
+        );
+    }
+    names
+}
+
+/// Generate a Rust enum from XSD simpleType with enumerations
+pub fn generate_simple_type_enum(
+    name: &str,
+    simple_type: &SimpleType,
+    schema: &XmlSchema,
+    type_mapper: &TypeMapper,
+) -> Result<Option<String>> {
+    match simple_type {
+        SimpleType::Restriction {
+            base: _,
+            restrictions,
+        } => {
+            // Check if we have enumerations
+            let enums: Vec<String> = restrictions
+                .iter()
+                .filter_map(|r| match r {
+                    crate::parser::Restriction::Enumeration(val) => Some(val.clone()),
+                    _ => None,
+                })
+                .collect();
+
+            if enums.is_empty() {
+                return Ok(None);
+            }
+
+            let mut output = String::new();
+            output.push_str(&format!("/// Generated from XSD simpleType: {}\n", name));
+            output.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+            output.push_str(&format!(
+                "pub enum {} {{\n",
+                type_mapper.type_name(schema.target_namespace.as_deref(), name)
+            ));
+
+            for val in enums {
+                let variant = to_pascal_case(&val);
+                output.push_str(&format!("    #[serde(rename = \"{}\")]\n", val));
+                output.push_str(&format!("    {},\n", variant));
+            }
+
+            output.push_str("}\n");
+
+            Ok(Some(output))
+        }
+        _ => Ok(None), // List and Union not supported yet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ComplexType, QName, Sequence, SequenceElement};
+
+    #[test]
+    fn test_generate_simple_struct() {
+        let complex_type = ComplexType {
+            sequence: Some(Sequence {
+                elements: vec![SequenceElement {
+                    name: "userName".to_string(),
+                    type_: QName::new("xs:string"),
+                    min_occurs: 1,
+                    max_occurs: None,
+                    nillable: false,
+                    reference: None,
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let type_mapper = TypeMapper::new();
+        let code =
+            generate_complex_type("User", &complex_type, &XmlSchema::default(), &type_mapper)
+                .unwrap();
+
+        assert!(code.contains("pub struct User"));
+        assert!(code.contains("pub user_name: String"));
+        assert!(code.contains("#[serde(rename = \"userName\")]"));
+        assert!(code.contains("PartialEq"));
+    }
+
+    #[test]
+    fn test_generate_referenced_element_fields() {
+        let element = |namespace: &str, name: &str| SequenceElement {
+            name: name.to_string(),
+            type_: QName::with_namespace("http://www.w3.org/2001/XMLSchema", "string"),
+            min_occurs: 1,
+            reference: Some(QName::with_namespace(namespace, name)),
+            ..Default::default()
+        };
+        let complex_type = ComplexType {
+            sequence: Some(Sequence {
+                elements: vec![
+                    element("urn:orders", "Product"),
+                    element("urn:common", "Note"),
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let schema = XmlSchema {
+            target_namespace: Some("urn:orders".to_string()),
+            element_form_default: Some("qualified".to_string()),
+            ..Default::default()
+        };
+
+        let code =
+            generate_complex_type("Order", &complex_type, &schema, &TypeMapper::new()).unwrap();
+
+        // Only elements of another namespace declare it
+        assert!(code.contains("    #[serde(rename = \"Product\")]\n    pub product: String,"));
+        assert!(code.contains(
+            "    #[serde(rename = \"Note\", serialize_with = \"Order::serialize_note\")]\n    pub note: String,"
+        ));
+        assert!(code.contains("impl Order {\n    fn serialize_note<T, S>("));
+        assert!(code.contains(
+            "soapus_runtime::namespace::serialize_qualified(value, \"urn:common\", serializer)"
+        ));
+    }
+
+    #[test]
+    fn test_generate_empty_struct() {
+        let complex_type = ComplexType::default();
+        let type_mapper = TypeMapper::new();
+        let code = generate_complex_type(
+            "EmptyType",
+            &complex_type,
+            &XmlSchema::default(),
+            &type_mapper,
+        )
+        .unwrap();
+
+        assert!(code.contains("pub struct EmptyType"));
+        assert!(code.contains("Default"));
+        assert!(code.contains("PartialEq"));
+    }
+
+    #[test]
+    fn test_generate_struct_with_optional_field() {
+        let complex_type = ComplexType {
+            sequence: Some(Sequence {
+                elements: vec![SequenceElement {
+                    name: "optionalField".to_string(),
+                    type_: QName::new("xs:string"),
+                    min_occurs: 0,
+                    max_occurs: None,
+                    nillable: false,
+                    reference: None,
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let type_mapper = TypeMapper::new();
+        let code = generate_complex_type(
+            "TestType",
+            &complex_type,
+            &XmlSchema::default(),
+            &type_mapper,
+        )
+        .unwrap();
+
+        assert!(code.contains("pub optional_field: Option<String>"));
+    }
+
+    #[test]
+    fn test_generate_struct_with_array_field() {
+        let complex_type = ComplexType {
+            sequence: Some(Sequence {
+                elements: vec![SequenceElement {
+                    name: "items".to_string(),
+                    type_: QName::new("xs:string"),
+                    min_occurs: 0,
+                    max_occurs: Some("unbounded".to_string()),
+                    nillable: false,
+                    reference: None,
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let type_mapper = TypeMapper::new();
+        let code = generate_complex_type(
+            "TestType",
+            &complex_type,
+            &XmlSchema::default(),
+            &type_mapper,
+        )
+        .unwrap();
+
+        assert!(code.contains("pub items: Option<Vec<String>>"));
+    }
+
+    #[test]
+    fn test_generate_struct_with_float_no_eq() {
+        let complex_type = ComplexType {
+            sequence: Some(Sequence {
+                elements: vec![SequenceElement {
+                    name: "price".to_string(),
+                    type_: QName::new("xs:double"),
+                    min_occurs: 1,
+                    max_occurs: None,
+                    nillable: false,
+                    reference: None,
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let type_mapper = TypeMapper::new();
+        let code = generate_complex_type(
+            "Product",
+            &complex_type,
+            &XmlSchema::default(),
+            &type_mapper,
+        )
+        .unwrap();
+
+        assert!(code.contains("pub price: f64"));
+        assert!(code.contains("PartialEq"));
+        // Floats are handled - no Eq is derived anywhere anymore
+    }
+
+    #[test]
+    fn test_generate_struct_with_multiple_fields() {
+        let complex_type = ComplexType {
+            sequence: Some(Sequence {
+                elements: vec![
+                    SequenceElement {
+                        name: "Code".to_string(),
+                        type_: QName::new("xs:int"),
+                        min_occurs: 1,
+                        max_occurs: None,
+                        nillable: false,
+                        reference: None,
+                    },
+                    SequenceElement {
+                        name: "Message".to_string(),
+                        type_: QName::new("xs:string"),
+                        min_occurs: 1,
+                        max_occurs: None,
+                        nillable: false,
+                        reference: None,
+                    },
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let type_mapper = TypeMapper::new();
+        let code = generate_complex_type(
+            "ServiceException",
+            &complex_type,
+            &XmlSchema::default(),
+            &type_mapper,
+        )
+        .unwrap();
+
+        assert!(code.contains("pub struct ServiceException"));
+        assert!(code.contains("pub code: i32"));
+        assert!(code.contains("pub message: String"));
+        assert!(code.contains("#[serde(rename = \"Code\")]"));
+        assert!(code.contains("#[serde(rename = \"Message\")]"));
+    }
+}
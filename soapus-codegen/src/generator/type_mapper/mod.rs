@@ -1,12 +1,30 @@
 //! Type mapping from XSD types to Rust types
 
-use crate::parser::QName;
+use crate::parser::{QName, XSD_NAMESPACE};
 use std::collections::HashMap;
 
+mod names;
+
 /// Maps XML Schema types to Rust types
 pub struct TypeMapper {
     /// Custom type mappings (QName -> Rust type)
     custom_mappings: HashMap<String, String>,
+    /// Types defined in the schemas ((namespace URI, local name) -> Rust type)
+    schema_types: HashMap<(String, String), String>,
+    /// Enums standing in for base types with derived types ((namespace URI, local name) -> Rust type)
+    polymorphic_types: HashMap<(String, String), String>,
+    /// SOAP encoded array types ((namespace URI, local name) -> item type)
+    array_types: HashMap<(String, String), QName>,
+    /// Enums of the choices of complex types ((namespace URI, local name) -> Rust type)
+    choice_enums: HashMap<(String, String), String>,
+    /// Error enums of operations with typed faults (operation name -> Rust type)
+    fault_enums: HashMap<String, String>,
+    /// Whether values are serialized with SOAP encoding, annotated with their `xsi:type`
+    encoded: bool,
+    /// Whether generated methods apply the WS-SecurityPolicy of their operation
+    ws_security: bool,
+    /// Whether generated methods send the WS-Addressing action of their operation
+    ws_addressing: bool,
 }
 
 impl TypeMapper {
@@ -14,6 +32,14 @@ impl TypeMapper {
     pub fn new() -> Self {
         Self {
             custom_mappings: HashMap::new(),
+            schema_types: HashMap::new(),
+            polymorphic_types: HashMap::new(),
+            array_types: HashMap::new(),
+            choice_enums: HashMap::new(),
+            fault_enums: HashMap::new(),
+            encoded: false,
+            ws_security: false,
+            ws_addressing: false,
         }
     }
 
@@ -23,13 +49,65 @@ impl TypeMapper {
             .insert(xsd_type.into(), rust_type.into());
     }
 
+    /// Serialize values with SOAP encoding, see [`is_encoded`](Self::is_encoded)
+    pub fn set_encoded(&mut self, encoded: bool) {
+        self.encoded = encoded;
+    }
+
+    /// Check whether generated types annotate their values with `xsi:type`
+    pub fn is_encoded(&self) -> bool {
+        self.encoded
+    }
+
+    /// Apply security policies in generated methods, see [`ws_security`](Self::ws_security)
+    pub fn set_ws_security(&mut self, ws_security: bool) {
+        self.ws_security = ws_security;
+    }
+
+    /// Check whether generated methods apply the WS-SecurityPolicy of the WSDL
+    pub fn ws_security(&self) -> bool {
+        self.ws_security
+    }
+
+    /// Send WS-Addressing actions in generated methods, see [`ws_addressing`](Self::ws_addressing)
+    pub fn set_ws_addressing(&mut self, ws_addressing: bool) {
+        self.ws_addressing = ws_addressing;
+    }
+
+    /// Check whether generated methods send the WS-Addressing actions of the WSDL
+    pub fn ws_addressing(&self) -> bool {
+        self.ws_addressing
+    }
+
     /// Map an XSD type to a Rust type
+    ///
+    /// Names outside the XML Schema namespace are looked up among the registered
+    /// schema types by namespace URI, so the prefix used doesn't matter.
+    /// Everything else is mapped by local name.
     pub fn map_type(&self, qname: &QName) -> String {
         // Check custom mappings first
         if let Some(rust_type) = self.custom_mappings.get(qname.as_str()) {
             return rust_type.clone();
         }
 
+        if qname.namespace() != Some(XSD_NAMESPACE) {
+            let key = (
+                qname.namespace().unwrap_or_default().to_string(),
+                qname.local_name().to_string(),
+            );
+            if let Some(rust_type) = self.polymorphic_types.get(&key) {
+                return rust_type.clone();
+            }
+            if let Some(item_type) = self.array_types.get(&key) {
+                return format!("Vec<{}>", self.map_type(item_type));
+            }
+            if let Some(rust_type) =
+                self.registered_type(qname.namespace().unwrap_or_default(), qname.local_name())
+            {
+                return rust_type.to_string();
+            }
+        }
+
         // Map based on local name (ignoring prefix)
         let local_name = qname.local_name();
 
@@ -98,10 +176,7 @@ impl TypeMapper {
 
     /// Check if a type is a collection based on maxOccurs
     pub fn is_collection(&self, max_occurs: &Option<String>) -> bool {
-        match max_occurs {
-            Some(max) => max == "unbounded" || max.parse::<u32>().unwrap_or(1) > 1,
-            None => false,
-        }
+        is_repeating(max_occurs)
     }
 
     /// Wrap a Rust type with Option if it's optional
@@ -130,7 +205,17 @@ impl TypeMapper {
         max_occurs: &Option<String>,
         nillable: bool,
     ) -> String {
-        let base_type = self.map_type(qname);
+        self.wrap_occurs(self.map_type(qname), min_occurs, max_occurs, nillable)
+    }
+
+    /// Wrap an already mapped Rust type according to occurrence constraints
+    pub fn wrap_occurs(
+        &self,
+        base_type: String,
+        min_occurs: Option<u32>,
+        max_occurs: &Option<String>,
+        nillable: bool,
+    ) -> String {
         let is_optional = self.is_optional(min_occurs, nillable);
         let is_collection = self.is_collection(max_occurs);
 
@@ -219,6 +304,14 @@ impl Default for TypeMapper {
     }
 }
 
+/// Check if a maxOccurs value allows more than one occurrence
+pub(crate) fn is_repeating(max_occurs: &Option<String>) -> bool {
+    match max_occurs {
+        Some(max) => max == "unbounded" || max.parse::<u32>().unwrap_or(1) > 1,
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
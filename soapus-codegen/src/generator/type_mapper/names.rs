@@ -0,0 +1,205 @@
+//! Rust names registered for schema types and generated enums
+
+use super::TypeMapper;
+use crate::generator::to_pascal_case;
+use crate::parser::QName;
+
+impl TypeMapper {
+    /// Register a type defined in a schema under its namespace URI
+    pub fn register_type(
+        &mut self,
+        namespace: impl Into<String>,
+        local_name: impl Into<String>,
+        rust_type: impl Into<String>,
+    ) {
+        self.schema_types
+            .insert((namespace.into(), local_name.into()), rust_type.into());
+    }
+
+    /// Look up a registered schema type by namespace URI and local name
+    pub fn registered_type(&self, namespace: &str, local_name: &str) -> Option<&str> {
+        self.schema_types
+            .get(&(namespace.to_string(), local_name.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Check whether a Rust name is used by a registered schema type or enum
+    pub fn is_type_name_taken(&self, rust_type: &str) -> bool {
+        self.schema_types
+            .values()
+            .chain(self.polymorphic_types.values())
+            .chain(self.choice_enums.values())
+            .chain(self.fault_enums.values())
+            .any(|name| name == rust_type)
+    }
+
+    /// Register the enum used wherever a base type with derived types is referenced
+    ///
+    /// Such references are mapped to the enum by [`map_type`](Self::map_type),
+    /// while [`type_name`](Self::type_name) keeps returning the struct name.
+    pub fn register_polymorphic_type(
+        &mut self,
+        namespace: impl Into<String>,
+        local_name: impl Into<String>,
+        rust_type: impl Into<String>,
+    ) {
+        self.polymorphic_types
+            .insert((namespace.into(), local_name.into()), rust_type.into());
+    }
+
+    /// Register the enum generated for the choice of a complex type
+    pub fn register_choice_enum(
+        &mut self,
+        namespace: impl Into<String>,
+        local_name: impl Into<String>,
+        rust_type: impl Into<String>,
+    ) {
+        self.choice_enums
+            .insert((namespace.into(), local_name.into()), rust_type.into());
+    }
+
+    /// Get the name of the enum generated for the choice of a complex type
+    ///
+    /// Falls back to the Rust name of the type followed by `Choice`.
+    pub fn choice_enum_name(&self, namespace: Option<&str>, local_name: &str) -> String {
+        let key = (
+            namespace.unwrap_or_default().to_string(),
+            local_name.to_string(),
+        );
+        self.choice_enums
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| format!("{}Choice", self.type_name(namespace, local_name)))
+    }
+
+    /// Register the error enum generated for an operation with typed faults
+    pub fn register_fault_enum(
+        &mut self,
+        operation: impl Into<String>,
+        rust_type: impl Into<String>,
+    ) {
+        self.fault_enums.insert(operation.into(), rust_type.into());
+    }
+
+    /// Get the name of the error enum of an operation
+    ///
+    /// Falls back to the PascalCase operation name followed by `Error`.
+    pub fn fault_enum_name(&self, operation: &str) -> String {
+        self.fault_enums
+            .get(operation)
+            .cloned()
+            .unwrap_or_else(|| format!("{}Error", to_pascal_case(operation)))
+    }
+
+    /// Register a SOAP encoded array type (a restriction of `soapenc:Array`)
+    ///
+    /// References to it are mapped to a `Vec` of the item type by
+    /// [`map_type`](Self::map_type).
+    pub fn register_array_type(
+        &mut self,
+        namespace: impl Into<String>,
+        local_name: impl Into<String>,
+        item_type: QName,
+    ) {
+        self.array_types
+            .insert((namespace.into(), local_name.into()), item_type);
+    }
+
+    /// Get the item type of a registered SOAP encoded array type
+    pub fn array_item_type(&self, qname: &QName) -> Option<&QName> {
+        self.array_types.get(&(
+            qname.namespace().unwrap_or_default().to_string(),
+            qname.local_name().to_string(),
+        ))
+    }
+
+    /// Get the Rust name of a schema type, falling back to its PascalCase local name
+    pub fn type_name(&self, namespace: Option<&str>, local_name: &str) -> String {
+        self.registered_type(namespace.unwrap_or_default(), local_name)
+            .map(str::to_string)
+            .unwrap_or_else(|| to_pascal_case(local_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::XSD_NAMESPACE;
+
+    #[test]
+    fn test_map_registered_type() {
+        let mut mapper = TypeMapper::new();
+        mapper.register_type("urn:orders", "string", "OrderString");
+        mapper.register_type("urn:orders", "Address", "Address");
+        mapper.register_type("urn:common", "Address", "CommonAddress");
+
+        // Built-ins are only recognized in the XSD namespace
+        assert_eq!(
+            mapper.map_type(&QName::with_namespace(XSD_NAMESPACE, "xs:string")),
+            "String"
+        );
+        assert_eq!(
+            mapper.map_type(&QName::with_namespace("urn:orders", "o:string")),
+            "OrderString"
+        );
+
+        // The prefix doesn't matter, only the namespace URI
+        assert_eq!(
+            mapper.map_type(&QName::with_namespace("urn:common", "tns:Address")),
+            "CommonAddress"
+        );
+        assert_eq!(
+            mapper.map_type(&QName::with_namespace("urn:orders", "tns:Address")),
+            "Address"
+        );
+        assert_eq!(
+            mapper.type_name(Some("urn:common"), "Address"),
+            "CommonAddress"
+        );
+        assert_eq!(mapper.type_name(None, "order_line"), "OrderLine");
+    }
+
+    #[test]
+    fn test_map_polymorphic_type() {
+        let mut mapper = TypeMapper::new();
+        mapper.register_type("urn:staff", "Employee", "Employee");
+        mapper.register_polymorphic_type("urn:staff", "Employee", "AnyEmployee");
+
+        // References use the enum, the struct keeps its name
+        assert_eq!(
+            mapper.map_type(&QName::with_namespace("urn:staff", "s:Employee")),
+            "AnyEmployee"
+        );
+        assert_eq!(mapper.type_name(Some("urn:staff"), "Employee"), "Employee");
+    }
+
+    #[test]
+    fn test_map_array_type() {
+        let mut mapper = TypeMapper::new();
+        mapper.register_type("urn:quotes", "Quote", "Quote");
+        mapper.register_array_type(
+            "urn:quotes",
+            "ArrayOfQuote",
+            QName::with_namespace("urn:quotes", "tns:Quote"),
+        );
+        mapper.register_array_type(
+            "urn:quotes",
+            "ArrayOfArrayOfQuote",
+            QName::with_namespace("urn:quotes", "tns:ArrayOfQuote"),
+        );
+
+        let array = QName::with_namespace("urn:quotes", "q:ArrayOfQuote");
+        assert_eq!(mapper.map_type(&array), "Vec<Quote>");
+        assert_eq!(
+            mapper.map_type(&QName::with_namespace(
+                "urn:quotes",
+                "q:ArrayOfArrayOfQuote"
+            )),
+            "Vec<Vec<Quote>>"
+        );
+        assert_eq!(
+            mapper.array_item_type(&array).map(QName::local_name),
+            Some("Quote")
+        );
+    }
+}
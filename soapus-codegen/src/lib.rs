@@ -6,6 +6,7 @@
 //! ## Features
 //!
 //! - **WSDL 1.1 Parsing** - Complete support for WSDL definitions, types, messages, bindings, and services
+//! - **Imports & Includes** - Follows `wsdl:import`, `xsd:import` and `xsd:include` across multiple files
 //! - **XSD Schema Support** - ComplexType, SimpleType, sequences, enumerations, and restrictions
 //! - **Idiomatic Rust** - Generates clean, type-safe Rust code with proper naming conventions
 //! - **Build-Time Generation** - Integrates seamlessly with `build.rs` for compile-time safety
@@ -69,7 +70,7 @@ use std::fs;
 use std::path::PathBuf;
 
 pub use error::{CodegenError, Result};
-use parser::parse_wsdl;
+use parser::{parse_wsdl_with_imports, FileSystemLoader};
 
 /// Main entry point for SOAP client code generation
 #[derive(Debug)]
@@ -80,6 +81,10 @@ pub struct SoapClientGenerator {
     client_name: Option<String>,
     generate_tests: bool,
     soap_version: SoapVersion,
+    encoded: bool,
+    unwrapped_methods: bool,
+    ws_security: bool,
+    ws_addressing: bool,
 }
 
 /// SOAP protocol version
@@ -109,13 +114,15 @@ impl SoapClientGenerator {
                 source: e,
             })?;
 
-        // Parse WSDL
-        let wsdl_model =
-            parse_wsdl(&wsdl_content).map_err(|e| CodegenError::WsdlParse(e.to_string()))?;
+        // Parse WSDL and resolve imports relative to the WSDL file
+        let wsdl_model = parse_wsdl_with_imports(
+            &wsdl_content,
+            &self.wsdl_path.to_string_lossy(),
+            &FileSystemLoader,
+        )?;
 
         // Generate code
-        let code = generator::generate_client_code(&wsdl_model, self)
-            .unwrap_or_else(|_| "// Code generation not yet implemented\n".to_string());
+        let code = generator::generate_client_code(&wsdl_model, self)?;
 
         // Write to output file
         let output_file = self.out_dir.join("soap_client.rs");
@@ -146,6 +153,26 @@ impl SoapClientGenerator {
     pub fn generate_tests(&self) -> bool {
         self.generate_tests
     }
+
+    /// Check if RPC/encoded operations are generated with SOAP encoding
+    pub fn encoded(&self) -> bool {
+        self.encoded
+    }
+
+    /// Check if methods taking the children of wrapped request elements are generated
+    pub fn unwrapped_methods(&self) -> bool {
+        self.unwrapped_methods
+    }
+
+    /// Check if generated methods apply the WS-SecurityPolicy of the WSDL
+    pub fn ws_security(&self) -> bool {
+        self.ws_security
+    }
+
+    /// Check if generated methods send the WS-Addressing actions of the WSDL
+    pub fn ws_addressing(&self) -> bool {
+        self.ws_addressing
+    }
 }
 
 /// Builder for configuring SOAP client generation
@@ -156,6 +183,10 @@ pub struct SoapClientGeneratorBuilder {
     client_name: Option<String>,
     generate_tests: bool,
     soap_version: SoapVersion,
+    encoded: bool,
+    unwrapped_methods: bool,
+    ws_security: bool,
+    ws_addressing: bool,
 }
 
 impl SoapClientGeneratorBuilder {
@@ -168,6 +199,10 @@ impl SoapClientGeneratorBuilder {
             client_name: None,
             generate_tests: false,
             soap_version: SoapVersion::Auto,
+            encoded: false,
+            unwrapped_methods: false,
+            ws_security: false,
+            ws_addressing: false,
         }
     }
 
@@ -207,6 +242,55 @@ impl SoapClientGeneratorBuilder {
         self
     }
 
+    /// Enable or disable SOAP encoding for operations bound with `use="encoded"`
+    ///
+    /// When enabled, such operations send their values annotated with
+    /// `xsi:type`, arrays as `soapenc:Array`, and resolve `href` references in
+    /// responses. When disabled (the default), they are called like literal
+    /// operations.
+    pub fn encoded(mut self, enable: bool) -> Self {
+        self.encoded = enable;
+        self
+    }
+
+    /// Enable or disable methods taking the children of wrapped request elements
+    ///
+    /// Document/literal operations following the wrapped convention (a single
+    /// part whose element is named after the operation) then get a second
+    /// method, `<operation>_unwrapped`, taking the children of the request
+    /// element as arguments, e.g. `client.add_unwrapped(1, 2)`. A single child
+    /// of the response element is returned directly. The methods taking the
+    /// request element are generated either way.
+    pub fn unwrapped_methods(mut self, enable: bool) -> Self {
+        self.unwrapped_methods = enable;
+        self
+    }
+
+    /// Enable or disable applying the WS-SecurityPolicy of the WSDL
+    ///
+    /// When enabled, the generated method of an operation with a policy
+    /// applies it to the client with `SoapClient::with_security_policy`
+    /// before the call. The generated code then requires the `ws-security`
+    /// feature of `soapus-runtime`. When disabled (the default), policies are
+    /// ignored.
+    pub fn ws_security(mut self, enable: bool) -> Self {
+        self.ws_security = enable;
+        self
+    }
+
+    /// Enable or disable sending the WS-Addressing actions of the WSDL
+    ///
+    /// When enabled, generated methods send the `wsam:Action` or `wsaw:Action`
+    /// of an operation's input with `SoapClient::with_addressing_action`, and
+    /// require WS-Addressing headers for bindings using them. The generated
+    /// code then requires the `ws-addressing` feature of `soapus-runtime`.
+    /// When disabled (the default), the WS-Addressing settings of the client
+    /// apply unchanged.
+    pub fn ws_addressing(mut self, enable: bool) -> Self {
+        self.ws_addressing = enable;
+        self
+    }
+
     /// Build the generator and generate the code
     pub fn generate(self) -> Result<GeneratedCode> {
         let generator = self.build()?;
@@ -240,6 +324,10 @@ impl SoapClientGeneratorBuilder {
             client_name: self.client_name,
             generate_tests: self.generate_tests,
             soap_version: self.soap_version,
+            encoded: self.encoded,
+            unwrapped_methods: self.unwrapped_methods,
+            ws_security: self.ws_security,
+            ws_addressing: self.ws_addressing,
         })
     }
 }
@@ -0,0 +1,82 @@
+//! Loading of WSDL and XSD documents by location
+//!
+//! [`parse_wsdl_with_imports`](super::parse_wsdl_with_imports) fetches every
+//! imported document through a [`DocumentLoader`].
+
+use std::collections::HashMap;
+use std::io;
+
+/// Loads the content of WSDL and XSD documents by location
+pub trait DocumentLoader {
+    /// Load the document at the given (already resolved) location
+    fn load(&self, location: &str) -> io::Result<String>;
+}
+
+/// Loads documents from the local file system
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FileSystemLoader;
+
+impl DocumentLoader for FileSystemLoader {
+    fn load(&self, location: &str) -> io::Result<String> {
+        if location.contains("://") {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "remote documents are not supported, download them next to the WSDL",
+            ));
+        }
+        std::fs::read_to_string(location)
+    }
+}
+
+/// Serves documents from memory, keyed by their resolved location
+///
+/// # Example
+///
+/// ```
+/// use soapus_codegen::parser::{parse_wsdl_with_imports, MemoryLoader};
+///
+/// let loader = MemoryLoader::new().with_document(
+///     "schemas/types.xsd",
+///     r#"<schema xmlns="http://www.w3.org/2001/XMLSchema"/>"#,
+/// );
+/// let wsdl = r#"<definitions xmlns="http://schemas.xmlsoap.org/wsdl/"
+///                            xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+///   <types>
+///     <xsd:schema><xsd:include schemaLocation="schemas/types.xsd"/></xsd:schema>
+///   </types>
+/// </definitions>"#;
+/// let model = parse_wsdl_with_imports(wsdl, "service.wsdl", &loader).unwrap();
+/// assert!(model.schema().is_some());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct MemoryLoader {
+    documents: HashMap<String, String>,
+}
+
+impl MemoryLoader {
+    /// Create an empty loader
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a document under the given location
+    pub fn with_document(
+        mut self,
+        location: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Self {
+        self.documents.insert(location.into(), content.into());
+        self
+    }
+}
+
+impl DocumentLoader for MemoryLoader {
+    fn load(&self, location: &str) -> io::Result<String> {
+        self.documents.get(location).cloned().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("document '{}' not found", location),
+            )
+        })
+    }
+}
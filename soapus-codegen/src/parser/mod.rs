@@ -1,86 +1,163 @@
 //! WSDL and XSD parsing module
 //!
-//! This module provides functionality to parse WSDL files and their embedded XSD schemas.
+//! This module provides functionality to parse WSDL files and their embedded XSD schemas,
+//! including documents pulled in via `wsdl:import`, `xsd:import` and `xsd:include`.
 
+mod loader;
+mod resolver;
 mod wsdl;
 mod xsd;
 
+pub use loader::{DocumentLoader, FileSystemLoader, MemoryLoader};
+pub use resolver::{parse_wsdl_with_imports, resolve_location};
+
 pub use wsdl::parser::parse_wsdl;
 pub use wsdl::{
-    Binding, BindingOperation, Fault, Message, MessagePart, Port, PortType, PortTypeOperation,
-    Service, WsdlModel,
+    Binding, BindingOperation, Fault, Message, MessagePart, PolicyExpression, Port, PortType,
+    PortTypeOperation, SecurityPolicy, Service, SoapBody, SoapHeader, WsdlImport, WsdlModel,
 };
 
 pub use xsd::parser::parse_schema;
 pub use xsd::{
-    ComplexType, Restriction, SchemaElement, Sequence, SequenceElement, SimpleType, XmlSchema,
+    Attribute, AttributeGroup, AttributeUse, Choice, ComplexType, Derivation, GroupRef,
+    Restriction, SchemaElement, SchemaImport, Sequence, SequenceElement, SimpleType, XmlSchema,
+    SOAP_ENCODING_NAMESPACE, XSD_NAMESPACE,
 };
 
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+
 /// Qualified Name (QName) representation
 ///
-/// Represents an XML qualified name with optional namespace prefix.
-/// Format: `prefix:localName` or just `localName`
-#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
-pub struct QName(pub String);
+/// Represents an XML qualified name as written in the document (`prefix:localName`
+/// or just `localName`) together with the namespace URI the prefix was bound to
+/// by the in-scope `xmlns` declarations when the document was parsed.
+///
+/// Two QNames are equal if their namespace URI and local name are equal,
+/// the prefix used to write them doesn't matter.
+#[derive(Default, Debug, Clone)]
+pub struct QName {
+    name: String,
+    namespace: Option<String>,
+}
 
 impl QName {
-    /// Create a new QName from a string
+    /// Create a new QName from a string, without resolving its prefix
     pub fn new(value: impl Into<String>) -> Self {
-        Self(value.into())
+        Self {
+            name: value.into(),
+            namespace: None,
+        }
+    }
+
+    /// Create a QName whose prefix has been resolved to a namespace URI
+    pub fn with_namespace(namespace: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: value.into(),
+            namespace: Some(namespace.into()),
+        }
+    }
+
+    /// Resolve a QName attribute value using the reader's in-scope namespace declarations
+    ///
+    /// Unprefixed names resolve to the default namespace, if declared. Names
+    /// with an undeclared prefix stay unresolved.
+    pub(crate) fn resolve<R>(value: &str, reader: &NsReader<R>) -> Self {
+        let (result, _) = reader.resolve_element(quick_xml::name::QName(value.as_bytes()));
+        let namespace = match result {
+            ResolveResult::Bound(ns) => Some(String::from_utf8_lossy(ns.as_ref()).into_owned()),
+            ResolveResult::Unbound | ResolveResult::Unknown(_) => None,
+        };
+        Self {
+            name: value.to_string(),
+            namespace,
+        }
+    }
+
+    /// Get the namespace URI, if the QName was resolved
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
     }
 
     /// Get the namespace prefix (part before ':')
     pub fn prefix(&self) -> Option<&str> {
-        self.0.split_once(':').map(|(prefix, _)| prefix)
+        self.name.split_once(':').map(|(prefix, _)| prefix)
     }
 
     /// Get the local name (part after ':' or the entire string if no ':')
     pub fn local_name(&self) -> &str {
-        self.0
+        self.name
             .split_once(':')
             .map(|(_, local)| local)
-            .unwrap_or(&self.0)
+            .unwrap_or(&self.name)
     }
 
-    /// Get the full qualified name
+    /// Get the qualified name as written in the document
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.name
     }
 
     /// Check if this QName is empty
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.name.is_empty()
     }
 
     /// Split into prefix and local name
     pub fn split(&self) -> (Option<&str>, &str) {
-        match self.0.split_once(':') {
+        match self.name.split_once(':') {
             Some((prefix, local)) => (Some(prefix), local),
-            None => (None, &self.0),
+            None => (None, &self.name),
         }
     }
 
     /// Create a QName with a specific prefix and local name
     pub fn with_prefix(prefix: &str, local_name: &str) -> Self {
-        Self(format!("{}:{}", prefix, local_name))
+        Self::new(format!("{}:{}", prefix, local_name))
+    }
+
+    /// Check whether this QName refers to the definition `local_name` in `namespace`
+    ///
+    /// Unresolved QNames (e.g. created with [`QName::new`]) match by local name only.
+    pub fn refers_to(&self, namespace: Option<&str>, local_name: &str) -> bool {
+        self.local_name() == local_name
+            && (self.namespace.is_none() || self.namespace.as_deref() == namespace)
+    }
+}
+
+impl PartialEq for QName {
+    fn eq(&self, other: &Self) -> bool {
+        self.namespace == other.namespace && self.local_name() == other.local_name()
+    }
+}
+
+impl Eq for QName {}
+
+impl std::hash::Hash for QName {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.namespace.hash(state);
+        self.local_name().hash(state);
     }
 }
 
 impl From<String> for QName {
     fn from(s: String) -> Self {
-        Self(s)
+        Self::new(s)
     }
 }
 
 impl From<&str> for QName {
     fn from(s: &str) -> Self {
-        Self(s.to_string())
+        Self::new(s)
     }
 }
 
+/// Formats resolved QNames in `{namespaceURI}localName` notation
 impl std::fmt::Display for QName {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        match &self.namespace {
+            Some(namespace) => write!(f, "{{{}}}{}", namespace, self.local_name()),
+            None => write!(f, "{}", self.name),
+        }
     }
 }
 
@@ -117,4 +194,38 @@ mod tests {
         assert_eq!(qname.prefix(), Some("soap"));
         assert_eq!(qname.local_name(), "Envelope");
     }
+
+    #[test]
+    fn test_qname_resolves_in_scope_prefixes() {
+        let mut reader = NsReader::from_str(
+            r#"<a xmlns:t="urn:outer" xmlns="urn:default"><b xmlns:t="urn:inner"/></a>"#,
+        );
+        reader.read_event().unwrap(); // <a>
+        assert_eq!(
+            QName::resolve("t:Item", &reader).namespace(),
+            Some("urn:outer")
+        );
+        assert_eq!(
+            QName::resolve("Item", &reader).namespace(),
+            Some("urn:default")
+        );
+        assert_eq!(QName::resolve("x:Item", &reader).namespace(), None);
+
+        reader.read_event().unwrap(); // <b/> redeclares t
+        let inner = QName::resolve("t:Item", &reader);
+        assert_eq!(inner.namespace(), Some("urn:inner"));
+        assert_eq!(inner.to_string(), "{urn:inner}Item");
+    }
+
+    #[test]
+    fn test_qname_equality_ignores_prefix() {
+        let a = QName::with_namespace("urn:orders", "a:Order");
+        let b = QName::with_namespace("urn:orders", "b:Order");
+        let c = QName::with_namespace("urn:common", "a:Order");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.refers_to(Some("urn:orders"), "Order"));
+        assert!(!a.refers_to(Some("urn:common"), "Order"));
+        assert!(QName::new("Order").refers_to(Some("urn:common"), "Order"));
+    }
 }
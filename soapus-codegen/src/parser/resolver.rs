@@ -0,0 +1,443 @@
+//! Resolution of WSDL and XSD imports across multiple documents
+//!
+//! Real-world WSDLs are often split into a root document plus several
+//! imported WSDL and XSD files. This module follows `<wsdl:import location>`,
+//! `<xsd:import schemaLocation>` and `<xsd:include schemaLocation>` references
+//! relative to the importing document and merges everything into one `WsdlModel`.
+//!
+//! Documents are loaded through the [`DocumentLoader`] trait, so callers (and
+//! tests) can supply documents from memory instead of the file system.
+
+use crate::error::{CodegenError, Result};
+use crate::parser::{parse_schema, parse_wsdl, DocumentLoader, WsdlModel, XmlSchema};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashSet;
+
+#[cfg(feature = "tracing")]
+use tracing::debug;
+
+/// Parse a WSDL document and resolve all of its imports and includes
+///
+/// # Arguments
+///
+/// * `xml` - The root WSDL document as a string
+/// * `location` - Location of the root document, used to resolve relative imports
+/// * `loader` - Loader used to fetch imported documents
+///
+/// # Errors
+///
+/// Returns `CodegenError::WsdlParse` if the root document is invalid and
+/// `CodegenError::ImportError` if an imported document can't be loaded or parsed.
+pub fn parse_wsdl_with_imports(
+    xml: &str,
+    location: &str,
+    loader: &dyn DocumentLoader,
+) -> Result<WsdlModel> {
+    let mut model = parse_wsdl(xml).map_err(|e| CodegenError::WsdlParse(e.to_string()))?;
+    let mut resolver = ImportResolver::new(loader);
+    let location = normalize_location(location);
+    resolver.visited.insert(location.clone());
+    resolver.in_progress.push(location.clone());
+    resolver.resolve_wsdl_imports(&mut model, &location)?;
+    Ok(model)
+}
+
+/// Resolve a (possibly relative) reference against the location of the referencing document
+///
+/// Absolute URLs and absolute paths are returned unchanged, relative references
+/// are resolved against the directory of `base`.
+pub fn resolve_location(base: &str, reference: &str) -> String {
+    if reference.contains("://") || reference.starts_with('/') {
+        return normalize_location(reference);
+    }
+    let base_dir = match base.rfind('/') {
+        Some(pos) => &base[..=pos],
+        None => "",
+    };
+    normalize_location(&format!("{}{}", base_dir, reference))
+}
+
+/// Collapse `.` and `..` segments of a location
+fn normalize_location(location: &str) -> String {
+    let location = location.replace('\\', "/");
+    let (prefix, path) = match location.find("://") {
+        Some(pos) => {
+            let authority_end = location[pos + 3..]
+                .find('/')
+                .map(|p| pos + 3 + p)
+                .unwrap_or(location.len());
+            location.split_at(authority_end)
+        }
+        None => ("", location.as_str()),
+    };
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "." => {}
+            ".." if segments.last().is_some_and(|s| !s.is_empty() && *s != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    format!("{}{}", prefix, segments.join("/"))
+}
+
+/// Check whether a document's root element is an XSD `<schema>`
+fn is_schema_document(xml: &str) -> bool {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                return e.local_name().as_ref() == b"schema";
+            }
+            Ok(Event::Eof) | Err(_) => return false,
+            _ => {}
+        }
+    }
+}
+
+/// Import resolution state
+///
+/// Tracks which documents have been loaded and which are currently being
+/// processed, so diamonds are only merged once and cycles are broken.
+struct ImportResolver<'a> {
+    loader: &'a dyn DocumentLoader,
+    visited: HashSet<String>,
+    in_progress: Vec<String>,
+}
+
+impl<'a> ImportResolver<'a> {
+    fn new(loader: &'a dyn DocumentLoader) -> Self {
+        Self {
+            loader,
+            visited: HashSet::new(),
+            in_progress: Vec::new(),
+        }
+    }
+
+    /// Check whether a document still needs to be loaded
+    fn needs_loading(&self, location: &str) -> bool {
+        if self.in_progress.iter().any(|l| l == location) {
+            #[cfg(feature = "tracing")]
+            debug!(location = %location, chain = ?self.in_progress, "Import cycle detected, skipping");
+            return false;
+        }
+        !self.visited.contains(location)
+    }
+
+    fn load(&mut self, location: &str) -> Result<String> {
+        #[cfg(feature = "tracing")]
+        debug!(location = %location, "Loading imported document");
+
+        self.visited.insert(location.to_string());
+        self.loader
+            .load(location)
+            .map_err(|e| CodegenError::ImportError {
+                uri: location.to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// Resolve the imports of a WSDL model and its embedded schemas
+    fn resolve_wsdl_imports(&mut self, model: &mut WsdlModel, base: &str) -> Result<()> {
+        let embedded = model
+            .schemas_mut()
+            .map(|schema| (take_references(schema), schema.target_namespace.clone()))
+            .collect::<Vec<_>>();
+        for (references, target_namespace) in embedded {
+            for schema in self.load_references(references, base, target_namespace.as_deref())? {
+                model.add_schema(schema);
+            }
+        }
+
+        for import in model.imports().to_vec() {
+            let location = resolve_location(base, &import.location);
+            if !self.needs_loading(&location) {
+                continue;
+            }
+            let xml = self.load(&location)?;
+            self.in_progress.push(location.clone());
+
+            if is_schema_document(&xml) {
+                let schema = parse_schema(&xml).map_err(|e| import_error(&location, e))?;
+                for schema in self.resolve_schema(schema, &location)? {
+                    model.add_schema(schema);
+                }
+            } else {
+                let mut imported = parse_wsdl(&xml).map_err(|e| import_error(&location, e))?;
+                self.resolve_wsdl_imports(&mut imported, &location)?;
+                model.merge(imported);
+            }
+
+            self.in_progress.pop();
+        }
+        Ok(())
+    }
+
+    /// Load the imported and included schemas of a schema with the given target namespace
+    fn load_references(
+        &mut self,
+        references: References,
+        base: &str,
+        target_namespace: Option<&str>,
+    ) -> Result<Vec<XmlSchema>> {
+        let mut schemas = self.load_schemas(&references.imports, base, None)?;
+        schemas.extend(self.load_schemas(&references.includes, base, target_namespace)?);
+        Ok(schemas)
+    }
+
+    /// Load the schemas at `locations`, including their own references
+    ///
+    /// Schemas without target namespace adopt `including_namespace`, which is
+    /// only given for `xsd:include` (chameleon include). Imported schemas
+    /// without target namespace stay in no namespace.
+    fn load_schemas(
+        &mut self,
+        locations: &[String],
+        base: &str,
+        including_namespace: Option<&str>,
+    ) -> Result<Vec<XmlSchema>> {
+        let mut schemas = Vec::new();
+        for reference in locations {
+            let location = resolve_location(base, reference);
+            if !self.needs_loading(&location) {
+                continue;
+            }
+            let xml = self.load(&location)?;
+            self.in_progress.push(location.clone());
+
+            let mut schema = parse_schema(&xml).map_err(|e| import_error(&location, e))?;
+            // Chameleon include: a schema without target namespace adopts the includer's
+            if schema.target_namespace.is_none() {
+                schema.target_namespace = including_namespace.map(str::to_string);
+            }
+            schemas.extend(self.resolve_schema(schema, &location)?);
+
+            self.in_progress.pop();
+        }
+        Ok(schemas)
+    }
+
+    /// Resolve the references of a loaded schema, returning it with all schemas it pulls in
+    fn resolve_schema(&mut self, mut schema: XmlSchema, location: &str) -> Result<Vec<XmlSchema>> {
+        let references = take_references(&mut schema);
+        let target_namespace = schema.target_namespace.clone();
+        let mut schemas = vec![schema];
+        schemas.extend(self.load_references(references, location, target_namespace.as_deref())?);
+        Ok(schemas)
+    }
+}
+
+/// Locations of the schemas a schema imports and includes
+struct References {
+    imports: Vec<String>,
+    includes: Vec<String>,
+}
+
+/// Take the import and include locations out of a schema
+fn take_references(schema: &mut XmlSchema) -> References {
+    References {
+        imports: std::mem::take(&mut schema.imports)
+            .into_iter()
+            .filter_map(|i| i.schema_location)
+            .collect(),
+        includes: std::mem::take(&mut schema.includes),
+    }
+}
+
+fn import_error(location: &str, error: Box<dyn std::error::Error>) -> CodegenError {
+    CodegenError::ImportError {
+        uri: location.to_string(),
+        reason: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::MemoryLoader;
+
+    const ROOT_WSDL: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<definitions xmlns="http://schemas.xmlsoap.org/wsdl/"
+             xmlns:soap="http://schemas.xmlsoap.org/wsdl/soap/"
+             xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+             xmlns:tns="http://example.com/orders"
+             targetNamespace="http://example.com/orders"
+             name="OrderService">
+  <import namespace="http://example.com/orders" location="wsdl/messages.wsdl"/>
+  <types>
+    <xsd:schema targetNamespace="http://example.com/orders">
+      <xsd:import namespace="http://example.com/common" schemaLocation="xsd/common.xsd"/>
+      <xsd:include schemaLocation="xsd/orders.xsd"/>
+    </xsd:schema>
+  </types>
+  <service name="OrderService">
+    <port name="OrderPort" binding="tns:OrderBinding">
+      <soap:address location="http://example.com/orders"/>
+    </port>
+  </service>
+</definitions>"#;
+
+    const MESSAGES_WSDL: &str = r#"<definitions xmlns="http://schemas.xmlsoap.org/wsdl/"
+             xmlns:tns="http://example.com/orders"
+             targetNamespace="http://example.com/orders">
+  <import location="../root.wsdl"/>
+  <message name="GetOrderIn">
+    <part name="parameters" element="tns:GetOrder"/>
+  </message>
+</definitions>"#;
+
+    const COMMON_XSD: &str = r#"<xsd:schema xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+            targetNamespace="http://example.com/common">
+  <xsd:include schemaLocation="address.xsd"/>
+  <xsd:complexType name="Money">
+    <xsd:sequence>
+      <xsd:element name="amount" type="xsd:decimal"/>
+    </xsd:sequence>
+  </xsd:complexType>
+</xsd:schema>"#;
+
+    const ADDRESS_XSD: &str = r#"<xsd:schema xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+  <xsd:complexType name="Address">
+    <xsd:sequence>
+      <xsd:element name="street" type="xsd:string"/>
+    </xsd:sequence>
+  </xsd:complexType>
+</xsd:schema>"#;
+
+    const ORDERS_XSD: &str = r#"<xsd:schema xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+            targetNamespace="http://example.com/orders">
+  <xsd:import namespace="http://example.com/common" schemaLocation="common.xsd"/>
+  <xsd:element name="GetOrder">
+    <xsd:complexType>
+      <xsd:sequence>
+        <xsd:element name="orderId" type="xsd:string"/>
+      </xsd:sequence>
+    </xsd:complexType>
+  </xsd:element>
+</xsd:schema>"#;
+
+    fn loader() -> MemoryLoader {
+        MemoryLoader::new()
+            .with_document("root.wsdl", ROOT_WSDL)
+            .with_document("wsdl/messages.wsdl", MESSAGES_WSDL)
+            .with_document("xsd/common.xsd", COMMON_XSD)
+            .with_document("xsd/address.xsd", ADDRESS_XSD)
+            .with_document("xsd/orders.xsd", ORDERS_XSD)
+    }
+
+    #[test]
+    fn test_resolve_location() {
+        assert_eq!(resolve_location("root.wsdl", "types.xsd"), "types.xsd");
+        assert_eq!(
+            resolve_location("wsdl/root.wsdl", "../xsd/types.xsd"),
+            "xsd/types.xsd"
+        );
+        assert_eq!(
+            resolve_location("/srv/wsdl/root.wsdl", "./types.xsd"),
+            "/srv/wsdl/types.xsd"
+        );
+        assert_eq!(
+            resolve_location("http://example.com/ws/root.wsdl", "../xsd/types.xsd"),
+            "http://example.com/xsd/types.xsd"
+        );
+        assert_eq!(
+            resolve_location("root.wsdl", "http://example.com/types.xsd"),
+            "http://example.com/types.xsd"
+        );
+    }
+
+    #[test]
+    fn resolves_imports_and_includes() {
+        let model = parse_wsdl_with_imports(ROOT_WSDL, "root.wsdl", &loader()).unwrap();
+
+        // Definitions from the imported WSDL are merged
+        assert!(model.find_message(&"tns:GetOrderIn".into()).is_some());
+        assert_eq!(model.services().len(), 1);
+
+        // Included schemas are merged, imported ones kept under their own namespace
+        let orders = model.find_schema("http://example.com/orders").unwrap();
+        assert!(orders.complex_types.contains_key("GetOrder"));
+        let common = model.find_schema("http://example.com/common").unwrap();
+        assert!(common.complex_types.contains_key("Money"));
+        assert!(common.complex_types.contains_key("Address"));
+    }
+
+    #[test]
+    fn keeps_imported_schema_without_namespace() {
+        let wsdl = r#"<definitions xmlns="http://schemas.xmlsoap.org/wsdl/"
+             xmlns:xsd="http://www.w3.org/2001/XMLSchema"
+             targetNamespace="http://example.com/orders">
+  <types>
+    <xsd:schema targetNamespace="http://example.com/orders">
+      <xsd:import schemaLocation="unqualified.xsd"/>
+      <xsd:include schemaLocation="chameleon.xsd"/>
+    </xsd:schema>
+  </types>
+</definitions>"#;
+        let unqualified = r#"<xsd:schema xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+  <xsd:complexType name="Note">
+    <xsd:sequence>
+      <xsd:element name="text" type="xsd:string"/>
+    </xsd:sequence>
+  </xsd:complexType>
+</xsd:schema>"#;
+        let chameleon = r#"<xsd:schema xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+  <xsd:import schemaLocation="unqualified.xsd"/>
+  <xsd:complexType name="Line">
+    <xsd:sequence>
+      <xsd:element name="quantity" type="xsd:int"/>
+    </xsd:sequence>
+  </xsd:complexType>
+</xsd:schema>"#;
+        let loader = MemoryLoader::new()
+            .with_document("unqualified.xsd", unqualified)
+            .with_document("chameleon.xsd", chameleon);
+        let model = parse_wsdl_with_imports(wsdl, "root.wsdl", &loader).unwrap();
+
+        // The included schema adopts the namespace of the including schema
+        let orders = model.find_schema("http://example.com/orders").unwrap();
+        assert!(orders.complex_types.contains_key("Line"));
+        assert!(!orders.complex_types.contains_key("Note"));
+
+        // The imported one stays in no namespace
+        let unqualified = model
+            .schemas()
+            .find(|schema| schema.target_namespace.is_none())
+            .unwrap();
+        assert!(unqualified.complex_types.contains_key("Note"));
+    }
+
+    #[test]
+    fn breaks_import_cycles() {
+        // messages.wsdl imports root.wsdl and orders.xsd/common.xsd import each other
+        let model = parse_wsdl_with_imports(ROOT_WSDL, "root.wsdl", &loader()).unwrap();
+        assert_eq!(model.services().len(), 1);
+        assert_eq!(model.messages().len(), 1);
+    }
+
+    #[test]
+    fn reports_missing_import() {
+        let loader = MemoryLoader::new()
+            .with_document("xsd/common.xsd", COMMON_XSD)
+            .with_document("xsd/address.xsd", ADDRESS_XSD)
+            .with_document("xsd/orders.xsd", ORDERS_XSD);
+        let result = parse_wsdl_with_imports(ROOT_WSDL, "root.wsdl", &loader);
+
+        match result {
+            Err(CodegenError::ImportError { uri, .. }) => assert_eq!(uri, "wsdl/messages.wsdl"),
+            other => panic!("Expected ImportError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_invalid_imported_document() {
+        let loader = loader().with_document("xsd/address.xsd", "<xsd:schema></xsd:element>");
+        let result = parse_wsdl_with_imports(ROOT_WSDL, "root.wsdl", &loader);
+
+        assert!(matches!(result, Err(CodegenError::ImportError { .. })));
+    }
+}
@@ -8,7 +8,7 @@ use tracing::warn;
 use crate::parser::QName;
 
 use super::parser::WsdlParser;
-use super::{Binding, BindingOperation};
+use super::{Binding, BindingOperation, SoapBody, SoapHeader};
 
 // Standard SOAP namespace URIs as defined by W3C WSDL specification
 const SOAP_11_BINDING_NS: &str = "http://schemas.xmlsoap.org/wsdl/soap/";
@@ -42,13 +42,15 @@ impl<B: std::io::BufRead> WsdlParser<B> {
         let mut transport = None;
         let mut soap_version = None;
         let mut is_soap_binding = false;
+        let mut binding_style = None;
         let mut operations = Vec::new();
+        let mut policies = Vec::new();
 
         for attr in ev.attributes().with_checks(false) {
             let attr = attr?;
             match attr.key.as_ref() {
                 b"name" => name = Some(attr.unescape_value()?.to_string()),
-                b"type" => type_ = Some(QName(attr.unescape_value()?.to_string())),
+                b"type" => type_ = Some(QName::resolve(&attr.unescape_value()?, &self.reader)),
                 b"xmlns:soap" => {
                     soap_version = Some(attr.unescape_value()?.to_string());
                     is_soap_binding = true;
@@ -60,6 +62,21 @@ impl<B: std::io::BufRead> WsdlParser<B> {
         let mut buf = Vec::new();
         loop {
             match self.reader.read_event_into(&mut buf)? {
+                // <wsp:Policy> or <wsp:PolicyReference> of the binding
+                Event::Start(e) if self.is_policy_attachment(&e) => {
+                    policies.push(self.parse_policy_expression(&e, false)?);
+                }
+                Event::Empty(e) if self.is_policy_attachment(&e) => {
+                    policies.push(self.parse_policy_expression(&e, true)?);
+                }
+                // <wsaw:UsingAddressing>, kept like the policy assertion it stands for
+                Event::Start(e) if e.local_name().as_ref() == b"UsingAddressing" => {
+                    policies.push(self.parse_policy_expression(&e, false)?);
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"UsingAddressing" => {
+                    policies.push(self.parse_policy_expression(&e, true)?);
+                }
+
                 // SOAP Binding Element
                 Event::Empty(e) | Event::Start(e)
                     if e.local_name().as_ref().ends_with(b"binding") =>
@@ -81,7 +98,7 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                         let attr = attr?;
                         match attr.key.as_ref() {
                             b"transport" => transport = Some(attr.unescape_value()?.to_string()),
-                            b"style" => {}
+                            b"style" => binding_style = Some(attr.unescape_value()?.to_string()),
                             b"version" => {
                                 // Allow explicit version attribute to override namespace detection
                                 if let Some(ns_uri) = self.get_namespace_uri(e.name().as_ref()) {
@@ -101,6 +118,15 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                     let mut op_name = None;
                     let mut soap_action = None;
                     let mut style = None;
+                    let mut input = None;
+                    let mut output = None;
+                    let mut input_headers = Vec::new();
+                    let mut output_headers = Vec::new();
+                    let mut operation_policies = Vec::new();
+                    let mut input_policies = Vec::new();
+                    let mut output_policies = Vec::new();
+                    // Whether within <input> (true) or <output> (false)
+                    let mut in_input = None;
 
                     for attr in e.attributes().with_checks(false) {
                         let attr = attr?;
@@ -111,6 +137,23 @@ impl<B: std::io::BufRead> WsdlParser<B> {
 
                     loop {
                         match self.reader.read_event_into(&mut buf)? {
+                            // Policies of the operation, its input or output
+                            Event::Start(e) if self.is_policy_attachment(&e) => {
+                                let policy = self.parse_policy_expression(&e, false)?;
+                                match in_input {
+                                    Some(true) => input_policies.push(policy),
+                                    Some(false) => output_policies.push(policy),
+                                    None => operation_policies.push(policy),
+                                }
+                            }
+                            Event::Empty(e) if self.is_policy_attachment(&e) => {
+                                let policy = self.parse_policy_expression(&e, true)?;
+                                match in_input {
+                                    Some(true) => input_policies.push(policy),
+                                    Some(false) => output_policies.push(policy),
+                                    None => operation_policies.push(policy),
+                                }
+                            }
                             // <soap:operation> or <soap12:operation>
                             Event::Empty(e) | Event::Start(e)
                                 if e.local_name().as_ref() == b"operation" =>
@@ -135,6 +178,88 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                                     }
                                 }
                             }
+                            Event::Start(e) if e.local_name().as_ref() == b"input" => {
+                                in_input = Some(true)
+                            }
+                            Event::Start(e) if e.local_name().as_ref() == b"output" => {
+                                in_input = Some(false)
+                            }
+                            Event::End(e)
+                                if matches!(e.local_name().as_ref(), b"input" | b"output") =>
+                            {
+                                in_input = None
+                            }
+                            // <soap:body> of the input or output
+                            Event::Empty(e) | Event::Start(e)
+                                if e.local_name().as_ref() == b"body" && in_input.is_some() =>
+                            {
+                                let mut body = SoapBody::default();
+                                for attr in e.attributes().with_checks(false) {
+                                    let attr = attr?;
+                                    match attr.key.as_ref() {
+                                        b"use" => {
+                                            body.use_ = Some(attr.unescape_value()?.to_string())
+                                        }
+                                        b"namespace" => {
+                                            body.namespace =
+                                                Some(attr.unescape_value()?.to_string())
+                                        }
+                                        b"parts" => {
+                                            body.parts = Some(
+                                                attr.unescape_value()?
+                                                    .split_whitespace()
+                                                    .map(str::to_string)
+                                                    .collect(),
+                                            )
+                                        }
+                                        _ => {}
+                                    }
+                                }
+                                if in_input == Some(true) {
+                                    input = Some(body);
+                                } else {
+                                    output = Some(body);
+                                }
+                            }
+                            // <soap:header> of the input or output
+                            Event::Empty(e) | Event::Start(e)
+                                if e.local_name().as_ref() == b"header" && in_input.is_some() =>
+                            {
+                                let mut message = None;
+                                let mut part = None;
+                                let mut use_ = None;
+                                let mut namespace = None;
+                                for attr in e.attributes().with_checks(false) {
+                                    let attr = attr?;
+                                    match attr.key.as_ref() {
+                                        b"message" => {
+                                            message = Some(QName::resolve(
+                                                &attr.unescape_value()?,
+                                                &self.reader,
+                                            ))
+                                        }
+                                        b"part" => part = Some(attr.unescape_value()?.to_string()),
+                                        b"use" => use_ = Some(attr.unescape_value()?.to_string()),
+                                        b"namespace" => {
+                                            namespace = Some(attr.unescape_value()?.to_string())
+                                        }
+                                        _ => {}
+                                    }
+                                }
+                                if let (Some(message), Some(part)) = (message, part) {
+                                    let header = SoapHeader {
+                                        message,
+                                        part,
+                                        use_,
+                                        namespace,
+                                    };
+                                    if in_input == Some(true) {
+                                        input_headers.push(header);
+                                    } else {
+                                        output_headers.push(header);
+                                    }
+                                }
+                            }
                             Event::End(e) if e.local_name().as_ref() == b"operation" => break,
                             _ => {}
                         }
@@ -158,7 +283,14 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                         operations.push(BindingOperation {
                             name,
                             soap_action,
-                            style,
+                            style: style.or_else(|| binding_style.clone()),
+                            input,
+                            output,
+                            input_headers,
+                            output_headers,
+                            policies: operation_policies,
+                            input_policies,
+                            output_policies,
                         });
                     }
                 }
@@ -189,13 +321,105 @@ impl<B: std::io::BufRead> WsdlParser<B> {
 
             self.model.bindings.push(Binding {
                 name,
+                namespace: self.target_namespace.clone(),
                 type_,
                 transport,
                 soap_version,
+                style: binding_style,
                 operations,
+                policies,
             });
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{parse_wsdl, QName};
+
+    #[test]
+    fn parses_rpc_binding() {
+        let wsdl = include_str!("../../../../testdata/wsdl/quotes.wsdl");
+        let model = parse_wsdl(wsdl).unwrap();
+
+        // The style of <soap:binding> applies to all operations
+        let binding = &model.bindings[0];
+        assert_eq!(binding.style.as_deref(), Some("rpc"));
+        let operation = model.find_binding_operation("getHistory").unwrap();
+        assert!(operation.is_rpc());
+
+        // <soap:body> of input and output
+        for body in [&operation.input, &operation.output] {
+            let body = body.as_ref().unwrap();
+            assert_eq!(body.use_.as_deref(), Some("literal"));
+            assert_eq!(
+                body.namespace.as_deref(),
+                Some("http://example.com/quotes/rpc")
+            );
+        }
+
+        // Parts of RPC messages are typed
+        let request = model.find_message(&"getHistoryRequest".into()).unwrap();
+        assert_eq!(request.parts.len(), 3);
+        assert!(request.parts[2]
+            .type_
+            .as_ref()
+            .unwrap()
+            .refers_to(Some(crate::parser::XSD_NAMESPACE), "int"));
+
+        // Document style operations are not affected
+        let calculator =
+            parse_wsdl(include_str!("../../../../testdata/wsdl/calculator.wsdl")).unwrap();
+        assert!(!calculator.find_binding_operation("Add").unwrap().is_rpc());
+    }
+
+    #[test]
+    fn parses_body_parts() {
+        let wsdl = include_str!("../../../../testdata/wsdl/bookings.wsdl");
+        let model = parse_wsdl(wsdl).unwrap();
+
+        // Only the listed parts are bound to the body
+        let operation = model.find_binding_operation("Book").unwrap();
+        let input = operation.input.as_ref().unwrap();
+        assert_eq!(
+            input.parts.as_deref(),
+            Some(&["guest".to_string(), "stay".to_string()][..])
+        );
+        assert!(input.binds_part("stay"));
+        assert!(!input.binds_part("channel"));
+
+        // Without a parts attribute, all parts are bound
+        let output = operation.output.as_ref().unwrap();
+        assert!(output.parts.is_none());
+        assert!(output.binds_part("price"));
+    }
+
+    #[test]
+    fn parses_binding_headers() {
+        let wsdl = include_str!("../../../../testdata/wsdl/sessions.wsdl");
+        let model = parse_wsdl(wsdl).unwrap();
+
+        let operation = model.find_binding_operation("Login").unwrap();
+        assert_eq!(operation.input_headers.len(), 1);
+        let header = &operation.input_headers[0];
+        assert_eq!(
+            header.message,
+            QName::with_namespace("http://example.com/sessions", "AuthHeaderMessage")
+        );
+        assert_eq!(header.part, "AuthHeader");
+        assert_eq!(header.use_.as_deref(), Some("literal"));
+        assert_eq!(operation.output_headers.len(), 1);
+        assert_eq!(operation.output_headers[0].part, "SessionHeader");
+
+        // Several headers, with the namespace of a typed part
+        let operation = model.find_binding_operation("GetProfile").unwrap();
+        assert_eq!(operation.input_headers.len(), 2);
+        assert_eq!(
+            operation.input_headers[1].namespace.as_deref(),
+            Some("http://example.com/clients")
+        );
+        assert!(operation.output_headers.is_empty());
+    }
+}
@@ -0,0 +1,40 @@
+//! Parsing of WSDL import elements
+
+use quick_xml::events::BytesStart;
+use std::error::Error;
+
+use super::parser::WsdlParser;
+use super::WsdlImport;
+
+impl<B: std::io::BufRead> WsdlParser<B> {
+    /// Parse an <import> element
+    ///
+    /// Imports pull the definitions of another WSDL document into this one.
+    /// Only the reference is recorded here; loading the imported document is
+    /// left to the import resolver.
+    ///
+    /// Example:
+    /// ```xml
+    /// <import namespace="http://example.com/orders" location="orders.wsdl"/>
+    /// ```
+    pub(super) fn parse_import(&mut self, ev: &BytesStart) -> Result<(), Box<dyn Error>> {
+        let mut namespace = None;
+        let mut location = None;
+
+        for attr in ev.attributes().with_checks(false) {
+            let attr = attr?;
+            match attr.key.as_ref() {
+                b"namespace" => namespace = Some(attr.unescape_value()?.to_string()),
+                b"location" => location = Some(attr.unescape_value()?.to_string()),
+                _ => {}
+            }
+        }
+
+        let location = location.ok_or("import missing 'location' attribute")?;
+        self.model.imports.push(WsdlImport {
+            namespace,
+            location,
+        });
+        Ok(())
+    }
+}
@@ -46,8 +46,8 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                         let val = attr.unescape_value()?;
                         match key {
                             b"name" => part_name = Some(val),
-                            b"element" => part_element = Some(QName(val.to_string())),
-                            b"type" => part_type = Some(QName(val.to_string())),
+                            b"element" => part_element = Some(QName::resolve(&val, &self.reader)),
+                            b"type" => part_type = Some(QName::resolve(&val, &self.reader)),
                             _ => {}
                         }
                     }
@@ -73,7 +73,11 @@ impl<B: std::io::BufRead> WsdlParser<B> {
             buf.clear()
         }
 
-        self.model.messages.push(Message { name, parts });
+        self.model.messages.push(Message {
+            name,
+            namespace: self.target_namespace.clone(),
+            parts,
+        });
         Ok(())
     }
 }
@@ -3,29 +3,44 @@ pub mod parser;
 // Parser sub-modules for different WSDL elements
 mod binding;
 mod definitions;
+mod import;
 mod message;
+mod policy;
 mod port_type;
 mod service;
 mod types;
 
 use crate::parser::QName;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Default, Debug)]
 pub struct WsdlModel {
     name: Option<String>,
     target_namespace: Option<String>,
     namespaces: HashMap<String, String>,
+    imports: Vec<WsdlImport>,
     messages: Vec<Message>,
     port_types: Vec<PortType>,
     bindings: Vec<Binding>,
     services: Vec<Service>,
-    schema: Option<crate::parser::XmlSchema>,
+    /// Top-level `<wsp:Policy>` elements keyed by their `wsu:Id` or `Name`
+    policies: HashMap<String, PolicyExpression>,
+    /// XSD schemas keyed by target namespace (empty string for no namespace)
+    schemas: BTreeMap<String, crate::parser::XmlSchema>,
+}
+
+/// A `<wsdl:import>` of another WSDL (or XSD) document
+#[derive(Clone, Debug)]
+pub struct WsdlImport {
+    pub namespace: Option<String>,
+    pub location: String,
 }
 
 #[derive(Clone, Debug)]
 pub struct Message {
     pub name: String,
+    /// Target namespace of the WSDL document defining the message
+    pub namespace: Option<String>,
     pub parts: Vec<MessagePart>,
 }
 
@@ -39,6 +54,8 @@ pub struct MessagePart {
 #[derive(Debug)]
 pub struct PortType {
     pub name: String,
+    /// Target namespace of the WSDL document defining the port type
+    pub namespace: Option<String>,
     pub operations: Vec<PortTypeOperation>,
 }
 
@@ -53,6 +70,10 @@ pub struct PortTypeOperation {
     pub name: String,
     pub input: Option<QName>,
     pub output: Option<QName>,
+    /// WS-Addressing action of the input, from `wsam:Action` or `wsaw:Action`
+    pub input_action: Option<String>,
+    /// WS-Addressing action of the output
+    pub output_action: Option<String>,
     pub faults: Vec<Fault>,
     /// Documentation from WSDL <wsdl:documentation> element
     pub documentation: Option<String>,
@@ -61,10 +82,15 @@ pub struct PortTypeOperation {
 #[derive(Debug)]
 pub struct Binding {
     pub name: String,
+    /// Target namespace of the WSDL document defining the binding
+    pub namespace: Option<String>,
     pub type_: QName,
-    pub transport: String,    // e.g. "http://schemas.xmlsoap.org/soap/http"
-    pub soap_version: String, // e.g. für <soap:binding style="..."> or xmlns:soap="..."
+    pub transport: String,     // e.g. "http://schemas.xmlsoap.org/soap/http"
+    pub soap_version: String,  // e.g. für <soap:binding style="..."> or xmlns:soap="..."
+    pub style: Option<String>, // default style of the operations, from <soap:binding style="...">
     pub operations: Vec<BindingOperation>,
+    /// Policies attached to the binding
+    pub policies: Vec<PolicyExpression>,
 }
 
 #[derive(Debug)]
@@ -72,6 +98,115 @@ pub struct BindingOperation {
     pub name: String,
     pub soap_action: Option<String>,
     pub style: Option<String>, // "document" or "rpc" for SOAP Binding
+    /// `<soap:body>` of the input
+    pub input: Option<SoapBody>,
+    /// `<soap:body>` of the output
+    pub output: Option<SoapBody>,
+    /// `<soap:header>`s of the input
+    pub input_headers: Vec<SoapHeader>,
+    /// `<soap:header>`s of the output
+    pub output_headers: Vec<SoapHeader>,
+    /// Policies attached to the operation
+    pub policies: Vec<PolicyExpression>,
+    /// Policies attached to the input
+    pub input_policies: Vec<PolicyExpression>,
+    /// Policies attached to the output
+    pub output_policies: Vec<PolicyExpression>,
+}
+
+/// A `<soap:body>` of a binding operation's input or output
+#[derive(Clone, Debug, Default)]
+pub struct SoapBody {
+    /// "literal" or "encoded"
+    pub use_: Option<String>,
+    /// Namespace of the operation element wrapping the parts (RPC style)
+    pub namespace: Option<String>,
+    /// Names of the message parts bound to the body, all parts if absent
+    pub parts: Option<Vec<String>>,
+}
+
+/// A `<soap:header>` of a binding operation's input or output
+///
+/// The header block is a part of a message, not necessarily the one of the
+/// input or output.
+#[derive(Clone, Debug)]
+pub struct SoapHeader {
+    pub message: QName,
+    pub part: String,
+    /// "literal" or "encoded"
+    pub use_: Option<String>,
+    /// Namespace of a header block declared with a `type` part
+    pub namespace: Option<String>,
+}
+
+/// A WS-Policy expression attached to a binding, an operation or a message
+///
+/// `<wsp:Policy>` elements nested in assertions are kept as
+/// [`PolicyExpression::All`] in the `nested` expressions of the assertion.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PolicyExpression {
+    /// `<wsp:Policy>` or `<wsp:All>`: all of the expressions apply
+    All(Vec<PolicyExpression>),
+    /// `<wsp:ExactlyOne>`: one of the expressions applies
+    ExactlyOne(Vec<PolicyExpression>),
+    /// `<wsp:PolicyReference URI="...">`
+    Reference(String),
+    /// A policy assertion, e.g. `<sp:UsernameToken>`
+    Assertion {
+        name: QName,
+        /// Whether the assertion is `wsp:Optional`
+        optional: bool,
+        /// Nested policy and child elements, e.g. the `<sp:Body>` of `<sp:SignedParts>`
+        nested: Vec<PolicyExpression>,
+    },
+}
+
+/// WS-SecurityPolicy assertions in effect for an operation
+///
+/// Assertions of the binding, the operation and its input (or output) are
+/// combined. Of several policy alternatives, the first one applies; optional
+/// assertions are left out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SecurityPolicy {
+    /// `<sp:UsernameToken>`: a `wsse:UsernameToken` is sent
+    pub username_token: bool,
+    /// `<sp:HashPassword>`: the password is sent as digest
+    pub hash_password: bool,
+    /// `<sp:IncludeTimestamp>`: a `wsu:Timestamp` is sent
+    pub include_timestamp: bool,
+    /// `<sp:AsymmetricBinding>`: messages are protected with X.509 tokens
+    pub asymmetric_binding: bool,
+    /// Name of the `<sp:AlgorithmSuite>`, e.g. `Basic256`
+    pub algorithm_suite: Option<String>,
+    /// `<sp:SignedParts>` of the input include the Body
+    pub sign_body: bool,
+    /// `<sp:EncryptedParts>` of the input include the Body
+    pub encrypt_body: bool,
+    /// `<sp:SignedParts>` of the output include the Body
+    pub signed_response: bool,
+}
+
+impl SoapBody {
+    /// Whether the named message part is bound to the body
+    pub fn binds_part(&self, part: &str) -> bool {
+        self.parts
+            .as_ref()
+            .is_none_or(|parts| parts.iter().any(|name| name == part))
+    }
+}
+
+impl BindingOperation {
+    /// Whether the operation uses the RPC style
+    pub fn is_rpc(&self) -> bool {
+        self.style.as_deref() == Some("rpc")
+    }
+
+    /// Whether the input is bound with `use="encoded"` (SOAP encoding)
+    pub fn is_encoded(&self) -> bool {
+        self.input
+            .as_ref()
+            .is_some_and(|body| body.use_.as_deref() == Some("encoded"))
+    }
 }
 
 #[derive(Debug)]
@@ -98,19 +233,126 @@ impl WsdlModel {
         self.port_types.iter().flat_map(|pt| pt.operations.iter())
     }
 
-    /// Get the embedded XSD schema (if any)
+    /// Get the primary XSD schema (if any)
+    ///
+    /// This is the schema for the WSDL's target namespace, or the first schema
+    /// in namespace order if none matches. Use [`WsdlModel::schemas`] to access
+    /// all of them.
     pub fn schema(&self) -> Option<&crate::parser::XmlSchema> {
-        self.schema.as_ref()
+        self.target_namespace
+            .as_deref()
+            .and_then(|ns| self.schemas.get(ns))
+            .or_else(|| self.schemas.values().next())
+    }
+
+    /// Get an iterator over all XSD schemas, ordered by target namespace
+    pub fn schemas(&self) -> impl Iterator<Item = &crate::parser::XmlSchema> {
+        self.schemas.values()
     }
 
-    /// Set the XSD schema
-    pub(crate) fn set_schema(&mut self, schema: crate::parser::XmlSchema) {
-        self.schema = Some(schema);
+    /// Get a mutable iterator over all XSD schemas
+    pub(crate) fn schemas_mut(&mut self) -> impl Iterator<Item = &mut crate::parser::XmlSchema> {
+        self.schemas.values_mut()
+    }
+
+    /// Find the XSD schema for a target namespace URI
+    pub fn find_schema(&self, namespace: &str) -> Option<&crate::parser::XmlSchema> {
+        self.schemas.get(namespace)
+    }
+
+    /// Find a complex type by QName in the schema of its namespace
+    ///
+    /// Unresolved QNames are looked up in all schemas.
+    pub fn find_complex_type(&self, qname: &QName) -> Option<&crate::parser::ComplexType> {
+        self.find_component(qname, |schema| &schema.complex_types)
+    }
+
+    /// Find a global element declaration by QName in the schema of its namespace
+    ///
+    /// Unresolved QNames are looked up in all schemas. Elements declared with
+    /// an inline complexType are not included, their type is registered as a
+    /// complex type of the same name.
+    pub fn find_element(&self, qname: &QName) -> Option<&crate::parser::SchemaElement> {
+        self.find_component(qname, |schema| &schema.elements)
+    }
+
+    /// Find an attribute group by QName in the schema of its namespace
+    ///
+    /// Unresolved QNames are looked up in all schemas.
+    pub fn find_attribute_group(&self, qname: &QName) -> Option<&crate::parser::AttributeGroup> {
+        self.find_component(qname, |schema| &schema.attribute_groups)
+    }
+
+    /// Find a named model group by QName in the schema of its namespace
+    ///
+    /// Unresolved QNames are looked up in all schemas.
+    pub fn find_group(&self, qname: &QName) -> Option<&crate::parser::Sequence> {
+        self.find_component(qname, |schema| &schema.groups)
+    }
+
+    /// Find a schema component by QName in the map `components` selects from each schema
+    fn find_component<'s, T>(
+        &'s self,
+        qname: &QName,
+        components: impl Fn(&'s crate::parser::XmlSchema) -> &'s HashMap<String, T>,
+    ) -> Option<&'s T> {
+        match qname.namespace() {
+            Some(namespace) => self
+                .find_schema(namespace)
+                .and_then(|schema| components(schema).get(qname.local_name())),
+            None => self
+                .schemas
+                .values()
+                .find_map(|schema| components(schema).get(qname.local_name())),
+        }
+    }
+
+    /// Add an XSD schema
+    ///
+    /// Schemas sharing a target namespace are merged into one.
+    pub(crate) fn add_schema(&mut self, schema: crate::parser::XmlSchema) {
+        let key = schema.target_namespace.clone().unwrap_or_default();
+        match self.schemas.get_mut(&key) {
+            Some(existing) => existing.merge(schema),
+            None => {
+                self.schemas.insert(key, schema);
+            }
+        }
+    }
+
+    /// Get all `<wsdl:import>` declarations
+    pub fn imports(&self) -> &[WsdlImport] {
+        &self.imports
+    }
+
+    /// Merge the definitions of an imported WSDL document into this model
+    ///
+    /// The name and target namespace of `self` are kept; namespace prefixes
+    /// already declared in `self` take precedence.
+    pub(crate) fn merge(&mut self, other: WsdlModel) {
+        if self.target_namespace.is_none() {
+            self.target_namespace = other.target_namespace;
+        }
+        for (prefix, uri) in other.namespaces {
+            self.namespaces.entry(prefix).or_insert(uri);
+        }
+        self.messages.extend(other.messages);
+        self.port_types.extend(other.port_types);
+        self.bindings.extend(other.bindings);
+        self.services.extend(other.services);
+        self.policies.extend(other.policies);
+        for (_, schema) in other.schemas {
+            self.add_schema(schema);
+        }
     }
 
     /// Find a message by QName
+    ///
+    /// The QName's namespace must match the target namespace the message was defined in.
     pub fn find_message(&self, qname: &QName) -> Option<&Message> {
-        self.messages.iter().find(|m| m.name == qname.local_name())
+        self.messages
+            .iter()
+            .find(|m| qname.refers_to(m.namespace.as_deref(), &m.name))
     }
 
     /// Get all services
@@ -138,14 +380,18 @@ impl WsdlModel {
         self.target_namespace.as_deref()
     }
 
-    /// Find a binding by name
-    pub fn find_binding(&self, name: &str) -> Option<&Binding> {
-        self.bindings.iter().find(|b| b.name == name)
+    /// Find a binding by QName
+    pub fn find_binding(&self, qname: &QName) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .find(|b| qname.refers_to(b.namespace.as_deref(), &b.name))
     }
 
-    /// Find a port type by name
-    pub fn find_port_type(&self, name: &str) -> Option<&PortType> {
-        self.port_types.iter().find(|pt| pt.name == name)
+    /// Find a port type by QName
+    pub fn find_port_type(&self, qname: &QName) -> Option<&PortType> {
+        self.port_types
+            .iter()
+            .find(|pt| qname.refers_to(pt.namespace.as_deref(), &pt.name))
     }
 
     /// Get the first service (convenience method)
@@ -161,6 +407,23 @@ impl WsdlModel {
             .map(|p| p.address.as_str())
     }
 
+    /// Find the binding of an operation by operation name
+    ///
+    /// The first binding defining the operation is used.
+    pub fn find_binding_operation(&self, operation_name: &str) -> Option<&BindingOperation> {
+        self.bindings
+            .iter()
+            .flat_map(|b| b.operations.iter())
+            .find(|op| op.name == operation_name)
+    }
+
+    /// Find a top-level policy by the URI of a `<wsp:PolicyReference>`
+    ///
+    /// `#id` refers to the `wsu:Id` of a policy, other URIs to its `Name`.
+    pub fn find_policy(&self, uri: &str) -> Option<&PolicyExpression> {
+        self.policies.get(uri.strip_prefix('#').unwrap_or(uri))
+    }
+
     /// Find the SOAPAction for a given operation name
     ///
     /// Searches through all bindings to find the SOAPAction header value
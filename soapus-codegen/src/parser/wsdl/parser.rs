@@ -5,15 +5,17 @@
 //! WSDL element type is implemented in separate modules:
 //!
 //! - `definitions` - Root element attributes and namespaces
+//! - `import` - Imports of other WSDL documents
 //! - `types` - XSD schema extraction
 //! - `message` - Message definitions
 //! - `port_type` - PortType and operation definitions
 //! - `binding` - SOAP binding and operation details
+//! - `policy` - WS-Policy expressions attached to bindings
 //! - `service` - Service endpoints and ports
 
 use super::WsdlModel;
 use quick_xml::events::Event;
-use quick_xml::Reader;
+use quick_xml::NsReader;
 use std::collections::HashMap;
 use std::error::Error;
 
@@ -37,7 +39,7 @@ pub fn parse_wsdl(xml: &str) -> Result<WsdlModel, Box<dyn Error>> {
     #[cfg(feature = "tracing")]
     info!(xml_size = xml.len(), "Starting WSDL parsing");
 
-    let reader = Reader::from_str(xml);
+    let reader = NsReader::from_str(xml);
     let result = WsdlParser::new(reader).parse();
 
     #[cfg(feature = "tracing")]
@@ -59,7 +61,7 @@ pub fn parse_wsdl(xml: &str) -> Result<WsdlModel, Box<dyn Error>> {
 /// This struct maintains the parsing state while traversing the WSDL document.
 /// It is used by the parsing functions in the submodules.
 pub struct WsdlParser<B: std::io::BufRead> {
-    pub(super) reader: Reader<B>,
+    pub(super) reader: NsReader<B>,
     pub(super) namespaces: HashMap<String, String>,
     pub(super) target_namespace: Option<String>,
     pub(super) model: WsdlModel,
@@ -67,7 +69,7 @@ pub struct WsdlParser<B: std::io::BufRead> {
 
 impl<B: std::io::BufRead> WsdlParser<B> {
     /// Create a new WSDL parser
-    pub fn new(reader: Reader<B>) -> Self {
+    pub fn new(reader: NsReader<B>) -> Self {
         Self {
             reader,
             namespaces: HashMap::new(),
@@ -137,8 +139,23 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                         debug!("Parsing service element");
                         self.parse_service(&ev)?
                     }
+                    b"import" => {
+                        #[cfg(feature = "tracing")]
+                        debug!("Parsing import element");
+                        self.parse_import(&ev)?
+                    }
+                    b"Policy" if self.is_policy_attachment(&ev) => {
+                        #[cfg(feature = "tracing")]
+                        debug!("Parsing policy element");
+                        self.parse_policy(&ev)?
+                    }
                     _ => {}
                 },
+                Event::Empty(ev) if ev.local_name().as_ref() == b"import" => {
+                    #[cfg(feature = "tracing")]
+                    debug!("Parsing import element");
+                    self.parse_import(&ev)?
+                }
                 Event::Eof => break,
                 _ => {}
             }
@@ -154,6 +171,7 @@ impl<B: std::io::BufRead> WsdlParser<B> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::QName;
 
     #[test]
     fn parses_wsdl() {
@@ -362,4 +380,56 @@ mod tests {
         // Check schema
         assert!(model.schema().is_some());
     }
+
+    #[test]
+    fn parses_multiple_schemas() {
+        let wsdl = include_str!("../../../../testdata/wsdl/multischema.wsdl");
+        let model = parse_wsdl(wsdl).unwrap();
+
+        // Every <schema> is kept, keyed by target namespace
+        assert_eq!(model.schemas().count(), 2);
+        let orders = model.find_schema("http://example.com/orders").unwrap();
+        assert!(orders.complex_types.contains_key("GetOrderResponse"));
+        let data = model.find_schema("http://example.com/orders/data").unwrap();
+        assert!(data.complex_types.contains_key("Order"));
+        assert!(data.simple_types.contains_key("OrderStatus"));
+
+        // The primary schema is the one for the WSDL's target namespace
+        assert_eq!(
+            model.schema().unwrap().target_namespace.as_deref(),
+            Some("http://example.com/orders")
+        );
+
+        // QNames are resolved with the in-scope declarations, including the
+        // prefixes declared on <definitions> and those redeclared on inner elements
+        let response = &orders.complex_types["GetOrderResponse"];
+        let fields = &response.sequence.as_ref().unwrap().elements;
+        let namespaces: Vec<_> = fields.iter().map(|f| f.type_.namespace()).collect();
+        assert_eq!(
+            namespaces,
+            vec![
+                Some("http://example.com/orders/data"),
+                Some("http://example.com/orders"),
+                Some("http://example.com/orders/data"),
+            ]
+        );
+
+        // Message lookups compare namespace URIs
+        assert!(model
+            .find_message(&QName::with_namespace(
+                "http://example.com/orders",
+                "x:GetOrderSoapIn"
+            ))
+            .is_some());
+        assert!(model
+            .find_message(&QName::with_namespace(
+                "http://example.com/other",
+                "GetOrderSoapIn"
+            ))
+            .is_none());
+        let port_type = &model.port_types()[0];
+        let input = port_type.operations[0].input.as_ref().unwrap();
+        assert_eq!(input.namespace(), Some("http://example.com/orders"));
+        assert!(model.find_message(input).is_some());
+    }
 }
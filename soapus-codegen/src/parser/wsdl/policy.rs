@@ -0,0 +1,359 @@
+//! Parsing and evaluation of WS-Policy elements attached to WSDL bindings
+
+use quick_xml::events::{BytesStart, Event};
+use std::error::Error;
+
+use crate::parser::QName;
+
+use super::parser::WsdlParser;
+use super::port_type::ADDRESSING_NAMESPACES;
+use super::{PolicyExpression, SecurityPolicy, WsdlModel};
+
+/// Namespaces of WS-Policy 1.5 and of the 2004/09 submission
+const WSP_NAMESPACES: [&str; 2] = [
+    "http://www.w3.org/ns/ws-policy",
+    "http://schemas.xmlsoap.org/ws/2004/09/policy",
+];
+
+/// Namespaces of WS-SecurityPolicy 1.2, 1.2 draft and 1.1
+const SP_NAMESPACES: [&str; 3] = [
+    "http://docs.oasis-open.org/ws-sx/ws-securitypolicy/200702",
+    "http://docs.oasis-open.org/ws-sx/ws-securitypolicy/200512",
+    "http://schemas.xmlsoap.org/ws/2005/07/securitypolicy",
+];
+
+impl<B: std::io::BufRead> WsdlParser<B> {
+    /// Whether an element is `<wsp:Policy>` or `<wsp:PolicyReference>`
+    pub(super) fn is_policy_attachment(&self, e: &BytesStart) -> bool {
+        matches!(e.local_name().as_ref(), b"Policy" | b"PolicyReference")
+            && is_policy_namespace(&QName::resolve(
+                &String::from_utf8_lossy(e.name().as_ref()),
+                &self.reader,
+            ))
+    }
+
+    /// Parse a top-level <wsp:Policy> element
+    ///
+    /// The policy is kept in the model under its `wsu:Id` (or `Name`) for
+    /// `<wsp:PolicyReference>`s to refer to. Policies without either are
+    /// dropped.
+    ///
+    /// Example:
+    /// ```xml
+    /// <wsp:Policy wsu:Id="Binding_policy">
+    ///   <wsp:ExactlyOne>
+    ///     <wsp:All>
+    ///       <sp:AsymmetricBinding>...</sp:AsymmetricBinding>
+    ///     </wsp:All>
+    ///   </wsp:ExactlyOne>
+    /// </wsp:Policy>
+    /// ```
+    pub(super) fn parse_policy(&mut self, ev: &BytesStart) -> Result<(), Box<dyn Error>> {
+        let mut id = None;
+        for attr in ev.attributes().with_checks(false) {
+            let attr = attr?;
+            match attr.key.local_name().as_ref() {
+                b"Id" => id = Some(attr.unescape_value()?.to_string()),
+                b"Name" if id.is_none() => id = Some(attr.unescape_value()?.to_string()),
+                _ => {}
+            }
+        }
+        let policy = self.parse_policy_expression(ev, false)?;
+        if let Some(id) = id {
+            self.model.policies.insert(id, policy);
+        }
+        Ok(())
+    }
+
+    /// Parse a `<wsp:Policy>`, `<wsp:PolicyReference>` or assertion element
+    ///
+    /// The content of a start element is read up to its end tag.
+    pub(super) fn parse_policy_expression(
+        &mut self,
+        ev: &BytesStart,
+        empty: bool,
+    ) -> Result<PolicyExpression, Box<dyn Error>> {
+        let name = QName::resolve(&String::from_utf8_lossy(ev.name().as_ref()), &self.reader);
+        let mut uri = String::new();
+        let mut optional = false;
+        for attr in ev.attributes().with_checks(false) {
+            let attr = attr?;
+            match attr.key.local_name().as_ref() {
+                b"URI" => uri = attr.unescape_value()?.to_string(),
+                b"Optional" => optional = attr.unescape_value()?.trim() == "true",
+                _ => {}
+            }
+        }
+
+        let nested = if empty {
+            Vec::new()
+        } else {
+            self.parse_policy_children()?
+        };
+        if !is_policy_namespace(&name) {
+            return Ok(PolicyExpression::Assertion {
+                name,
+                optional,
+                nested,
+            });
+        }
+        Ok(match name.local_name() {
+            "ExactlyOne" => PolicyExpression::ExactlyOne(nested),
+            "PolicyReference" => PolicyExpression::Reference(uri),
+            _ => PolicyExpression::All(nested),
+        })
+    }
+
+    /// Parse the child elements of a policy element up to its end tag
+    fn parse_policy_children(&mut self) -> Result<Vec<PolicyExpression>, Box<dyn Error>> {
+        let mut children = Vec::new();
+        let mut buf = Vec::new();
+        loop {
+            match self.reader.read_event_into(&mut buf)? {
+                Event::Start(e) => {
+                    children.push(self.parse_policy_expression(&e, false)?);
+                }
+                Event::Empty(e) => {
+                    children.push(self.parse_policy_expression(&e, true)?);
+                }
+                Event::End(_) | Event::Eof => break,
+                _ => {}
+            }
+            buf.clear()
+        }
+        Ok(children)
+    }
+}
+
+impl WsdlModel {
+    /// Find the WS-SecurityPolicy assertions in effect for an operation
+    ///
+    /// The first binding defining the operation is used. Returns `None` if
+    /// no security assertion applies.
+    pub fn security_policy(&self, operation_name: &str) -> Option<SecurityPolicy> {
+        let (binding, operation) = self.bindings.iter().find_map(|binding| {
+            binding
+                .operations
+                .iter()
+                .find(|op| op.name == operation_name)
+                .map(|op| (binding, op))
+        })?;
+        let common = binding.policies.iter().chain(&operation.policies);
+        let input: Vec<_> = common.clone().chain(&operation.input_policies).collect();
+        let output: Vec<_> = common.chain(&operation.output_policies).collect();
+
+        let mut policy = SecurityPolicy::default();
+        self.visit_assertions(
+            &input,
+            0,
+            is_security_policy,
+            &mut |name, nested| match name.local_name() {
+                "UsernameToken" => policy.username_token = true,
+                "HashPassword" => policy.hash_password = true,
+                "IncludeTimestamp" => policy.include_timestamp = true,
+                "AsymmetricBinding" => policy.asymmetric_binding = true,
+                "SignedParts" => policy.sign_body |= includes_body(nested),
+                "EncryptedParts" => policy.encrypt_body |= includes_body(nested),
+                suite if is_algorithm_suite(suite) => {
+                    policy.algorithm_suite = Some(suite.to_string())
+                }
+                _ => {}
+            },
+        );
+        self.visit_assertions(&output, 0, is_security_policy, &mut |name, nested| {
+            if name.local_name() == "SignedParts" {
+                policy.signed_response |= includes_body(nested);
+            }
+        });
+        (policy != SecurityPolicy::default()).then_some(policy)
+    }
+
+    /// Whether the binding of an operation requires WS-Addressing
+    ///
+    /// That is a `wsam:Addressing` or `wsaw:UsingAddressing` assertion in the
+    /// policies of the binding or the operation, or a `<wsaw:UsingAddressing>`
+    /// element of the binding. The first binding defining the operation is
+    /// used.
+    pub fn uses_addressing(&self, operation_name: &str) -> bool {
+        let Some((binding, operation)) = self.bindings.iter().find_map(|binding| {
+            binding
+                .operations
+                .iter()
+                .find(|op| op.name == operation_name)
+                .map(|op| (binding, op))
+        }) else {
+            return false;
+        };
+        let policies: Vec<_> = binding.policies.iter().chain(&operation.policies).collect();
+        let mut uses_addressing = false;
+        self.visit_assertions(&policies, 0, is_addressing_assertion, &mut |_, _| {
+            uses_addressing = true
+        });
+        uses_addressing
+    }
+
+    /// Call `visit` with the name and nested expressions of every
+    /// assertion that applies and is `relevant`, including nested ones
+    ///
+    /// References are followed up to a depth of 8 to stop at cycles.
+    fn visit_assertions<'m>(
+        &'m self,
+        expressions: &[&'m PolicyExpression],
+        depth: usize,
+        relevant: fn(&QName) -> bool,
+        visit: &mut impl FnMut(&'m QName, &'m [PolicyExpression]),
+    ) {
+        for expression in expressions {
+            match expression {
+                PolicyExpression::All(expressions) => self.visit_assertions(
+                    &expressions.iter().collect::<Vec<_>>(),
+                    depth,
+                    relevant,
+                    visit,
+                ),
+                PolicyExpression::ExactlyOne(alternatives) => {
+                    if let Some(first) = alternatives.first() {
+                        self.visit_assertions(&[first], depth, relevant, visit)
+                    }
+                }
+                PolicyExpression::Reference(uri) if depth < 8 => {
+                    if let Some(policy) = self.find_policy(uri) {
+                        self.visit_assertions(&[policy], depth + 1, relevant, visit)
+                    }
+                }
+                PolicyExpression::Reference(_) => {}
+                PolicyExpression::Assertion {
+                    name,
+                    optional,
+                    nested,
+                } => {
+                    if *optional || !relevant(name) {
+                        continue;
+                    }
+                    visit(name, nested);
+                    self.visit_assertions(
+                        &nested.iter().collect::<Vec<_>>(),
+                        depth,
+                        relevant,
+                        visit,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Whether a name is in a WS-Policy namespace
+fn is_policy_namespace(name: &QName) -> bool {
+    name.namespace()
+        .is_some_and(|namespace| WSP_NAMESPACES.contains(&namespace))
+}
+
+/// Whether a name is in a WS-SecurityPolicy namespace
+fn is_security_policy(name: &QName) -> bool {
+    name.namespace()
+        .is_some_and(|namespace| SP_NAMESPACES.contains(&namespace))
+}
+
+/// Whether a name is `wsam:Addressing` or `wsaw:UsingAddressing`
+fn is_addressing_assertion(name: &QName) -> bool {
+    matches!(name.local_name(), "Addressing" | "UsingAddressing")
+        && name
+            .namespace()
+            .is_some_and(|namespace| ADDRESSING_NAMESPACES.contains(&namespace))
+}
+
+/// Whether the content of `<sp:SignedParts>` or `<sp:EncryptedParts>` covers the Body
+///
+/// Without any part listed, the Body and all headers are covered.
+fn includes_body(nested: &[PolicyExpression]) -> bool {
+    nested.is_empty()
+        || nested.iter().any(|expression| {
+            matches!(expression, PolicyExpression::Assertion { name, .. } if name.local_name() == "Body")
+        })
+}
+
+/// Whether an assertion names an algorithm suite, e.g. `Basic256` or `TripleDesRsa15`
+fn is_algorithm_suite(local_name: &str) -> bool {
+    local_name.starts_with("Basic") || local_name.starts_with("TripleDes")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{parse_wsdl, PolicyExpression, QName, SecurityPolicy};
+
+    #[test]
+    fn parses_policies() {
+        let wsdl = include_str!("../../../../testdata/wsdl/ledger.wsdl");
+        let model = parse_wsdl(wsdl).unwrap();
+
+        // Policies attached by reference to the binding, the input and the output
+        let binding = &model.bindings[0];
+        assert_eq!(
+            binding.policies,
+            vec![PolicyExpression::Reference(
+                "#LedgerBinding_policy".to_string()
+            )]
+        );
+        assert!(model.find_policy("#LedgerBinding_policy").is_some());
+        let operation = model.find_binding_operation("PostEntry").unwrap();
+        assert!(operation.policies.is_empty());
+        assert_eq!(operation.input_policies.len(), 1);
+        assert_eq!(operation.output_policies.len(), 1);
+
+        // An inline policy of the operation, assertions keep their nested policy
+        let operation = model.find_binding_operation("GetBalance").unwrap();
+        let PolicyExpression::All(policy) = &operation.policies[0] else {
+            panic!("expected a policy, got {:?}", operation.policies);
+        };
+        let PolicyExpression::ExactlyOne(alternatives) = &policy[0] else {
+            panic!("expected alternatives, got {:?}", policy);
+        };
+        assert_eq!(alternatives.len(), 2);
+        let PolicyExpression::Assertion { name, nested, .. } = &alternatives[0] else {
+            panic!("expected an assertion, got {:?}", alternatives[0]);
+        };
+        assert_eq!(
+            name,
+            &QName::with_namespace(
+                "http://docs.oasis-open.org/ws-sx/ws-securitypolicy/200702",
+                "SignedSupportingTokens"
+            )
+        );
+        assert!(matches!(nested[..], [PolicyExpression::All(_)]));
+
+        // The security assertions of the binding, operation and message policies
+        assert_eq!(
+            model.security_policy("PostEntry"),
+            Some(SecurityPolicy {
+                include_timestamp: true,
+                asymmetric_binding: true,
+                algorithm_suite: Some("Basic128Sha256".to_string()),
+                sign_body: true,
+                encrypt_body: true,
+                signed_response: true,
+                ..Default::default()
+            })
+        );
+        // Of two alternatives, the first one applies
+        assert_eq!(
+            model.security_policy("GetBalance"),
+            Some(SecurityPolicy {
+                username_token: true,
+                hash_password: true,
+                include_timestamp: true,
+                asymmetric_binding: true,
+                algorithm_suite: Some("Basic128Sha256".to_string()),
+                ..Default::default()
+            })
+        );
+
+        // WSDLs without policies
+        assert_eq!(
+            parse_wsdl(include_str!("../../../../testdata/wsdl/calculator.wsdl"))
+                .unwrap()
+                .security_policy("Add"),
+            None
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Parsing of WSDL portType elements
 
 use crate::parser::QName;
+use quick_xml::events::attributes::Attribute;
 use quick_xml::events::{BytesStart, Event};
 use std::error::Error;
 #[cfg(feature = "tracing")]
@@ -9,6 +10,14 @@ use tracing::warn;
 use super::parser::WsdlParser;
 use super::{Fault, PortType, PortTypeOperation};
 
+/// Namespaces of WS-Addressing metadata and the WSDL binding of WS-Addressing
+/// 1.0, and of the 2004/08 submission
+pub(super) const ADDRESSING_NAMESPACES: [&str; 3] = [
+    "http://www.w3.org/2007/05/addressing/metadata",
+    "http://www.w3.org/2006/05/addressing/wsdl",
+    "http://schemas.xmlsoap.org/ws/2004/08/addressing",
+];
+
 impl<B: std::io::BufRead> WsdlParser<B> {
     /// Parse a <portType> element
     ///
@@ -25,6 +34,9 @@ impl<B: std::io::BufRead> WsdlParser<B> {
     ///   </operation>
     /// </portType>
     /// ```
+    ///
+    /// The `wsam:Action` (or `wsaw:Action`) attributes of inputs and outputs
+    /// are kept as the WS-Addressing actions of the operation.
     pub(super) fn parse_port_type(&mut self, ev: &BytesStart) -> Result<(), Box<dyn Error>> {
         let mut name = None;
         for attr in ev.attributes().with_checks(false) {
@@ -39,6 +51,8 @@ impl<B: std::io::BufRead> WsdlParser<B> {
         let mut current_op_name = None;
         let mut current_input = None;
         let mut current_output = None;
+        let mut current_input_action = None;
+        let mut current_output_action = None;
         let mut current_documentation = None;
 
         let mut buf = Vec::new();
@@ -49,6 +63,8 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                     current_op_name = None;
                     current_input = None;
                     current_output = None;
+                    current_input_action = None;
+                    current_output_action = None;
                     current_documentation = None;
 
                     for attr in e.attributes().with_checks(false) {
@@ -79,7 +95,10 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                     for attr in e.attributes().with_checks(false) {
                         let attr = attr?;
                         if attr.key.as_ref() == b"message" {
-                            current_input = Some(QName(attr.unescape_value()?.to_string()));
+                            current_input =
+                                Some(QName::resolve(&attr.unescape_value()?, &self.reader));
+                        } else if self.is_addressing_action(&attr) {
+                            current_input_action = Some(attr.unescape_value()?.to_string());
                         }
                     }
                 }
@@ -87,7 +106,10 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                     for attr in e.attributes().with_checks(false) {
                         let attr = attr?;
                         if attr.key.as_ref() == b"message" {
-                            current_output = Some(QName(attr.unescape_value()?.to_string()));
+                            current_output =
+                                Some(QName::resolve(&attr.unescape_value()?, &self.reader));
+                        } else if self.is_addressing_action(&attr) {
+                            current_output_action = Some(attr.unescape_value()?.to_string());
                         }
                     }
                 }
@@ -99,7 +121,8 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                         match attr.key.as_ref() {
                             b"name" => fault_name = Some(attr.unescape_value()?.to_string()),
                             b"message" => {
-                                fault_message = Some(QName(attr.unescape_value()?.to_string()))
+                                fault_message =
+                                    Some(QName::resolve(&attr.unescape_value()?, &self.reader))
                             }
                             _ => {}
                         }
@@ -129,6 +152,8 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                             name,
                             input: current_input.take(),
                             output: current_output.take(),
+                            input_action: current_input_action.take(),
+                            output_action: current_output_action.take(),
                             faults,
                             documentation: current_documentation.take(),
                         });
@@ -141,7 +166,56 @@ impl<B: std::io::BufRead> WsdlParser<B> {
             buf.clear()
         }
 
-        self.model.port_types.push(PortType { name, operations });
+        self.model.port_types.push(PortType {
+            name,
+            namespace: self.target_namespace.clone(),
+            operations,
+        });
         Ok(())
     }
+
+    /// Whether an attribute is a `wsam:Action`, `wsaw:Action` or `wsa:Action`
+    fn is_addressing_action(&self, attr: &Attribute) -> bool {
+        attr.key.local_name().as_ref() == b"Action"
+            && attr.key.prefix().is_some()
+            && QName::resolve(&String::from_utf8_lossy(attr.key.as_ref()), &self.reader)
+                .namespace()
+                .is_some_and(|namespace| ADDRESSING_NAMESPACES.contains(&namespace))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_wsdl;
+
+    #[test]
+    fn parses_addressing() {
+        let wsdl = include_str!("../../../../testdata/wsdl/tickets.wsdl");
+        let model = parse_wsdl(wsdl).unwrap();
+
+        // Actions of the WSDL binding and of the metadata namespace
+        let operations = &model.port_types[0].operations;
+        assert_eq!(
+            operations[0].input_action.as_deref(),
+            Some("http://tempuri.org/ITicketService/OpenTicket")
+        );
+        assert_eq!(
+            operations[0].output_action.as_deref(),
+            Some("http://tempuri.org/ITicketService/OpenTicketResponse")
+        );
+        assert_eq!(
+            operations[1].input_action.as_deref(),
+            Some("urn:tickets:close")
+        );
+
+        // <wsaw:UsingAddressing> in the policy of the binding
+        assert!(model.uses_addressing("OpenTicket"));
+        assert!(model.uses_addressing("CloseTicket"));
+
+        // Neither actions nor addressing without WS-Addressing metadata
+        let wsdl = include_str!("../../../../testdata/wsdl/ledger.wsdl");
+        let model = parse_wsdl(wsdl).unwrap();
+        assert!(model.port_types[0].operations[0].input_action.is_none());
+        assert!(!model.uses_addressing("PostEntry"));
+    }
 }
@@ -51,7 +51,10 @@ impl<B: std::io::BufRead> WsdlParser<B> {
                         let attr = attr?;
                         match attr.key.as_ref() {
                             b"name" => port_name = Some(attr.unescape_value()?.to_string()),
-                            b"binding" => binding = Some(QName(attr.unescape_value()?.to_string())),
+                            b"binding" => {
+                                binding =
+                                    Some(QName::resolve(&attr.unescape_value()?, &self.reader))
+                            }
                             _ => {}
                         }
                     }
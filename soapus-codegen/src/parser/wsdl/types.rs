@@ -2,16 +2,21 @@
 
 use crate::parser::parse_schema;
 use quick_xml::events::Event;
+use quick_xml::name::PrefixDeclaration;
 use std::error::Error;
 
 use super::parser::WsdlParser;
 
 impl<B: std::io::BufRead> WsdlParser<B> {
-    /// Parse the <types> section and extract embedded XSD schema
+    /// Parse the <types> section and extract embedded XSD schemas
     ///
     /// The types section contains XML Schema definitions that define
     /// the data types used in SOAP messages. This function extracts
-    /// the embedded schema and parses it using the XSD parser.
+    /// every embedded schema and parses it using the XSD parser.
+    ///
+    /// All namespace declarations in scope at the <schema> element (including
+    /// those on <definitions>) are copied onto each extracted schema, so QNames
+    /// inside it resolve exactly as they do in the WSDL document.
     pub(super) fn parse_types(&mut self) -> Result<(), Box<dyn Error>> {
         let mut buf = Vec::new();
         let mut schema_xml = String::new();
@@ -20,17 +25,35 @@ impl<B: std::io::BufRead> WsdlParser<B> {
 
         loop {
             match self.reader.read_event_into(&mut buf)? {
-                Event::Start(e) if e.local_name().as_ref() == b"schema" => {
+                Event::Start(e) if !in_schema && e.local_name().as_ref() == b"schema" => {
                     in_schema = true;
                     _depth = 1;
+                    schema_xml.clear();
                     schema_xml.push_str("<schema");
-                    // Add attributes
+                    // Add attributes, namespace declarations are added below
                     for attr in e.attributes().with_checks(false) {
                         let attr = attr?;
+                        if attr.key.as_namespace_binding().is_some() {
+                            continue;
+                        }
                         let key = std::str::from_utf8(attr.key.as_ref())?;
                         let value = attr.unescape_value()?;
                         schema_xml.push_str(&format!(" {}=\"{}\"", key, value));
                     }
+                    // Inherit all in-scope namespace declarations
+                    for (prefix, namespace) in self.reader.prefixes() {
+                        let uri = std::str::from_utf8(namespace.as_ref())?;
+                        match prefix {
+                            PrefixDeclaration::Default => {
+                                schema_xml.push_str(&format!(" xmlns=\"{}\"", uri))
+                            }
+                            PrefixDeclaration::Named(prefix) => schema_xml.push_str(&format!(
+                                " xmlns:{}=\"{}\"",
+                                std::str::from_utf8(prefix)?,
+                                uri
+                            )),
+                        }
+                    }
                     schema_xml.push('>');
                 }
                 Event::Start(e) if in_schema => {
@@ -50,10 +73,10 @@ impl<B: std::io::BufRead> WsdlParser<B> {
 
                     // Parse the extracted schema
                     if let Ok(schema) = parse_schema(&schema_xml) {
-                        self.model.set_schema(schema);
+                        self.model.add_schema(schema);
                     }
 
-                    break;
+                    in_schema = false;
                 }
                 Event::End(e) if in_schema => {
                     _depth -= 1;
@@ -0,0 +1,183 @@
+//! Parsing of XSD attribute and attributeGroup declarations
+
+use crate::parser::xsd::{Attribute, AttributeGroup, AttributeUse};
+use crate::parser::QName;
+use quick_xml::events::{BytesStart, Event};
+use std::error::Error;
+
+use super::parser::SchemaParser;
+
+impl<B: std::io::BufRead> SchemaParser<B> {
+    /// Parse an <attribute> declaration within a complexType or attributeGroup
+    ///
+    /// Attributes referencing a global declaration (`ref="xml:lang"`) are
+    /// taken by the local name of the reference. Inline simple types are
+    /// skipped, those attributes are treated as strings. The item type of a
+    /// SOAP encoded array is kept without its dimensions.
+    ///
+    /// Example:
+    /// ```xml
+    /// <attribute name="currency" type="xs:string" use="required"/>
+    /// <attribute name="version" type="xs:int" default="1"/>
+    /// <attribute ref="soapenc:arrayType" wsdl:arrayType="xsd:string[]"/>
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - The attribute's start tag
+    /// * `has_content` - If true, this is a Start event and the content is skipped
+    pub(super) fn parse_attribute(
+        &mut self,
+        e: &BytesStart,
+        has_content: bool,
+    ) -> Result<Option<Attribute>, Box<dyn Error>> {
+        let mut attribute = Attribute::default();
+
+        for attr in e.attributes().with_checks(false) {
+            let attr = attr?;
+            let val = attr.unescape_value()?;
+            match attr.key.as_ref() {
+                b"name" => attribute.name = val.to_string(),
+                b"ref" => attribute.name = QName::new(val.as_ref()).local_name().to_string(),
+                b"type" => attribute.type_ = Some(QName::resolve(&val, &self.reader)),
+                b"use" => {
+                    attribute.use_ = match val.as_ref() {
+                        "required" => AttributeUse::Required,
+                        "prohibited" => AttributeUse::Prohibited,
+                        _ => AttributeUse::Optional,
+                    }
+                }
+                b"default" => attribute.default = Some(val.to_string()),
+                b"fixed" => attribute.fixed = Some(val.to_string()),
+                _ if attr.key.local_name().as_ref() == b"arrayType" => {
+                    let item_type = val.split('[').next().unwrap_or_default();
+                    attribute.array_type = Some(QName::resolve(item_type, &self.reader));
+                }
+                _ => {}
+            }
+        }
+
+        if has_content {
+            self.skip_element()?;
+        }
+
+        Ok(Some(attribute).filter(|a| !a.name.is_empty()))
+    }
+
+    /// Parse the reference of an <attributeGroup ref="..."> within a complexType
+    pub(super) fn parse_attribute_group_ref(
+        &mut self,
+        e: &BytesStart,
+        has_content: bool,
+    ) -> Result<Option<QName>, Box<dyn Error>> {
+        let reference = e
+            .try_get_attribute("ref")?
+            .map(|a| a.unescape_value().map(|v| QName::resolve(&v, &self.reader)))
+            .transpose()?;
+
+        if has_content {
+            self.skip_element()?;
+        }
+
+        Ok(reference)
+    }
+
+    /// Parse a named top-level <attributeGroup> definition
+    ///
+    /// Example:
+    /// ```xml
+    /// <attributeGroup name="Versioned">
+    ///   <attribute name="version" type="xs:int" use="required"/>
+    ///   <attributeGroup ref="tns:Audited"/>
+    /// </attributeGroup>
+    /// ```
+    pub(super) fn parse_attribute_group(&mut self, e: &BytesStart) -> Result<(), Box<dyn Error>> {
+        let mut group = AttributeGroup {
+            name: e
+                .try_get_attribute("name")?
+                .map(|a| a.unescape_value().map(|v| v.into_owned()))
+                .transpose()?
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        loop {
+            match self.reader.read_event_into(&mut buf)? {
+                Event::Start(e) if e.local_name().as_ref() == b"attribute" => {
+                    group.attributes.extend(self.parse_attribute(&e, true)?);
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"attribute" => {
+                    group.attributes.extend(self.parse_attribute(&e, false)?);
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"attributeGroup" => {
+                    group
+                        .attribute_groups
+                        .extend(self.parse_attribute_group_ref(&e, true)?);
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"attributeGroup" => {
+                    group
+                        .attribute_groups
+                        .extend(self.parse_attribute_group_ref(&e, false)?);
+                }
+                Event::Start(_) => self.skip_element()?,
+                Event::End(e) if e.local_name().as_ref() == b"attributeGroup" => break,
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        if !group.name.is_empty() {
+            self.model
+                .attribute_groups
+                .insert(group.name.clone(), group);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::xsd::AttributeUse;
+
+    #[test]
+    fn parses_attributes() {
+        let wsdl = include_str!("../../../../testdata/wsdl/invoices.wsdl");
+        let model = crate::parser::parse_wsdl(wsdl).unwrap();
+        let schema = model.schema().unwrap();
+
+        let invoice = &schema.complex_types["Invoice"];
+        let names: Vec<_> = invoice.attributes.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "currency", "paid", "status", "note"]);
+
+        let id = &invoice.attributes[0];
+        assert_eq!(id.use_, AttributeUse::Required);
+        assert!(id
+            .type_
+            .as_ref()
+            .unwrap()
+            .refers_to(Some(crate::parser::XSD_NAMESPACE), "ID"));
+        assert_eq!(invoice.attributes[1].default.as_deref(), Some("EUR"));
+        assert_eq!(invoice.attributes[1].use_, AttributeUse::Optional);
+
+        // Inline simple types leave the type open
+        assert!(invoice.attributes[4].type_.is_none());
+
+        // Attribute group references and definitions
+        assert_eq!(invoice.attribute_groups.len(), 1);
+        assert!(
+            invoice.attribute_groups[0].refers_to(Some("http://example.com/invoices"), "Versioned")
+        );
+        let versioned = &schema.attribute_groups["Versioned"];
+        assert_eq!(versioned.attributes.len(), 2);
+        assert_eq!(versioned.attributes[1].fixed.as_deref(), Some("2.0"));
+        assert_eq!(versioned.attribute_groups.len(), 1);
+
+        // Attributes inside derivations
+        let credit_note = &schema.complex_types["CreditNote"];
+        assert_eq!(credit_note.attributes[0].name, "reason");
+        let draft = &schema.complex_types["DraftInvoice"];
+        assert_eq!(draft.attributes[0].use_, AttributeUse::Prohibited);
+    }
+}
@@ -0,0 +1,109 @@
+//! Parsing of XSD choice compositors
+
+use crate::parser::xsd::Choice;
+use quick_xml::events::{BytesStart, Event};
+use std::error::Error;
+
+use super::parser::SchemaParser;
+
+impl<B: std::io::BufRead> SchemaParser<B> {
+    /// Parse a <choice> compositor
+    ///
+    /// Exactly one of the alternatives appears in the instance document (or
+    /// several, one after another, if the choice has maxOccurs > 1).
+    /// Choices nested directly in a choice are flattened into it, since they
+    /// only add alternatives, and so are the alternatives of referenced groups
+    /// once those are resolved.
+    ///
+    /// Example:
+    /// ```xml
+    /// <choice minOccurs="0">
+    ///   <element name="card" type="tns:Card"/>
+    ///   <element name="iban" type="xs:string"/>
+    /// </choice>
+    /// ```
+    pub(super) fn parse_choice(&mut self, e: &BytesStart) -> Result<Choice, Box<dyn Error>> {
+        let mut choice = Choice {
+            min_occurs: 1,
+            ..Default::default()
+        };
+
+        for attr in e.attributes().with_checks(false) {
+            let attr = attr?;
+            let val = attr.unescape_value()?;
+            match attr.key.as_ref() {
+                b"minOccurs" => choice.min_occurs = val.parse().unwrap_or(1),
+                b"maxOccurs" => choice.max_occurs = Some(val.to_string()),
+                _ => {}
+            }
+        }
+
+        let mut buf = Vec::new();
+        loop {
+            match self.reader.read_event_into(&mut buf)? {
+                Event::Start(e) if e.local_name().as_ref() == b"element" => {
+                    let elem = self.parse_sequence_element(&e, true)?;
+                    choice.elements.push(elem);
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"element" => {
+                    let elem = self.parse_sequence_element(&e, false)?;
+                    choice.elements.push(elem);
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"choice" => {
+                    let nested = self.parse_choice(&e)?;
+                    choice.elements.extend(nested.elements);
+                    choice.groups.extend(nested.groups);
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"group" => {
+                    choice.groups.extend(self.parse_group_ref(&e, true)?);
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"group" => {
+                    choice.groups.extend(self.parse_group_ref(&e, false)?);
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"sequence" => {
+                    // Alternatives consisting of several elements are not supported yet
+                    self.skip_element()?;
+                }
+                Event::End(e) if e.local_name().as_ref() == b"choice" => break,
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(choice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parses_choices() {
+        let wsdl = include_str!("../../../../testdata/wsdl/payments.wsdl");
+        let model = crate::parser::parse_wsdl(wsdl).unwrap();
+        let schema = model.schema().unwrap();
+
+        // Choice as the whole content, nested choices are flattened
+        let method = schema.complex_types["PaymentMethod"]
+            .choice
+            .as_ref()
+            .unwrap();
+        let names: Vec<_> = method.elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["card", "iban", "voucher"]);
+        assert_eq!(method.min_occurs, 1);
+
+        // Choice nested in a sequence keeps its position and occurrence
+        let payer = schema.complex_types["Payer"].sequence.as_ref().unwrap();
+        let names: Vec<_> = payer.elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["name", "reference"]);
+        assert_eq!(payer.choices.len(), 1);
+        assert_eq!(payer.choices[0].position, 1);
+        assert_eq!(payer.choices[0].max_occurs.as_deref(), Some("unbounded"));
+        assert_eq!(payer.choices[0].elements.len(), 3);
+
+        let response = schema.complex_types["SubmitPaymentResponse"]
+            .sequence
+            .as_ref()
+            .unwrap();
+        assert_eq!(response.choices[0].min_occurs, 0);
+    }
+}
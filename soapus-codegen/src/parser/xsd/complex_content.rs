@@ -0,0 +1,122 @@
+//! Parsing of XSD complexContent derivations (type inheritance)
+
+use crate::parser::xsd::{ComplexType, Derivation};
+use crate::parser::QName;
+use quick_xml::events::{BytesStart, Event};
+use std::error::Error;
+
+use super::parser::SchemaParser;
+
+impl<B: std::io::BufRead> SchemaParser<B> {
+    /// Parse a <complexContent> element
+    ///
+    /// Derives a complexType from a base type, either by extension (base
+    /// content followed by the new content) or by restriction (the content
+    /// is restated in full, possibly with fewer elements).
+    ///
+    /// Example:
+    /// ```xml
+    /// <complexType name="Employee">
+    ///   <complexContent>
+    ///     <extension base="tns:Person">
+    ///       <sequence>
+    ///         <element name="employeeId" type="xs:string"/>
+    ///       </sequence>
+    ///     </extension>
+    ///   </complexContent>
+    /// </complexType>
+    /// ```
+    pub(super) fn parse_complex_content(
+        &mut self,
+        complex_type: &mut ComplexType,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut buf = Vec::new();
+        loop {
+            match self.reader.read_event_into(&mut buf)? {
+                Event::Start(e)
+                    if matches!(e.local_name().as_ref(), b"extension" | b"restriction") =>
+                {
+                    self.parse_derivation(&e, complex_type)?;
+                    let end = e.local_name().as_ref().to_vec();
+                    self.parse_content_model(complex_type, &end)?;
+                }
+                Event::Empty(e)
+                    if matches!(e.local_name().as_ref(), b"extension" | b"restriction") =>
+                {
+                    // Derivation without own content like <xs:extension base="..."/>
+                    self.parse_derivation(&e, complex_type)?;
+                }
+                Event::End(e) if e.local_name().as_ref() == b"complexContent" => break,
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        Ok(())
+    }
+
+    /// Record the base type and kind of an <extension> or <restriction>
+    pub(super) fn parse_derivation(
+        &mut self,
+        e: &BytesStart,
+        complex_type: &mut ComplexType,
+    ) -> Result<(), Box<dyn Error>> {
+        complex_type.base_type = e
+            .try_get_attribute("base")?
+            .map(|a| a.unescape_value().map(|v| QName::resolve(&v, &self.reader)))
+            .transpose()?;
+        complex_type.derivation = Some(match e.local_name().as_ref() {
+            b"extension" => Derivation::Extension,
+            _ => Derivation::Restriction,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::xsd::Derivation;
+
+    #[test]
+    fn parses_complex_content() {
+        let wsdl = include_str!("../../../../testdata/wsdl/employees.wsdl");
+        let model = crate::parser::parse_wsdl(wsdl).unwrap();
+        let schema = model.schema().unwrap();
+        let namespace = Some("http://example.com/employees");
+
+        assert!(schema.complex_types["Party"].is_abstract);
+        assert!(!schema.complex_types["Person"].is_abstract);
+
+        // Extension keeps the base and only the type's own content
+        let employee = &schema.complex_types["Employee"];
+        assert_eq!(employee.derivation, Some(Derivation::Extension));
+        assert!(employee
+            .base_type
+            .as_ref()
+            .unwrap()
+            .refers_to(namespace, "Person"));
+        let names: Vec<_> = employee
+            .sequence
+            .as_ref()
+            .unwrap()
+            .elements
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["employeeId", "department"]);
+
+        // Choices inside an extension are parsed as well
+        let manager = schema.complex_types["Manager"].sequence.as_ref().unwrap();
+        assert_eq!(manager.choices.len(), 1);
+        assert_eq!(manager.choices[0].position, 1);
+
+        // Empty extension
+        let contact = &schema.complex_types["Contact"];
+        assert_eq!(contact.derivation, Some(Derivation::Extension));
+        assert!(contact.sequence.is_none());
+
+        let anonymous = &schema.complex_types["AnonymousPerson"];
+        assert_eq!(anonymous.derivation, Some(Derivation::Restriction));
+        assert_eq!(anonymous.sequence.as_ref().unwrap().elements.len(), 1);
+    }
+}
@@ -1,6 +1,7 @@
 //! Parsing of XSD complexType definitions
 
 use crate::parser::xsd::{ComplexType, Sequence};
+use crate::parser::QName;
 use quick_xml::events::{BytesStart, Event};
 use std::error::Error;
 
@@ -13,7 +14,10 @@ impl<B: std::io::BufRead> SchemaParser<B> {
     /// They can contain:
     /// - <sequence> - Ordered sequence of elements
     /// - <all> - Unordered collection of elements
-    /// - <choice> - Alternative elements (not yet fully supported)
+    /// - <choice> - Alternative elements
+    /// - <group> - Reference to a named model group
+    /// - <attribute>/<attributeGroup> - Attributes of the element
+    /// - <complexContent>/<simpleContent> - Derivation from a base type
     ///
     /// Example:
     /// ```xml
@@ -32,7 +36,40 @@ impl<B: std::io::BufRead> SchemaParser<B> {
         if let Some(n) = name {
             complex_type.name = n;
         }
+        if let Some(a) = e.try_get_attribute("abstract")? {
+            complex_type.is_abstract = matches!(a.unescape_value()?.as_ref(), "true" | "1");
+        }
+
+        self.parse_complex_type_content(&mut complex_type)?;
+
+        if !complex_type.name.is_empty() {
+            self.model
+                .complex_types
+                .insert(complex_type.name.clone(), complex_type);
+        }
+        Ok(())
+    }
 
+    /// Parse the content of a <complexType> up to its end tag
+    ///
+    /// Used for named complexTypes as well as inline ones within <element>.
+    /// Anonymous types nested in the content are named after this type.
+    pub(super) fn parse_complex_type_content(
+        &mut self,
+        complex_type: &mut ComplexType,
+    ) -> Result<(), Box<dyn Error>> {
+        self.type_scope.push(complex_type.name.clone());
+        let result = self.parse_content_model(complex_type, b"complexType");
+        self.type_scope.pop();
+        result
+    }
+
+    /// Parse compositors and derivations up to the end tag named `end`
+    pub(super) fn parse_content_model(
+        &mut self,
+        complex_type: &mut ComplexType,
+        end: &[u8],
+    ) -> Result<(), Box<dyn Error>> {
         let mut buf = Vec::new();
         loop {
             match self.reader.read_event_into(&mut buf)? {
@@ -51,18 +88,107 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                     // Empty all like <xs:all/>
                     complex_type.sequence = Some(Sequence::default());
                 }
-                Event::End(e) if e.local_name().as_ref() == b"complexType" => break,
+                Event::Start(e) if e.local_name().as_ref() == b"choice" => {
+                    complex_type.choice = Some(self.parse_choice(&e)?);
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"group" => {
+                    let sequence = complex_type.sequence.get_or_insert_with(Sequence::default);
+                    self.push_group_ref(sequence, &e, true)?;
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"group" => {
+                    let sequence = complex_type.sequence.get_or_insert_with(Sequence::default);
+                    self.push_group_ref(sequence, &e, false)?;
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"complexContent" => {
+                    self.parse_complex_content(complex_type)?;
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"simpleContent" => {
+                    self.parse_simple_content(complex_type)?;
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"simpleType" => {
+                    // Inline base of a simpleContent restriction, its end tag would end the loop
+                    self.skip_element()?;
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"attribute" => {
+                    complex_type
+                        .attributes
+                        .extend(self.parse_attribute(&e, true)?);
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"attribute" => {
+                    complex_type
+                        .attributes
+                        .extend(self.parse_attribute(&e, false)?);
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"attributeGroup" => {
+                    complex_type
+                        .attribute_groups
+                        .extend(self.parse_attribute_group_ref(&e, true)?);
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"attributeGroup" => {
+                    complex_type
+                        .attribute_groups
+                        .extend(self.parse_attribute_group_ref(&e, false)?);
+                }
+                Event::End(e) if e.local_name().as_ref() == end => break,
                 Event::Eof => break,
                 _ => {}
             }
             buf.clear();
         }
+        Ok(())
+    }
 
-        if !complex_type.name.is_empty() {
-            self.model
-                .complex_types
-                .insert(complex_type.name.clone(), complex_type);
+    /// Parse an anonymous <complexType> of a local element and register it under a synthesized name
+    ///
+    /// The name joins the name of the enclosing type (or group) with the
+    /// element name, e.g. `OrderLineItem` for the element `lineItem` within
+    /// `Order`. Names taken by global definitions or other anonymous types
+    /// get a numeric suffix. Returns the QName for the element to refer to
+    /// the type.
+    ///
+    /// # Arguments
+    ///
+    /// * `element_name` - Name of the element declaring the type
+    /// * `has_content` - If true, this is a Start event and the content is parsed
+    pub(super) fn parse_anonymous_complex_type(
+        &mut self,
+        element_name: &str,
+        has_content: bool,
+    ) -> Result<QName, Box<dyn Error>> {
+        let mut chars = element_name.chars();
+        let element_name: String = chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default();
+        let scope = self
+            .type_scope
+            .last()
+            .map(String::as_str)
+            .unwrap_or_default();
+        let name = self.reserve_type_name(&format!("{}{}", scope, element_name));
+
+        let mut complex_type = ComplexType {
+            name: name.clone(),
+            ..Default::default()
+        };
+        if has_content {
+            self.parse_complex_type_content(&mut complex_type)?;
         }
-        Ok(())
+        self.model.complex_types.insert(name.clone(), complex_type);
+
+        Ok(match &self.model.target_namespace {
+            Some(namespace) => QName::with_namespace(namespace.clone(), name),
+            None => QName::new(name),
+        })
+    }
+
+    /// Reserve a type name based on `name` that is not taken yet
+    fn reserve_type_name(&mut self, name: &str) -> String {
+        let name = std::iter::once(name.to_string())
+            .chain((2..).map(|n| format!("{}{}", name, n)))
+            .find(|candidate| !self.type_names.contains(candidate))
+            .unwrap_or_default();
+        self.type_names.insert(name.clone());
+        name
     }
 }
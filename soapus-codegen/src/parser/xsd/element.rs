@@ -1,6 +1,6 @@
 //! Parsing of XSD element definitions
 
-use crate::parser::xsd::{ComplexType, SchemaElement, Sequence};
+use crate::parser::xsd::{ComplexType, SchemaElement};
 use crate::parser::QName;
 use quick_xml::events::{BytesStart, Event};
 use std::error::Error;
@@ -36,7 +36,7 @@ impl<B: std::io::BufRead> SchemaParser<B> {
             if key == b"name" {
                 element_name = Some(val.to_string());
             } else if key == b"type" {
-                type_name = Some(QName(val.to_string()));
+                type_name = Some(QName::resolve(&val, &self.reader));
             } else if key == b"nillable" && val == "true" {
                 nillable = true;
             }
@@ -58,29 +58,7 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                                 ..Default::default()
                             };
 
-                            let mut inner_buf = Vec::new();
-                            loop {
-                                match self.reader.read_event_into(&mut inner_buf)? {
-                                    Event::Start(e) if e.local_name().as_ref() == b"sequence" => {
-                                        complex_type.sequence = Some(self.parse_sequence()?);
-                                    }
-                                    Event::Empty(e) if e.local_name().as_ref() == b"sequence" => {
-                                        complex_type.sequence = Some(Sequence::default());
-                                    }
-                                    Event::Start(e) if e.local_name().as_ref() == b"all" => {
-                                        complex_type.sequence = Some(self.parse_all()?);
-                                    }
-                                    Event::Empty(e) if e.local_name().as_ref() == b"all" => {
-                                        complex_type.sequence = Some(Sequence::default());
-                                    }
-                                    Event::End(e) if e.local_name().as_ref() == b"complexType" => {
-                                        break
-                                    }
-                                    Event::Eof => break,
-                                    _ => {}
-                                }
-                                inner_buf.clear();
-                            }
+                            self.parse_complex_type_content(&mut complex_type)?;
 
                             self.model.complex_types.insert(name.clone(), complex_type);
                             found_inline_complex_type = true;
@@ -124,3 +102,50 @@ impl<B: std::io::BufRead> SchemaParser<B> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parses_element_references() {
+        let wsdl = include_str!("../../../../testdata/wsdl/catalog.wsdl");
+        let model = crate::parser::parse_wsdl(wsdl).unwrap();
+        let schema = model.schema().unwrap();
+
+        // The local name of the reference becomes the element name, the type is left to resolution
+        let place_order = schema.complex_types["PlaceOrder"]
+            .sequence
+            .as_ref()
+            .unwrap();
+        let names: Vec<_> = place_order
+            .elements
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["Product", "Customer", "Address", "Currency", "Note"]
+        );
+        let customer = &place_order.elements[1];
+        assert!(customer
+            .reference
+            .as_ref()
+            .unwrap()
+            .refers_to(Some("http://example.com/common"), "Customer"));
+        assert!(customer.type_.local_name().is_empty());
+        assert_eq!(
+            place_order.elements[0].max_occurs.as_deref(),
+            Some("unbounded")
+        );
+        assert_eq!(place_order.elements[2].min_occurs, 0);
+
+        // References within a choice
+        let response = schema.complex_types["PlaceOrderResponse"]
+            .sequence
+            .as_ref()
+            .unwrap();
+        assert!(response.choices[0]
+            .elements
+            .iter()
+            .all(|e| e.reference.is_some()));
+    }
+}
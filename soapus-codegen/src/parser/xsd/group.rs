@@ -0,0 +1,153 @@
+//! Parsing of XSD model group definitions and references
+
+use crate::parser::xsd::{Choice, GroupRef, Sequence};
+use crate::parser::QName;
+use quick_xml::events::{BytesStart, Event};
+use std::error::Error;
+
+use super::parser::SchemaParser;
+
+impl<B: std::io::BufRead> SchemaParser<B> {
+    /// Parse a <group ref="..."> reference within a sequence, choice or complexType
+    ///
+    /// The position of the reference is left to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `e` - The group's start tag
+    /// * `has_content` - If true, this is a Start event and the content is skipped
+    pub(super) fn parse_group_ref(
+        &mut self,
+        e: &BytesStart,
+        has_content: bool,
+    ) -> Result<Option<GroupRef>, Box<dyn Error>> {
+        let mut reference = None;
+        let mut group = GroupRef {
+            min_occurs: 1,
+            ..Default::default()
+        };
+
+        for attr in e.attributes().with_checks(false) {
+            let attr = attr?;
+            let val = attr.unescape_value()?;
+            match attr.key.as_ref() {
+                b"ref" => reference = Some(QName::resolve(&val, &self.reader)),
+                b"minOccurs" => group.min_occurs = val.parse().unwrap_or(1),
+                b"maxOccurs" => group.max_occurs = Some(val.to_string()),
+                _ => {}
+            }
+        }
+
+        if has_content {
+            self.skip_element()?;
+        }
+
+        Ok(reference.map(|reference| GroupRef { reference, ..group }))
+    }
+
+    /// Parse a named top-level <group> definition
+    ///
+    /// The content of a group is a single sequence, choice or all compositor.
+    /// It is stored as a sequence, a choice becoming the only choice of it.
+    ///
+    /// Example:
+    /// ```xml
+    /// <group name="ContactInfo">
+    ///   <sequence>
+    ///     <element name="email" type="xs:string"/>
+    ///     <element name="phone" type="xs:string" minOccurs="0"/>
+    ///   </sequence>
+    /// </group>
+    /// ```
+    pub(super) fn parse_group(&mut self, e: &BytesStart) -> Result<(), Box<dyn Error>> {
+        let name = e
+            .try_get_attribute("name")?
+            .map(|a| a.unescape_value().map(|v| v.into_owned()))
+            .transpose()?
+            .unwrap_or_default();
+        let mut content = Sequence::default();
+
+        // Anonymous types within the group are named after it
+        self.type_scope.push(name.clone());
+        let mut buf = Vec::new();
+        loop {
+            match self.reader.read_event_into(&mut buf)? {
+                Event::Start(e) if e.local_name().as_ref() == b"sequence" => {
+                    content = self.parse_sequence()?;
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"all" => {
+                    content = self.parse_all()?;
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"choice" => {
+                    content.choices = vec![Choice {
+                        position: 0,
+                        ..self.parse_choice(&e)?
+                    }];
+                }
+                Event::Start(_) => self.skip_element()?,
+                Event::End(e) if e.local_name().as_ref() == b"group" => break,
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        self.type_scope.pop();
+
+        if !name.is_empty() {
+            self.model.groups.insert(name, content);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parses_group_references() {
+        let wsdl = include_str!("../../../../testdata/wsdl/reservations.wsdl");
+        let model = crate::parser::parse_wsdl(wsdl).unwrap();
+        let schema = model.schema().unwrap();
+
+        // Definitions keep their content, a choice group becomes a sequence with one choice
+        assert_eq!(schema.groups.len(), 8);
+        assert_eq!(schema.groups["ContactInfo"].elements.len(), 2);
+        assert_eq!(schema.groups["PaymentChoice"].choices[0].elements.len(), 2);
+        assert_eq!(schema.groups["Traveler"].groups.len(), 1);
+
+        // References in a sequence keep their position among elements and choices
+        let reservation = schema.complex_types["Reservation"]
+            .sequence
+            .as_ref()
+            .unwrap();
+        let positions: Vec<_> = reservation
+            .groups
+            .iter()
+            .map(|g| {
+                (
+                    g.reference.local_name(),
+                    g.position,
+                    g.max_occurs.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            positions,
+            vec![("PaymentChoice", 1, None), ("Stay", 2, Some("unbounded"))]
+        );
+        let guest = schema.complex_types["Guest"].sequence.as_ref().unwrap();
+        assert_eq!(guest.groups[1].min_occurs, 0);
+        assert!(guest.groups[1]
+            .reference
+            .refers_to(Some("http://example.com/reservations"), "ContactInfo"));
+
+        // A group as the whole content, within an extension and within a choice
+        let remark = &schema.complex_types["Remark"];
+        assert_eq!(remark.sequence.as_ref().unwrap().groups.len(), 1);
+        assert_eq!(remark.attributes.len(), 1);
+        let vip = &schema.complex_types["VipGuest"];
+        assert_eq!(vip.sequence.as_ref().unwrap().groups.len(), 1);
+        let lookup = schema.complex_types["Lookup"].choice.as_ref().unwrap();
+        assert_eq!(lookup.elements.len(), 1);
+        assert_eq!(lookup.groups.len(), 2);
+    }
+}
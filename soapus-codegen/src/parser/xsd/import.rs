@@ -0,0 +1,54 @@
+//! Parsing of XSD import and include declarations
+
+use quick_xml::events::BytesStart;
+use std::error::Error;
+
+use super::parser::SchemaParser;
+use super::SchemaImport;
+
+impl<B: std::io::BufRead> SchemaParser<B> {
+    /// Parse an <import> declaration
+    ///
+    /// Imports reference schemas with a different target namespace. The
+    /// `schemaLocation` is optional; imports without a location usually refer
+    /// to another schema embedded in the same WSDL.
+    ///
+    /// Example:
+    /// ```xml
+    /// <import namespace="http://example.com/common" schemaLocation="common.xsd"/>
+    /// ```
+    pub(super) fn parse_import(&mut self, e: &BytesStart) -> Result<(), Box<dyn Error>> {
+        let mut import = SchemaImport::default();
+        for attr in e.attributes().with_checks(false) {
+            let attr = attr?;
+            match attr.key.as_ref() {
+                b"namespace" => import.namespace = Some(attr.unescape_value()?.to_string()),
+                b"schemaLocation" => {
+                    import.schema_location = Some(attr.unescape_value()?.to_string())
+                }
+                _ => {}
+            }
+        }
+        self.model.imports.push(import);
+        Ok(())
+    }
+
+    /// Parse an <include> declaration
+    ///
+    /// Includes pull in schema documents sharing the including schema's
+    /// target namespace (or having none at all).
+    ///
+    /// Example:
+    /// ```xml
+    /// <include schemaLocation="order-types.xsd"/>
+    /// ```
+    pub(super) fn parse_include(&mut self, e: &BytesStart) -> Result<(), Box<dyn Error>> {
+        let location = e
+            .try_get_attribute("schemaLocation")?
+            .map(|a| a.unescape_value().map(|v| v.into_owned()))
+            .transpose()?
+            .ok_or("include missing 'schemaLocation' attribute")?;
+        self.model.includes.push(location);
+        Ok(())
+    }
+}
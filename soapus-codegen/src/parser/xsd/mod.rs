@@ -1,16 +1,28 @@
 pub mod parser;
 
 // Parser sub-modules for different XSD elements
+mod attribute;
+mod choice;
+mod complex_content;
 mod complex_type;
 mod element;
+mod group;
+mod import;
 mod schema_attributes;
 mod schema_content;
 mod sequence;
+mod simple_content;
 mod simple_type;
 
 use crate::parser::QName;
 use std::collections::HashMap;
 
+/// Namespace URI of the XML Schema built-in types
+pub const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+
+/// Namespace URI of the SOAP 1.1 encoding, defining `soapenc:Array`
+pub const SOAP_ENCODING_NAMESPACE: &str = "http://schemas.xmlsoap.org/soap/encoding/";
+
 /// XML Schema representation
 #[derive(Debug, Default)]
 pub struct XmlSchema {
@@ -22,6 +34,50 @@ pub struct XmlSchema {
     pub elements: HashMap<String, SchemaElement>,
     pub complex_types: HashMap<String, ComplexType>,
     pub simple_types: HashMap<String, SimpleType>,
+    /// Named `<attributeGroup>` definitions
+    pub attribute_groups: HashMap<String, AttributeGroup>,
+    /// Named `<group>` definitions, with their content as a sequence
+    pub groups: HashMap<String, Sequence>,
+    /// `<import>` declarations referencing schemas of other namespaces
+    pub imports: Vec<SchemaImport>,
+    /// `schemaLocation` values of `<include>` declarations
+    pub includes: Vec<String>,
+}
+
+/// An `<import>` of another schema document
+#[derive(Debug, Default, Clone)]
+pub struct SchemaImport {
+    pub namespace: Option<String>,
+    pub schema_location: Option<String>,
+}
+
+impl XmlSchema {
+    /// Merge the definitions of another schema into this one
+    ///
+    /// Definitions already present in `self` take precedence over those of `other`.
+    pub(crate) fn merge(&mut self, other: XmlSchema) {
+        for (prefix, uri) in other.namespaces {
+            self.namespaces.entry(prefix).or_insert(uri);
+        }
+        for (name, element) in other.elements {
+            self.elements.entry(name).or_insert(element);
+        }
+        for (name, complex_type) in other.complex_types {
+            self.complex_types.entry(name).or_insert(complex_type);
+        }
+        for (name, simple_type) in other.simple_types {
+            self.simple_types.entry(name).or_insert(simple_type);
+        }
+        for (name, attribute_group) in other.attribute_groups {
+            self.attribute_groups.entry(name).or_insert(attribute_group);
+        }
+        for (name, group) in other.groups {
+            self.groups.entry(name).or_insert(group);
+        }
+        if self.target_namespace.is_none() {
+            self.target_namespace = other.target_namespace;
+        }
+    }
 }
 
 /// A top-level or nested element definition
@@ -43,12 +99,84 @@ pub struct ComplexType {
     pub all: Option<All>,
     // For extensions and restrictions
     pub base_type: Option<QName>,
+    /// How the type is derived from `base_type` (complexContent)
+    pub derivation: Option<Derivation>,
+    /// Abstract types only occur as one of their derived types (`abstract="true"`)
+    pub is_abstract: bool,
+    /// Attributes declared directly in the type
+    pub attributes: Vec<Attribute>,
+    /// References to `<attributeGroup>` definitions
+    pub attribute_groups: Vec<QName>,
+    /// Type of the text content of a `<simpleContent>` type
+    ///
+    /// As parsed this is the base type, which may itself be a complex type
+    /// with simple content.
+    pub simple_content: Option<QName>,
+}
+
+/// An attribute of a complex type
+#[derive(Debug, Default, Clone)]
+pub struct Attribute {
+    pub name: String,
+    /// Type of the attribute, `None` for inline simple types and references
+    pub type_: Option<QName>,
+    pub use_: AttributeUse,
+    /// Value assumed when the attribute is absent
+    pub default: Option<String>,
+    /// The only value the attribute may have
+    pub fixed: Option<String>,
+    /// Item type declared for a SOAP encoded array (`wsdl:arrayType="xsd:string[]"`)
+    pub array_type: Option<QName>,
+}
+
+/// Whether an attribute must, may or must not appear (`use`)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeUse {
+    #[default]
+    Optional,
+    Required,
+    /// Removes an inherited attribute in a restriction
+    Prohibited,
+}
+
+/// A named, reusable group of attributes
+#[derive(Debug, Default, Clone)]
+pub struct AttributeGroup {
+    pub name: String,
+    pub attributes: Vec<Attribute>,
+    /// References to other `<attributeGroup>` definitions
+    pub attribute_groups: Vec<QName>,
+}
+
+/// Derivation method of a complex type from its base type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Derivation {
+    /// The base content followed by the derived type's own content
+    Extension,
+    /// The derived type restates (a subset of) the base content
+    Restriction,
 }
 
 /// A sequence of elements (ordered)
 #[derive(Debug, Default, Clone)]
 pub struct Sequence {
     pub elements: Vec<SequenceElement>,
+    /// Choices nested in the sequence, see [`Choice::position`]
+    pub choices: Vec<Choice>,
+    /// References to named groups, expanded during code generation
+    pub groups: Vec<GroupRef>,
+}
+
+/// A reference to a named model group (`<group ref="..."/>`)
+#[derive(Debug, Default, Clone)]
+pub struct GroupRef {
+    pub reference: QName,
+    pub min_occurs: u32,
+    pub max_occurs: Option<String>, // "unbounded" or a number
+    /// Number of sequence elements preceding the reference
+    pub position: usize,
+    /// Number of sequence choices preceding the reference
+    pub choice_index: usize,
 }
 
 /// An element within a sequence
@@ -59,6 +187,8 @@ pub struct SequenceElement {
     pub min_occurs: u32,
     pub max_occurs: Option<String>, // "unbounded" or a number
     pub nillable: bool,
+    /// Global element referenced with `ref`, whose declaration provides the type
+    pub reference: Option<QName>,
 }
 
 /// A simple type definition (restriction, list, union)
@@ -103,6 +233,12 @@ pub enum WhiteSpace {
 #[derive(Debug, Default, Clone)]
 pub struct Choice {
     pub elements: Vec<SequenceElement>,
+    pub min_occurs: u32,
+    pub max_occurs: Option<String>, // "unbounded" or a number
+    /// Number of sequence elements preceding the choice when nested in a sequence
+    pub position: usize,
+    /// References to named groups providing further alternatives
+    pub groups: Vec<GroupRef>,
 }
 
 /// All elements must appear (unordered)
@@ -8,13 +8,19 @@
 //! - `schema_content` - Top-level schema elements
 //! - `element` - Element definitions
 //! - `complex_type` - ComplexType definitions
+//! - `complex_content` - ComplexContent extension and restriction
+//! - `simple_content` - SimpleContent extension and restriction
 //! - `sequence` - Sequence and all compositors
+//! - `choice` - Choice compositors
+//! - `group` - Model group definitions and references
+//! - `attribute` - Attribute and attributeGroup declarations
 //! - `simple_type` - SimpleType definitions and utilities
+//! - `import` - Import and include declarations
 
 use crate::parser::xsd::XmlSchema;
 use quick_xml::events::Event;
-use quick_xml::Reader;
-use std::collections::HashMap;
+use quick_xml::NsReader;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
 /// Parse an XML Schema string into a structured model
@@ -31,8 +37,41 @@ use std::error::Error;
 ///
 /// Returns an error if the XML is malformed or the schema is invalid
 pub fn parse_schema(xml: &str) -> Result<XmlSchema, Box<dyn std::error::Error>> {
-    let reader = Reader::from_str(xml);
-    SchemaParser::new(reader).parse()
+    let reader = NsReader::from_str(xml);
+    SchemaParser::new(reader)
+        .with_type_names(global_type_names(xml)?)
+        .parse()
+}
+
+/// Collect the names of the global complex types of a schema document
+///
+/// Top-level elements with an inline complexType count as well, their type
+/// is registered under the element name.
+fn global_type_names(xml: &str) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
+    let mut reader = NsReader::from_str(xml);
+    let mut names = HashSet::new();
+    let mut depth = 0;
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                depth += 1;
+                if depth == 2 && matches!(e.local_name().as_ref(), b"complexType" | b"element") {
+                    if let Some(name) = e.try_get_attribute("name")? {
+                        names.insert(name.unescape_value()?.into_owned());
+                    }
+                }
+            }
+            Event::Empty(e) if depth == 1 && e.local_name().as_ref() == b"complexType" => {
+                if let Some(name) = e.try_get_attribute("name")? {
+                    names.insert(name.unescape_value()?.into_owned());
+                }
+            }
+            Event::End(_) => depth -= 1,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(names)
 }
 
 /// XSD schema parser state
@@ -40,24 +79,39 @@ pub fn parse_schema(xml: &str) -> Result<XmlSchema, Box<dyn std::error::Error>>
 /// This struct maintains the parsing state while traversing the schema document.
 /// It is used by the parsing functions in the submodules.
 pub struct SchemaParser<B: std::io::BufRead> {
-    pub(super) reader: Reader<B>,
+    pub(super) reader: NsReader<B>,
     pub(super) namespaces: HashMap<String, String>,
     #[allow(dead_code)]
     pub(super) target_namespace: Option<String>,
     pub(super) model: XmlSchema,
+    /// Names of the enclosing type and group definitions, used to name anonymous types
+    pub(super) type_scope: Vec<String>,
+    /// Names of global types and of the anonymous types named so far
+    pub(super) type_names: HashSet<String>,
 }
 
 impl<B: std::io::BufRead> SchemaParser<B> {
     /// Create a new schema parser
-    pub fn new(reader: Reader<B>) -> Self {
+    pub fn new(reader: NsReader<B>) -> Self {
         Self {
             reader,
             namespaces: HashMap::new(),
             target_namespace: None,
             model: XmlSchema::default(),
+            type_scope: Vec::new(),
+            type_names: HashSet::new(),
         }
     }
 
+    /// Reserve the names of the global types of the document
+    ///
+    /// Anonymous types are named so that they do not collide with these,
+    /// including the ones defined further down in the document.
+    pub fn with_type_names(mut self, names: impl IntoIterator<Item = String>) -> Self {
+        self.type_names.extend(names);
+        self
+    }
+
     /// Resolve a namespace prefix to its URI
     #[allow(dead_code)]
     pub fn resolve_prefix(&self, prefix: &str) -> Option<&String> {
@@ -72,12 +126,15 @@ impl<B: std::io::BufRead> SchemaParser<B> {
         let mut buf = Vec::new();
         loop {
             match self.reader.read_event_into(&mut buf)? {
-                Event::Start(ev) => {
-                    if ev.local_name().as_ref() == b"schema" {
-                        self.parse_schema_attributes(&ev)?;
-                        self.parse_schema_content()?;
-                        break;
-                    }
+                Event::Start(ev) if ev.local_name().as_ref() == b"schema" => {
+                    self.parse_schema_attributes(&ev)?;
+                    self.parse_schema_content()?;
+                    break;
+                }
+                Event::Empty(ev) if ev.local_name().as_ref() == b"schema" => {
+                    // Empty schema like <xs:schema/> - attributes only
+                    self.parse_schema_attributes(&ev)?;
+                    break;
                 }
                 Event::End(ev) if ev.local_name().as_ref() == b"schema" => {
                     break;
@@ -94,6 +151,7 @@ impl<B: std::io::BufRead> SchemaParser<B> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::xsd::Derivation;
 
     #[test]
     fn parses_calculator_schema() {
@@ -179,4 +237,100 @@ mod tests {
             Some("unbounded".to_string())
         );
     }
+
+    #[test]
+    fn names_anonymous_types() {
+        let wsdl = include_str!("../../../../testdata/wsdl/shipments.wsdl");
+        let model = crate::parser::parse_wsdl(wsdl).unwrap();
+        let schema = model.schema().unwrap();
+        let namespace = Some("http://example.com/shipments");
+
+        // Named after the enclosing type, at any depth
+        let order = schema.complex_types["Order"].sequence.as_ref().unwrap();
+        assert!(order.elements[1]
+            .type_
+            .refers_to(namespace, "OrderLineItem"));
+        let line_item = schema.complex_types["OrderLineItem"]
+            .sequence
+            .as_ref()
+            .unwrap();
+        assert!(line_item.elements[2]
+            .type_
+            .refers_to(namespace, "OrderLineItemOptions"));
+        assert!(schema.complex_types.contains_key("OrderLineItemOptions"));
+        assert!(schema
+            .complex_types
+            .contains_key("CreateShipmentResponseTrackingCarrier"));
+
+        // Within choices and groups, and without content
+        for name in [
+            "CreateShipmentResponseAccepted",
+            "CreateShipmentResponseRejected",
+            "PackagingPackage",
+        ] {
+            assert!(schema.complex_types.contains_key(name), "{}", name);
+        }
+
+        // A global type defined further down keeps its name
+        let shipment = schema.complex_types["Shipment"].sequence.as_ref().unwrap();
+        assert!(shipment.elements[0]
+            .type_
+            .refers_to(namespace, "ShipmentAddress2"));
+        assert!(shipment.elements[1]
+            .type_
+            .refers_to(namespace, "ShipmentAddress"));
+        let named = schema.complex_types["ShipmentAddress"]
+            .sequence
+            .as_ref()
+            .unwrap();
+        assert_eq!(named.elements[0].name, "label");
+    }
+
+    #[test]
+    fn parses_encoded_array_types() {
+        let wsdl = include_str!("../../../../testdata/wsdl/accounts.wsdl");
+        let model = crate::parser::parse_wsdl(wsdl).unwrap();
+        let schema = model.schema().unwrap();
+
+        let strings = &schema.complex_types["ArrayOfString"];
+        assert_eq!(strings.derivation, Some(Derivation::Restriction));
+        assert!(strings
+            .base_type
+            .as_ref()
+            .unwrap()
+            .refers_to(Some(crate::parser::SOAP_ENCODING_NAMESPACE), "Array"));
+        // The dimensions are dropped from the item type
+        assert!(strings.attributes[0]
+            .array_type
+            .as_ref()
+            .unwrap()
+            .refers_to(Some(crate::parser::XSD_NAMESPACE), "string"));
+    }
+
+    #[test]
+    fn flattens_nested_sequences() {
+        let schema = r#"
+        <schema xmlns="http://www.w3.org/2001/XMLSchema">
+            <complexType name="Outer">
+                <sequence>
+                    <element name="a" type="string"/>
+                    <sequence>
+                        <element name="b" type="string"/>
+                        <choice>
+                            <element name="c" type="string"/>
+                            <element name="d" type="string"/>
+                        </choice>
+                    </sequence>
+                    <element name="e" type="string"/>
+                </sequence>
+            </complexType>
+        </schema>
+        "#;
+
+        let model = parse_schema(schema).unwrap();
+        let sequence = model.complex_types["Outer"].sequence.as_ref().unwrap();
+        let names: Vec<_> = sequence.elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "e"]);
+        assert_eq!(sequence.choices[0].position, 2);
+    }
 }
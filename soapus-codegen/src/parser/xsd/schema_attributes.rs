@@ -13,7 +13,7 @@ impl<B: std::io::BufRead> SchemaParser<B> {
     /// - attributeFormDefault
     /// - elementFormDefault
     /// - version
-    /// - xmlns:* namespace declarations
+    /// - xmlns and xmlns:* namespace declarations
     pub(super) fn parse_schema_attributes(&mut self, e: &BytesStart) -> Result<(), Box<dyn Error>> {
         for attr in e.attributes().with_checks(false) {
             let attr = attr?;
@@ -28,6 +28,10 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                 self.model.element_form_default = Some(val.clone());
             } else if key == b"version" {
                 self.model.version = Some(val.clone());
+            } else if key == b"xmlns" {
+                // Default namespace, stored under the empty prefix
+                self.namespaces.insert(String::new(), val.clone());
+                self.model.namespaces.insert(String::new(), val);
             } else if key.starts_with(b"xmlns:") {
                 let prefix = String::from_utf8_lossy(&key[6..]).to_string();
                 self.namespaces.insert(prefix.clone(), val.clone());
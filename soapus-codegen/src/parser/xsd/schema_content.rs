@@ -13,6 +13,9 @@ impl<B: std::io::BufRead> SchemaParser<B> {
     /// - <element> - Top-level element definitions
     /// - <complexType> - Complex type definitions
     /// - <simpleType> - Simple type definitions
+    /// - <group> - Reusable groups of elements
+    /// - <attributeGroup> - Reusable groups of attributes
+    /// - <import>/<include> - References to external schema documents
     pub(super) fn parse_schema_content(&mut self) -> Result<(), Box<dyn Error>> {
         let mut buf = Vec::new();
 
@@ -22,7 +25,17 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                     b"element" => self.parse_element(&e, true)?,
                     b"complexType" => self.parse_complex_type(&e)?,
                     b"simpleType" => self.parse_simple_type(&e)?,
-                    // Additional schema elements (attribute, group, etc.) can be added here if needed
+                    b"group" => self.parse_group(&e)?,
+                    b"attributeGroup" => self.parse_attribute_group(&e)?,
+                    b"import" => {
+                        self.parse_import(&e)?;
+                        self.skip_element()?;
+                    }
+                    b"include" => {
+                        self.parse_include(&e)?;
+                        self.skip_element()?;
+                    }
+                    // Additional schema elements (attribute, notation, etc.) can be added here if needed
                     _ => {} // Ignore unknown schema elements
                 },
                 Event::Empty(e) => match e.local_name().as_ref() {
@@ -45,6 +58,8 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                     b"simpleType" => {
                         // Empty simple type - just skip
                     }
+                    b"import" => self.parse_import(&e)?,
+                    b"include" => self.parse_include(&e)?,
                     _ => {} // Ignoriere unbekannte Schema-Elemente
                 },
                 Event::End(e) if e.local_name().as_ref() == b"schema" => break,
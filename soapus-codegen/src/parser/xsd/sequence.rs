@@ -12,6 +12,8 @@ impl<B: std::io::BufRead> SchemaParser<B> {
     ///
     /// Sequences define an ordered list of child elements.
     /// Each element can have minOccurs/maxOccurs attributes to control cardinality.
+    /// Nested choices and group references are kept with their position,
+    /// nested sequences are flattened.
     ///
     /// Example:
     /// ```xml
@@ -19,6 +21,11 @@ impl<B: std::io::BufRead> SchemaParser<B> {
     ///   <element name="firstName" type="xs:string"/>
     ///   <element name="lastName" type="xs:string"/>
     ///   <element name="age" type="xs:int" minOccurs="0"/>
+    ///   <choice>
+    ///     <element name="email" type="xs:string"/>
+    ///     <element name="phone" type="xs:string"/>
+    ///   </choice>
+    ///   <group ref="tns:ContactInfo" minOccurs="0"/>
     /// </sequence>
     /// ```
     pub(super) fn parse_sequence(&mut self) -> Result<Sequence, Box<dyn Error>> {
@@ -35,6 +42,36 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                     let elem = self.parse_sequence_element(&e, false)?;
                     sequence.elements.push(elem);
                 }
+                Event::Start(e) if e.local_name().as_ref() == b"choice" => {
+                    let mut choice = self.parse_choice(&e)?;
+                    choice.position = sequence.elements.len();
+                    sequence.choices.push(choice);
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"group" => {
+                    self.push_group_ref(&mut sequence, &e, true)?;
+                }
+                Event::Empty(e) if e.local_name().as_ref() == b"group" => {
+                    self.push_group_ref(&mut sequence, &e, false)?;
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"sequence" => {
+                    let nested = self.parse_sequence()?;
+                    let offset = sequence.elements.len();
+                    let choice_offset = sequence.choices.len();
+                    sequence
+                        .groups
+                        .extend(nested.groups.into_iter().map(|mut g| {
+                            g.position += offset;
+                            g.choice_index += choice_offset;
+                            g
+                        }));
+                    sequence
+                        .choices
+                        .extend(nested.choices.into_iter().map(|mut c| {
+                            c.position += offset;
+                            c
+                        }));
+                    sequence.elements.extend(nested.elements);
+                }
                 Event::End(e) if e.local_name().as_ref() == b"sequence" => break,
                 Event::Eof => break,
                 _ => {}
@@ -44,6 +81,21 @@ impl<B: std::io::BufRead> SchemaParser<B> {
         Ok(sequence)
     }
 
+    /// Parse a <group ref="..."> and add it at the current end of `sequence`
+    pub(super) fn push_group_ref(
+        &mut self,
+        sequence: &mut Sequence,
+        e: &BytesStart,
+        has_content: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        if let Some(mut group) = self.parse_group_ref(e, has_content)? {
+            group.position = sequence.elements.len();
+            group.choice_index = sequence.choices.len();
+            sequence.groups.push(group);
+        }
+        Ok(())
+    }
+
     /// Parse an <all> compositor
     ///
     /// The <all> compositor is similar to sequence but elements can appear in any order.
@@ -86,6 +138,7 @@ impl<B: std::io::BufRead> SchemaParser<B> {
     /// Extracts:
     /// - name - Element name
     /// - type - Element type (QName)
+    /// - ref - Referenced global element, its local name becomes the element name
     /// - minOccurs - Minimum occurrences (default: 1)
     /// - maxOccurs - Maximum occurrences (default: 1, or "unbounded")
     /// - nillable - Whether the element can be nil/null
@@ -93,7 +146,8 @@ impl<B: std::io::BufRead> SchemaParser<B> {
     /// # Arguments
     ///
     /// * `e` - The element's start tag
-    /// * `should_skip` - If true, skip to the end of the element
+    /// * `should_skip` - If true, this is a Start event and the content is parsed;
+    ///   an inline complexType becomes an anonymous type named after the element
     pub(super) fn parse_sequence_element(
         &mut self,
         e: &BytesStart,
@@ -104,6 +158,7 @@ impl<B: std::io::BufRead> SchemaParser<B> {
         let mut min_occurs = 1u32;
         let mut max_occurs = None;
         let mut nillable = false;
+        let mut reference = None;
 
         for attr in e.attributes().with_checks(false) {
             let attr = attr?;
@@ -112,7 +167,8 @@ impl<B: std::io::BufRead> SchemaParser<B> {
 
             match key {
                 b"name" => name = Some(val.to_string()),
-                b"type" => type_name = Some(QName(val.to_string())),
+                b"type" => type_name = Some(QName::resolve(&val, &self.reader)),
+                b"ref" => reference = Some(QName::resolve(&val, &self.reader)),
                 b"minOccurs" => min_occurs = val.parse().unwrap_or(1),
                 b"maxOccurs" => {
                     max_occurs = if val == "unbounded" {
@@ -126,9 +182,32 @@ impl<B: std::io::BufRead> SchemaParser<B> {
             }
         }
 
-        // If this is a Start event, skip to the end of the element
+        // If this is a Start event, look for an inline type up to the end of the element
         if should_skip {
-            self.skip_element()?;
+            let mut buf = Vec::new();
+            loop {
+                match self.reader.read_event_into(&mut buf)? {
+                    Event::Start(e) if e.local_name().as_ref() == b"complexType" => {
+                        let element_name = name.clone().unwrap_or_default();
+                        type_name = Some(self.parse_anonymous_complex_type(&element_name, true)?);
+                    }
+                    Event::Empty(e) if e.local_name().as_ref() == b"complexType" => {
+                        let element_name = name.clone().unwrap_or_default();
+                        type_name = Some(self.parse_anonymous_complex_type(&element_name, false)?);
+                    }
+                    Event::Start(_) => self.skip_element()?,
+                    Event::End(e) if e.local_name().as_ref() == b"element" => break,
+                    Event::Eof => break,
+                    _ => {}
+                }
+                buf.clear();
+            }
+        }
+
+        if name.is_none() {
+            name = reference
+                .as_ref()
+                .map(|r: &QName| r.local_name().to_string());
         }
 
         Ok(SequenceElement {
@@ -137,6 +216,7 @@ impl<B: std::io::BufRead> SchemaParser<B> {
             min_occurs,
             max_occurs,
             nillable,
+            reference,
         })
     }
 }
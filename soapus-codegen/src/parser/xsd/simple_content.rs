@@ -0,0 +1,91 @@
+//! Parsing of XSD simpleContent derivations (text content with attributes)
+
+use crate::parser::xsd::ComplexType;
+use quick_xml::events::Event;
+use std::error::Error;
+
+use super::parser::SchemaParser;
+
+impl<B: std::io::BufRead> SchemaParser<B> {
+    /// Parse a <simpleContent> element
+    ///
+    /// Describes elements with text content and attributes but no child
+    /// elements. The base is either a simple type or another complex type
+    /// with simple content. Facets of a restriction are ignored.
+    ///
+    /// Example:
+    /// ```xml
+    /// <complexType name="Amount">
+    ///   <simpleContent>
+    ///     <extension base="xs:decimal">
+    ///       <attribute name="currency" type="xs:string" use="required"/>
+    ///     </extension>
+    ///   </simpleContent>
+    /// </complexType>
+    /// ```
+    pub(super) fn parse_simple_content(
+        &mut self,
+        complex_type: &mut ComplexType,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut buf = Vec::new();
+        loop {
+            match self.reader.read_event_into(&mut buf)? {
+                Event::Start(e)
+                    if matches!(e.local_name().as_ref(), b"extension" | b"restriction") =>
+                {
+                    self.parse_derivation(&e, complex_type)?;
+                    let end = e.local_name().as_ref().to_vec();
+                    self.parse_content_model(complex_type, &end)?;
+                }
+                Event::Empty(e)
+                    if matches!(e.local_name().as_ref(), b"extension" | b"restriction") =>
+                {
+                    self.parse_derivation(&e, complex_type)?;
+                }
+                Event::End(e) if e.local_name().as_ref() == b"simpleContent" => break,
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        complex_type.simple_content = complex_type.base_type.clone();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::xsd::Derivation;
+
+    #[test]
+    fn parses_simple_content() {
+        let wsdl = include_str!("../../../../testdata/wsdl/invoices.wsdl");
+        let model = crate::parser::parse_wsdl(wsdl).unwrap();
+        let schema = model.schema().unwrap();
+
+        let amount = &schema.complex_types["Amount"];
+        assert!(amount
+            .simple_content
+            .as_ref()
+            .unwrap()
+            .refers_to(Some(crate::parser::XSD_NAMESPACE), "decimal"));
+        assert_eq!(amount.derivation, Some(Derivation::Extension));
+        assert_eq!(amount.attributes.len(), 1);
+        assert!(amount.sequence.is_none());
+
+        // Based on another complex type with simple content
+        let precise = &schema.complex_types["PreciseAmount"];
+        assert!(precise
+            .simple_content
+            .as_ref()
+            .unwrap()
+            .refers_to(Some("http://example.com/invoices"), "Amount"));
+
+        // Attributes following an inline simple type of a restriction
+        let label = &schema.complex_types["Label"];
+        assert_eq!(label.derivation, Some(Derivation::Restriction));
+        assert_eq!(label.attributes.len(), 1);
+        assert_eq!(label.attributes[0].name, "value");
+    }
+}
@@ -5,7 +5,7 @@ use std::error::Error;
 
 use super::parser::SchemaParser;
 use super::{Restriction, SimpleType};
-use crate::parser::QName;
+use crate::parser::{QName, XSD_NAMESPACE};
 
 impl<B: std::io::BufRead> SchemaParser<B> {
     /// Parse a <simpleType> definition
@@ -45,8 +45,8 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                     // Empty restriction - extract base type
                     let base = e
                         .try_get_attribute("base")?
-                        .map(|a| QName(a.unescape_value().unwrap().into_owned()))
-                        .unwrap_or_else(|| QName("xs:string".to_string()));
+                        .map(|a| QName::resolve(&a.unescape_value().unwrap(), &self.reader))
+                        .unwrap_or_else(|| QName::with_namespace(XSD_NAMESPACE, "xs:string"));
                     simple_type = Some(SimpleType::Restriction {
                         base,
                         restrictions: vec![],
@@ -55,8 +55,8 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                 Event::Start(e) if e.local_name().as_ref() == b"list" => {
                     let item_type = e
                         .try_get_attribute("itemType")?
-                        .map(|a| QName(a.unescape_value().unwrap().into_owned()))
-                        .unwrap_or_else(|| QName("xs:string".to_string()));
+                        .map(|a| QName::resolve(&a.unescape_value().unwrap(), &self.reader))
+                        .unwrap_or_else(|| QName::with_namespace(XSD_NAMESPACE, "xs:string"));
                     simple_type = Some(SimpleType::List { item_type });
                     self.skip_element()?;
                 }
@@ -67,7 +67,7 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                         .unwrap_or_default();
                     let member_types = member_types_str
                         .split_whitespace()
-                        .map(|s| QName(s.to_string()))
+                        .map(|s| QName::resolve(s, &self.reader))
                         .collect();
                     simple_type = Some(SimpleType::Union { member_types });
                     self.skip_element()?;
@@ -94,8 +94,8 @@ impl<B: std::io::BufRead> SchemaParser<B> {
         // Extract base attribute
         let base = e
             .try_get_attribute("base")?
-            .map(|a| QName(a.unescape_value().unwrap().into_owned()))
-            .unwrap_or_else(|| QName("xs:string".to_string()));
+            .map(|a| QName::resolve(&a.unescape_value().unwrap(), &self.reader))
+            .unwrap_or_else(|| QName::with_namespace(XSD_NAMESPACE, "xs:string"));
 
         let mut restrictions = Vec::new();
         let mut buf = Vec::new();
@@ -107,14 +107,16 @@ impl<B: std::io::BufRead> SchemaParser<B> {
                     match name.as_ref() {
                         b"enumeration" => {
                             if let Some(value) = e.try_get_attribute("value")? {
-                                restrictions
-                                    .push(Restriction::Enumeration(value.unescape_value()?.into_owned()));
+                                restrictions.push(Restriction::Enumeration(
+                                    value.unescape_value()?.into_owned(),
+                                ));
                             }
                         }
                         b"pattern" => {
                             if let Some(value) = e.try_get_attribute("value")? {
-                                restrictions
-                                    .push(Restriction::Pattern(value.unescape_value()?.into_owned()));
+                                restrictions.push(Restriction::Pattern(
+                                    value.unescape_value()?.into_owned(),
+                                ));
                             }
                         }
                         b"minLength" => {
@@ -142,13 +142,88 @@ fn test_generate_from_numberconversion_wsdl() {
     );
 }
 
+#[test]
+fn test_generate_from_multischema_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/multischema.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Multi-schema code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Types from both schemas are generated
+    assert!(content.contains("pub struct GetOrderResponse"));
+    assert!(content.contains("pub struct Order "));
+    assert!(content.contains("pub enum OrderStatus"));
+
+    // Cross-schema references are resolved by namespace URI, not by prefix
+    assert!(content.contains("pub order: Option<Order>"));
+    assert!(content.contains("pub status: OrderStatus"));
+
+    // Restricted simple types without enumerations map to their base type
+    assert!(content.contains("pub reference: Option<String>"));
+
+    // Same-named types in different namespaces don't collide
+    assert!(content.contains("pub struct Address "));
+    assert!(content.contains("pub struct DataAddress "));
+    assert!(content.contains("#[serde(rename = \"Address\")]\npub struct DataAddress"));
+    assert!(content.contains("pub ship_to: DataAddress"));
+    assert!(content.contains("pub return_address: Option<Address>"));
+
+    // A prefix redeclared on an inner element is honored
+    assert!(content.contains("pub bill_to: Option<DataAddress>"));
+}
+
+#[test]
+fn test_generated_code_is_reproducible() {
+    // Schemas and their types are kept in hash maps, the output must not depend on their order
+    let generate = || {
+        let dir = tempdir().unwrap();
+        let gen = SoapClientGenerator::builder()
+            .wsdl_path("../testdata/wsdl/multischema.wsdl")
+            .out_dir(dir.path())
+            .generate()
+            .unwrap();
+        fs::read_to_string(gen.output_file).unwrap()
+    };
+
+    let first = generate();
+    for _ in 0..4 {
+        assert_eq!(generate(), first);
+    }
+}
+
 #[test]
 fn test_all_wsdls_generate_valid_rust() {
-    // Test that all WSDL files generate code that at least compiles syntactically
+    // Test that all WSDL files generate syntactically valid code, `generated-tests` compiles some of them
     let wsdl_files = vec![
+        ("../testdata/wsdl/accounts.wsdl", "AccountService"),
+        ("../testdata/wsdl/bookings.wsdl", "BookingService"),
         ("../testdata/wsdl/calculator.wsdl", "Calculator"),
+        ("../testdata/wsdl/catalog.wsdl", "CatalogService"),
+        ("../testdata/wsdl/collisions.wsdl", "DirectoryService"),
         ("../testdata/wsdl/countryinfo.wsdl", "CountryInfo"),
         ("../testdata/wsdl/numberconversion.wsdl", "NumberConversion"),
+        ("../testdata/wsdl/multischema.wsdl", "OrderService"),
+        ("../testdata/wsdl/orders.wsdl", "OrderService"),
+        ("../testdata/wsdl/payments.wsdl", "PaymentService"),
+        ("../testdata/wsdl/employees.wsdl", "EmployeeService"),
+        ("../testdata/wsdl/invoices.wsdl", "InvoiceService"),
+        ("../testdata/wsdl/ledger.wsdl", "LedgerService"),
+        ("../testdata/wsdl/tickets.wsdl", "TicketService"),
+        ("../testdata/wsdl/quotes.wsdl", "QuoteService"),
+        ("../testdata/wsdl/reservations.wsdl", "ReservationService"),
+        ("../testdata/wsdl/sessions.wsdl", "SessionService"),
+        ("../testdata/wsdl/shipments.wsdl", "ShipmentService"),
+        ("../testdata/wsdl/wrapped.wsdl", "SearchService"),
     ];
 
     for (wsdl_path, expected_name) in wsdl_files {
@@ -157,6 +232,7 @@ fn test_all_wsdls_generate_valid_rust() {
         let result = SoapClientGenerator::builder()
             .wsdl_path(wsdl_path)
             .out_dir(dir.path())
+            .unwrapped_methods(true)
             .generate();
 
         assert!(
@@ -169,6 +245,10 @@ fn test_all_wsdls_generate_valid_rust() {
         let gen = result.unwrap();
         let content = fs::read_to_string(&gen.output_file).unwrap();
 
+        if let Err(e) = syn::parse_file(&content) {
+            panic!("{} generates invalid Rust: {}", wsdl_path, e);
+        }
+
         // Basic sanity checks
         assert!(
             content.contains("pub struct") || content.contains("pub enum"),
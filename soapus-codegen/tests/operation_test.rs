@@ -0,0 +1,420 @@
+use soapus_codegen::SoapClientGenerator;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_generate_from_orders_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/orders.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Fault code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Operations with faults return their own error enum
+    assert!(content.contains(
+        "pub async fn get_order(&self, request: GetOrder) -> Result<GetOrderResponse, GetOrderError> {"
+    ));
+    assert!(content.contains(".await.map_err(GetOrderError::from)"));
+    assert!(content.contains(
+        "pub async fn list_orders(&self, request: ListOrders) -> SoapResult<ListOrdersResponse> {"
+    ));
+
+    // One variant per fault, typed by the detail element
+    assert!(content.contains("pub enum GetOrderError {"));
+    assert!(content.contains("    OrderNotFound(OrderNotFoundFault),"));
+    assert!(content.contains("    AccessDenied(AccessDenied),"));
+    assert!(content.contains("    AlreadyShipped(String),"));
+    assert!(content.contains("    Soap(soapus_runtime::SoapError),"));
+    assert!(!content.contains("pub enum ListOrdersError"));
+
+    // Faults are recognized by the element of their detail
+    assert!(content.contains(
+        "if let Some(detail) = error.fault_detail(\"AccessDeniedFault\") {\n            return Self::AccessDenied(detail);"
+    ));
+    assert!(content.contains("impl std::error::Error for CancelOrderError {"));
+}
+
+#[test]
+fn test_generate_from_quotes_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/quotes.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "RPC/literal code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Input parts become arguments, wrapped in the operation element
+    assert!(content.contains(
+        "pub async fn get_history(&self, symbol: String, period: Period, limit: i32) -> SoapResult<GetHistoryResponse> {"
+    ));
+    assert!(content.contains("let request = GetHistoryRequest { symbol, period, limit };"));
+    assert!(content.contains("#[serde(rename = \"getHistory\")]\npub struct GetHistoryRequest {"));
+
+    // The operation element is in the soap:body namespace, the parts are unqualified
+    assert!(content.contains(
+        "self.client.call_with_soap_action(\"getHistory\", Some(\"\"), Some(\"http://example.com/quotes/rpc\"), false, &request).await"
+    ));
+
+    // A single output part is unwrapped from the response element
+    assert!(content.contains(
+        "pub async fn get_quote(&self, symbol: String) -> Result<Quote, GetQuoteError> {"
+    ));
+    assert!(
+        content.contains("#[serde(rename = \"getQuoteResponse\")]\npub struct GetQuoteResponse {")
+    );
+    assert!(content.contains("    #[serde(rename = \"return\")]\n    pub r#return: Quote,"));
+    assert!(content.contains("Ok(response.r#return)"));
+
+    // Several output parts are returned as the response struct
+    assert!(content.contains(
+        "pub struct GetHistoryResponse {\n    pub prices: PriceList,\n    pub complete: bool,\n}"
+    ));
+}
+
+#[test]
+fn test_generate_from_accounts_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/accounts.wsdl")
+        .out_dir(dir.path())
+        .encoded(true)
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "RPC/encoded code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Encoded operations are called with SOAP encoding, literal ones as before
+    assert!(content.contains(
+        "self.client.call_encoded(\"findAccounts\", Some(\"urn:accounts#findAccounts\"), \"urn:accounts\", &request).await"
+    ));
+    assert!(content.contains(
+        "self.client.call_with_soap_action(\"getVersion\", Some(\"urn:accounts#getVersion\"), Some(\"urn:accounts\"), false, &request).await"
+    ));
+
+    // Values are annotated with their xsi:type
+    assert!(content.contains(
+        "#[serde(rename = \"maxResults\", serialize_with = \"FindAccountsRequest::serialize_max_results\")]"
+    ));
+    assert!(content.contains(
+        "soapus_runtime::encoding::serialize_typed(value, \"http://www.w3.org/2001/XMLSchema\", \"int\", serializer)"
+    ));
+    assert!(content.contains(
+        "soapus_runtime::encoding::serialize_typed(value, \"http://example.com/accounts\", \"AccountStatus\", serializer)"
+    ));
+
+    // soapenc:Array types become Vecs of their items, both ways of declaring the item type
+    assert!(!content.contains("pub struct ArrayOfString"));
+    assert!(!content.contains("pub struct ArrayOfAccount"));
+    assert!(content
+        .contains("pub async fn find_accounts(&self, numbers: Vec<String>, max_results: i32)"));
+    assert!(content.contains("    pub accounts: Vec<Account>,"));
+    assert!(content.contains(
+        "soapus_runtime::encoding::serialize_array(value, \"http://example.com/accounts\", \"Account\", serializer)"
+    ));
+    assert!(content.contains("deserialize_with = \"soapus_runtime::encoding::deserialize_array\""));
+    assert!(content.contains(
+        "deserialize_with = \"soapus_runtime::encoding::deserialize_optional_array\", default)]\n    pub holders: Option<Vec<String>>,"
+    ));
+
+    // Without opting in, encoded operations are called like literal ones
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/accounts.wsdl")
+        .out_dir(dir.path())
+        .generate()
+        .unwrap();
+    let content = fs::read_to_string(&result.output_file).unwrap();
+    assert!(!content.contains("call_encoded"));
+    assert!(!content.contains("soapus_runtime::encoding"));
+}
+
+#[test]
+fn test_generate_from_bookings_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/bookings.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Multi-part code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Body parts become arguments, followed by the header part
+    assert!(content.contains(
+        "pub async fn book(&self, guest: Guest, stay: Stay, channel: String) -> SoapResult<BookResponse> {"
+    ));
+    assert!(content.contains("let request = BookRequest { guest, stay };"));
+    assert!(content.contains(
+        "self.client.with_headers(headers).call_parts(\"Book\", Some(\"http://example.com/bookings/Book\"), \
+         &[\"http://example.com/bookings\", \"http://example.com/bookings\"], \
+         ELEMENT_FORM_QUALIFIED, &request).await"
+    ));
+
+    // Parts are named after their elements
+    assert!(content.contains(
+        "pub struct BookRequest {\n    #[serde(rename = \"Guest\")]\n    pub guest: Guest,"
+    ));
+    assert!(content.contains(
+        "pub struct BookResponse {\n    #[serde(rename = \"Confirmation\")]\n    pub confirmation: Confirmation,\n    #[serde(rename = \"Price\")]\n    pub price: f64,\n}"
+    ));
+
+    // A single body part keeps the request element signature
+    assert!(content.contains(
+        "pub async fn get_booking(&self, request: BookingQuery, channel: String) -> SoapResult<Booking> {"
+    ));
+    assert!(!content.contains("pub struct GetBookingRequest"));
+}
+
+#[test]
+fn test_generate_from_sessions_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/sessions.wsdl")
+        .out_dir(dir.path())
+        .unwrapped_methods(true)
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "SOAP header code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Input headers become typed arguments, serialized into header blocks
+    assert!(content.contains(
+        "pub async fn login(&self, request: Login, auth_header: AuthHeader) -> SoapResult<LoginResponse> {"
+    ));
+    assert!(content.contains(
+        "soapus_runtime::HeaderBlock::new(\"AuthHeader\", &auth_header)?\n                \
+         .with_namespace(\"http://example.com/sessions\", ELEMENT_FORM_QUALIFIED),"
+    ));
+    assert!(content.contains(
+        "self.client.with_headers(headers).call_with_soap_action(\"Login\", \
+         Some(\"http://example.com/sessions/Login\"), Some(TARGET_NAMESPACE), ELEMENT_FORM_QUALIFIED, &request).await"
+    ));
+
+    // Typed parts are named after the part in the namespace of the header,
+    // clashing argument names are suffixed
+    assert!(content.contains(
+        "pub async fn get_profile(&self, request: GetProfile, session_header: SessionHeader, request_header: String)"
+    ));
+    assert!(content.contains(
+        "soapus_runtime::HeaderBlock::new(\"request\", &request_header)?\n                \
+         .with_namespace(\"http://example.com/clients\", ELEMENT_FORM_QUALIFIED),"
+    ));
+
+    // Unwrapped methods pass the headers on
+    assert!(content
+        .contains("self.login(Login {}, auth_header).await.map(|response| response.expires)"));
+}
+
+#[test]
+fn test_generate_from_ledger_wsdl() {
+    let dir = tempdir().unwrap();
+
+    // Policies are not enforced unless enabled
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/ledger.wsdl")
+        .out_dir(dir.path())
+        .generate()
+        .unwrap();
+    let content = fs::read_to_string(&result.output_file).unwrap();
+    assert!(!content.contains("SecurityPolicy"));
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/ledger.wsdl")
+        .out_dir(dir.path())
+        .ws_security(true)
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Security policy code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // The policies of binding, input and output combined, the algorithm
+    // suite selecting the key size
+    assert!(content.contains(
+        "        let policy = soapus_runtime::security::SecurityPolicy {\n            \
+         timestamp: true,\n            sign_body: true,\n            encrypt_body: true,\n            \
+         signed_response: true,\n            \
+         encryption_algorithm: Some(soapus_runtime::security::EncryptionAlgorithm::Aes128Cbc),\n            \
+         ..Default::default()\n        };\n"
+    ));
+    assert!(content.contains(
+        "self.client.with_security_policy(&policy)?.call_with_soap_action(\"PostEntry\""
+    ));
+
+    // A UsernameToken with password digest from the policy of the operation
+    assert!(content.contains(
+        "            username_token: true,\n            password_digest: true,\n            timestamp: true,\n"
+    ));
+}
+
+#[test]
+fn test_generate_from_tickets_wsdl() {
+    let dir = tempdir().unwrap();
+
+    // No WS-Addressing headers are sent unless enabled
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/tickets.wsdl")
+        .out_dir(dir.path())
+        .generate()
+        .unwrap();
+    let content = fs::read_to_string(&result.output_file).unwrap();
+    assert!(!content.contains("addressing"));
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/tickets.wsdl")
+        .out_dir(dir.path())
+        .ws_addressing(true)
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "WS-Addressing code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // The binding uses WS-Addressing, the actions come from the portType
+    assert!(content.contains(
+        "self.client.with_addressing_required().with_addressing_action(\"http://tempuri.org/ITicketService/OpenTicket\").call_with_soap_action(\"OpenTicket\", Some(\"http://tempuri.org/ITicketService/OpenTicket\")"
+    ));
+    assert!(content.contains(
+        "self.client.with_addressing_required().with_addressing_action(\"urn:tickets:close\").call_with_soap_action(\"CloseTicket\", None"
+    ));
+}
+
+#[test]
+fn test_generate_unwrapped_methods() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/calculator.wsdl")
+        .out_dir(dir.path())
+        .unwrapped_methods(true)
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Unwrapped code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // The children of the request element become arguments, the single
+    // child of the response element is returned
+    assert!(content.contains(
+        "pub async fn add_unwrapped(&self, int_a: i32, int_b: i32) -> SoapResult<i32> {"
+    ));
+    assert!(content
+        .contains("self.add(Add { int_a, int_b }).await.map(|response| response.add_result)"));
+
+    // The methods taking the request element stay
+    assert!(content.contains("pub async fn add(&self, request: Add) -> SoapResult<AddResponse> {"));
+
+    // An empty request element gives a method without arguments
+    let dir = tempdir().unwrap();
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/countryinfo.wsdl")
+        .out_dir(dir.path())
+        .unwrapped_methods(true)
+        .generate()
+        .unwrap();
+    let content = fs::read_to_string(&result.output_file).unwrap();
+    assert!(content.contains(
+        "pub async fn list_of_continents_by_name_unwrapped(&self) -> SoapResult<ArrayOftContinent> {"
+    ));
+    assert!(content.contains("self.list_of_continents_by_name(ListOfContinentsByName {}).await"));
+
+    // RPC operations are not wrapped
+    let dir = tempdir().unwrap();
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/quotes.wsdl")
+        .out_dir(dir.path())
+        .unwrapped_methods(true)
+        .generate()
+        .unwrap();
+    assert!(!result.code.contains("_unwrapped("));
+
+    // The mode is opt-in
+    let dir = tempdir().unwrap();
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/calculator.wsdl")
+        .out_dir(dir.path())
+        .generate()
+        .unwrap();
+    assert!(!result.code.contains("_unwrapped("));
+}
+
+#[test]
+fn test_generate_unwrapped_methods_edge_cases() {
+    let dir = tempdir().unwrap();
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/wrapped.wsdl")
+        .out_dir(dir.path())
+        .unwrapped_methods(true)
+        .generate()
+        .unwrap();
+    let content = result.code;
+
+    // Keyword children become raw identifiers, optional and repeated children keep their types
+    assert!(content.contains(
+        "pub async fn search_unwrapped(&self, r#type: String, query: String, limit: Option<i32>, tag: Option<Vec<String>>) -> SoapResult<Option<Vec<String>>> {"
+    ));
+    assert!(content.contains(
+        "self.search(Search { r#type, query, limit, tag }).await.map(|response| response.hit)"
+    ));
+    assert!(content.contains("    /// * `type` - The type element\n"));
+
+    // A response element without children is returned as it is
+    assert!(content.contains("pub async fn ping_unwrapped(&self) -> SoapResult<PingResponse> {"));
+    assert!(content.contains("        self.ping(Ping {}).await\n"));
+
+    // Request elements not named after the operation, with attributes or with
+    // choices don't follow the wrapped convention
+    assert!(!content.contains("fn lookup_unwrapped("));
+    assert!(!content.contains("fn tag_unwrapped("));
+    assert!(!content.contains("fn move_unwrapped("));
+    assert!(content.contains("pub async fn lookup(&self, request: LookupRequest)"));
+
+    // Operations named like Rust keywords get raw identifier methods
+    assert!(content.contains("pub async fn r#move(&self, request: Move) -> SoapResult<Done> {"));
+}
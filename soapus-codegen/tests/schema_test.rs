@@ -0,0 +1,356 @@
+use soapus_codegen::{CodegenError, SoapClientGenerator};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_generate_from_payments_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/payments.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Choice code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // A type whose content is a choice holds the generated enum
+    assert!(content.contains("pub struct PaymentMethod {"));
+    assert!(
+        content.contains("    #[serde(rename = \"$value\")]\n    pub choice: PaymentMethodChoice,")
+    );
+    assert!(content.contains("pub enum PaymentMethodChoice {"));
+    assert!(content.contains("    #[serde(rename = \"card\")]\n    Card(Card),"));
+    assert!(content.contains("    #[serde(rename = \"voucher\")]\n    Voucher(String),"));
+
+    // Repeated and optional choices nested in sequences
+    assert!(content.contains("pub choice: Vec<PayerChoice>,"));
+    assert!(content.contains("    #[serde(rename = \"customerNumber\")]\n    CustomerNumber(i32),"));
+    assert!(content.contains("pub choice: Option<SubmitPaymentResponseChoice>,"));
+}
+
+#[test]
+fn test_generate_rejects_two_repeated_choices() {
+    let dir = tempdir().unwrap();
+    let wsdl_path = dir.path().join("contacts.wsdl");
+    fs::write(
+        &wsdl_path,
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<wsdl:definitions xmlns:wsdl="http://schemas.xmlsoap.org/wsdl/"
+                  xmlns:xs="http://www.w3.org/2001/XMLSchema"
+                  targetNamespace="http://example.com/contacts">
+  <wsdl:types>
+    <xs:schema targetNamespace="http://example.com/contacts">
+      <xs:complexType name="Contact">
+        <xs:sequence>
+          <xs:choice maxOccurs="unbounded">
+            <xs:element name="email" type="xs:string"/>
+            <xs:element name="phone" type="xs:string"/>
+          </xs:choice>
+          <xs:choice maxOccurs="unbounded">
+            <xs:element name="street" type="xs:string"/>
+            <xs:element name="poBox" type="xs:string"/>
+          </xs:choice>
+        </xs:sequence>
+      </xs:complexType>
+    </xs:schema>
+  </wsdl:types>
+</wsdl:definitions>"#,
+    )
+    .unwrap();
+
+    // Only one choice can keep its repetitions, generation fails instead of dropping elements
+    let result = SoapClientGenerator::builder()
+        .wsdl_path(&wsdl_path)
+        .out_dir(dir.path())
+        .generate();
+
+    match result {
+        Err(CodegenError::UnsupportedFeature { feature }) => {
+            assert!(feature.contains("'Contact'"), "{}", feature)
+        }
+        other => panic!("Expected UnsupportedFeature, got {:?}", other.map(|_| ())),
+    }
+    assert!(!dir.path().join("soap_client.rs").exists());
+}
+
+#[test]
+fn test_generate_from_employees_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/employees.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Inheritance code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Extensions are flattened with base fields first
+    let manager = &content[content.find("pub struct Manager {").unwrap()..];
+    let manager = &manager[..manager.find('}').unwrap()];
+    let fields: Vec<_> = manager
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.trim().strip_prefix("pub "))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            "name: String,",
+            "birth_date: Option<String>,",
+            "employee_id: i32,",
+            "department: String,",
+            "reports: Option<Vec<AnyEmployee>>,",
+            "choice: ManagerChoice,",
+        ]
+    );
+    assert!(content.contains("pub struct Contact {\n    pub name: String,\n}"));
+
+    // Restrictions only keep their own content
+    assert!(content.contains("pub struct AnonymousPerson {\n    pub name: String,\n}"));
+
+    // Conversions into every base type
+    assert!(content.contains("impl From<Manager> for Employee {"));
+    assert!(content.contains("impl From<Manager> for Party {"));
+    assert!(content.contains("impl From<Person> for Party {"));
+    assert!(content.contains("impl From<Contact> for Party {"));
+    assert!(!content.contains("impl From<AnonymousPerson> for Person"));
+
+    // Base types with derived types are referenced through an xsi:type dispatching enum
+    assert!(content.contains("pub relations: Option<Vec<AnyParty>>,"));
+    assert!(content
+        .contains("pub enum AnyEmployee {\n    Employee(Employee),\n    Manager(Manager),\n}"));
+    assert!(content.contains("impl soapus_runtime::xsi::XsiTypeDispatch for AnyEmployee {"));
+    assert!(content.contains(
+        "None | Some(\"Employee\") => Employee::deserialize(deserializer).map(Self::Employee),"
+    ));
+    assert!(content
+        .contains("Some(\"Manager\") => Manager::deserialize(deserializer).map(Self::Manager),"));
+    assert!(content.contains("soapus_runtime::xsi::XsiTyped::new(\"Manager\", Some(\"http://example.com/employees\"), value)"));
+    assert!(content.contains("impl From<Manager> for AnyEmployee {"));
+
+    // Abstract base types have no variant of their own
+    assert!(content.contains("pub enum AnyParty {"));
+    assert!(!content.contains("    Party(Party),"));
+    assert!(!content.contains("None | Some(\"Party\")"));
+}
+
+#[test]
+fn test_generate_from_invoices_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/invoices.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Attribute code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Required, optional and defaulted attributes
+    assert!(content.contains("    #[serde(rename = \"@id\")]\n    pub id_attr: String,"));
+    assert!(content.contains(
+        "    #[serde(rename = \"@paid\", skip_serializing_if = \"Option::is_none\")]\n    pub paid: Option<bool>,"
+    ));
+    assert!(content.contains("pub status: Option<InvoiceStatus>,"));
+    assert!(content.contains(
+        "    /// Default: `EUR`\n    #[serde(rename = \"@currency\", default = \"Invoice::default_currency\")]\n    pub currency: String,"
+    ));
+    assert!(content
+        .contains("    fn default_currency() -> String {\n        \"EUR\".to_string()\n    }"));
+    assert!(content.contains("    fn default_include_lines() -> bool {\n        false\n    }"));
+
+    // Attribute groups, including nested ones
+    assert!(content.contains("    /// Fixed value: `2.0`\n"));
+    assert!(content.contains("    fn default_version() -> i32 {\n        1_i32\n    }"));
+    assert!(content.contains("pub created_by: Option<String>,"));
+
+    // Inherited attributes, prohibited ones are removed by restrictions
+    let credit_note = &content[content.find("pub struct CreditNote {").unwrap()..];
+    assert!(credit_note[..credit_note.find('}').unwrap()].contains("pub paid: Option<bool>,"));
+    assert!(content.contains("    #[serde(rename = \"@reason\")]\n    pub reason: String,"));
+    let draft = &content[content.find("pub struct DraftInvoice {").unwrap()..];
+    assert!(!draft[..draft.find('}').unwrap()].contains("paid"));
+    assert!(content.contains("            id_attr: value.id_attr,\n"));
+
+    // Simple content becomes a `$text` field next to the attributes
+    assert!(content.contains(
+        "pub struct Amount {\n    #[serde(rename = \"@currency\")]\n    pub currency: String,\n    #[serde(rename = \"$text\")]\n    pub value: f64,\n}"
+    ));
+    let precise = &content[content.find("pub struct PreciseAmount {").unwrap()..];
+    let precise = &precise[..precise.find('}').unwrap()];
+    assert!(precise.contains("pub precision: Option<i32>,"));
+    assert!(precise.contains("pub value: f64,"));
+    assert!(content.contains("            value: value.value,\n"));
+    assert!(content.contains("pub balance: Option<AnyAmount>,"));
+
+    // The text of a string is empty for an empty element; `value` is taken by the text
+    assert!(content.contains("    #[serde(rename = \"$text\", default)]\n    pub value: String,"));
+    assert!(content.contains("pub value_attr: Option<String>,"));
+}
+
+#[test]
+fn test_generate_from_reservations_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/reservations.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Group code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Group content is inserted where the group is referenced, nested groups included
+    assert!(content.contains(
+        "pub struct Guest {\n    pub id: String,\n    #[serde(rename = \"givenName\")]\n    pub given_name: String,\n    pub surname: String,\n    #[serde(rename = \"birthDate\")]\n    pub birth_date: Option<String>,\n    pub email: Option<String>,\n    pub phone: Option<String>,\n}"
+    ));
+
+    // A choice group between elements, a repeated group at the end: the
+    // repeated group keeps the order of its elements in the enum list
+    let reservation = &content[content.find("pub struct Reservation {").unwrap()..];
+    let reservation = &reservation[..reservation.find('}').unwrap()];
+    assert!(reservation.contains("pub hotel_code: String,"));
+    assert!(reservation.contains("pub card_token: Option<String>,"));
+    assert!(reservation.contains(
+        "    #[serde(rename = \"$value\", default)]\n    pub choice: Vec<ReservationChoice>,"
+    ));
+    assert!(content.contains("    Arrival(String),"));
+    assert!(content.contains("    Nights(i32),"));
+
+    // Groups as the whole content and within an extension
+    assert!(content.contains("pub struct Remark {"));
+    let remark = &content[content.find("pub struct Remark {").unwrap()..];
+    assert!(remark[..remark.find('}').unwrap()].contains("pub email: String,"));
+    let vip = &content[content.find("pub struct VipGuest {").unwrap()..];
+    let vip = &vip[..vip.find('}').unwrap()];
+    assert!(vip.contains("pub given_name: String,"));
+    assert!(vip.contains("pub program: String,"));
+
+    // Groups referenced in a choice add alternatives
+    let lookup = &content[content.find("pub enum LookupChoice {").unwrap()..];
+    let lookup = &lookup[..lookup.find('}').unwrap()];
+    for variant in ["Confirmation", "GuestEmail", "Pnr", "TicketNumber"] {
+        assert!(lookup.contains(&format!("    {}(String),", variant)));
+    }
+}
+
+#[test]
+fn test_generated_enum_names_avoid_schema_types() {
+    let dir = tempdir().unwrap();
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/collisions.wsdl")
+        .out_dir(dir.path())
+        .generate()
+        .unwrap();
+    let content = fs::read_to_string(&result.output_file).unwrap();
+
+    // Schema types keep their names, the generated enums get the namespace label
+    for (schema_type, generated) in [
+        (
+            "pub struct PayerChoice {",
+            "pub enum CollisionsPayerChoice {",
+        ),
+        ("pub struct AnyParty {", "pub enum CollisionsAnyParty {"),
+        (
+            "pub struct GetPayerError {",
+            "pub enum CollisionsGetPayerError {",
+        ),
+    ] {
+        assert_eq!(content.matches(schema_type).count(), 1, "{}", schema_type);
+        assert_eq!(content.matches(generated).count(), 1, "{}", generated);
+    }
+    assert!(content.contains("pub choice: CollisionsPayerChoice,"));
+    assert!(content.contains("pub party: CollisionsAnyParty,"));
+    assert!(content.contains("Result<GetPayerResponse, CollisionsGetPayerError>"));
+}
+
+#[test]
+fn test_generate_from_catalog_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/catalog.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Element reference code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // A reference within the same (qualified) namespace is a plain field
+    assert!(content.contains("    #[serde(rename = \"Product\")]\n    pub product: Vec<Product>,"));
+
+    // References into the common schema take the type of the declaration and declare its namespace
+    assert!(content.contains(
+        "    #[serde(rename = \"Customer\", serialize_with = \"PlaceOrder::serialize_customer\")]\n    pub customer: Customer,"
+    ));
+    assert!(content.contains("pub address: Option<Address>,"));
+    assert!(content.contains("pub currency: CurrencyCode,"));
+    assert!(content.contains("pub note: Option<String>,"));
+    assert!(content.contains(
+        "soapus_runtime::namespace::serialize_qualified(value, \"http://example.com/common\", serializer)"
+    ));
+
+    // References as alternatives of a choice
+    assert!(content.contains("    Product(Product),"));
+    assert!(content.contains("    Note(String),"));
+}
+
+#[test]
+fn test_generate_from_shipments_wsdl() {
+    let dir = tempdir().unwrap();
+
+    let result = SoapClientGenerator::builder()
+        .wsdl_path("../testdata/wsdl/shipments.wsdl")
+        .out_dir(dir.path())
+        .generate();
+
+    assert!(
+        result.is_ok(),
+        "Anonymous type code generation failed: {:?}",
+        result.err()
+    );
+
+    let content = fs::read_to_string(&result.unwrap().output_file).unwrap();
+
+    // Anonymous types nested at any depth get structs named after their context
+    assert!(content.contains("pub line_item: Vec<OrderLineItem>,"));
+    assert!(content.contains("pub struct OrderLineItem {"));
+    assert!(content.contains("pub options: Option<OrderLineItemOptions>,"));
+    assert!(content.contains("pub struct OrderLineItemOptions {"));
+    assert!(content.contains("pub tracking: CreateShipmentResponseTracking,"));
+    assert!(content.contains("pub carrier: CreateShipmentResponseTrackingCarrier,"));
+    assert!(content.contains("    Accepted(CreateShipmentResponseAccepted),"));
+    assert!(content.contains("pub package: Vec<PackagingPackage>,"));
+
+    // Names of global types are not taken
+    assert!(content.contains("pub address: ShipmentAddress2,"));
+    assert!(content.contains("pub return_address: Option<ShipmentAddress>,"));
+    assert!(content.contains("pub struct ShipmentAddress {\n    pub label: String,"));
+}
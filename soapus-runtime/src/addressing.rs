@@ -0,0 +1,374 @@
+//! WS-Addressing 1.0 message addressing properties
+//!
+//! A client configured with [`WsAddressing`] sends these header blocks with
+//! every request:
+//!
+//! ```xml
+//! <wsa:Action xmlns:wsa="..." soap:mustUnderstand="1">http://tempuri.org/IService/Ping</wsa:Action>
+//! <wsa:MessageID xmlns:wsa="...">urn:uuid:6b29fc40-ca47-4067-b31d-00dd010662da</wsa:MessageID>
+//! <wsa:ReplyTo xmlns:wsa="...">
+//!   <wsa:Address>http://www.w3.org/2005/08/addressing/anonymous</wsa:Address>
+//! </wsa:ReplyTo>
+//! <wsa:To xmlns:wsa="..." soap:mustUnderstand="1">http://example.com/soap</wsa:To>
+//! ```
+//!
+//! The action is the SOAPAction of the call unless set with
+//! [`SoapClient::with_addressing_action`](crate::SoapClient::with_addressing_action).
+//! The `wsa:RelatesTo` of a response must name the `wsa:MessageID` of its
+//! request.
+//!
+//! This module is only available with the `ws-addressing` feature.
+
+use crate::envelope::{SOAP_11_ENVELOPE_NS, SOAP_12_ENVELOPE_NS};
+use crate::error::{SoapError, SoapResult};
+use crate::header::HeaderBlock;
+use quick_xml::escape::{escape, unescape};
+use quick_xml::events::Event;
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+
+/// Namespace of WS-Addressing 1.0
+pub const WSA_NAMESPACE: &str = "http://www.w3.org/2005/08/addressing";
+
+/// Namespace of the WS-Addressing member submission of August 2004
+pub const WSA_SUBMISSION_NAMESPACE: &str = "http://schemas.xmlsoap.org/ws/2004/08/addressing";
+
+/// Version of WS-Addressing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressingVersion {
+    /// WS-Addressing 1.0 (W3C Recommendation, WCF `WSAddressing10`)
+    #[default]
+    W3c,
+    /// The member submission of August 2004 (WCF `WSAddressingAugust2004`)
+    Submission,
+}
+
+impl AddressingVersion {
+    /// Namespace of the `wsa` elements
+    pub fn namespace(&self) -> &'static str {
+        match self {
+            AddressingVersion::W3c => WSA_NAMESPACE,
+            AddressingVersion::Submission => WSA_SUBMISSION_NAMESPACE,
+        }
+    }
+
+    /// Address of an endpoint receiving replies on the back-channel of the request
+    pub fn anonymous(&self) -> &'static str {
+        match self {
+            AddressingVersion::W3c => "http://www.w3.org/2005/08/addressing/anonymous",
+            AddressingVersion::Submission => {
+                "http://schemas.xmlsoap.org/ws/2004/08/addressing/role/anonymous"
+            }
+        }
+    }
+
+    /// Whether a `RelationshipType` marks a reply, which is the default
+    fn is_reply(&self, relationship: &str) -> bool {
+        match self {
+            AddressingVersion::W3c => relationship == "http://www.w3.org/2005/08/addressing/reply",
+            AddressingVersion::Submission => {
+                relationship == "wsa:Reply" || relationship.ends_with(":Reply")
+            }
+        }
+    }
+}
+
+/// WS-Addressing settings of a client
+///
+/// # Example
+///
+/// ```
+/// use soapus_runtime::addressing::WsAddressing;
+/// use soapus_runtime::SoapClient;
+///
+/// let client = SoapClient::builder("http://example.com/soap")
+///     .ws_addressing(WsAddressing::new().with_fault_to("http://example.com/faults"))
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WsAddressing {
+    version: AddressingVersion,
+    to: Option<String>,
+    reply_to: Option<String>,
+    fault_to: Option<String>,
+}
+
+impl WsAddressing {
+    /// Create settings sending WS-Addressing 1.0 headers to the endpoint of the client
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send the headers of another version of WS-Addressing
+    pub fn with_version(self, version: AddressingVersion) -> Self {
+        Self { version, ..self }
+    }
+
+    /// Send `to` as `wsa:To` instead of the endpoint of the client
+    ///
+    /// For services whose logical address differs from the one they are
+    /// reached at, e.g. behind a proxy.
+    pub fn with_to(self, to: impl Into<String>) -> Self {
+        Self {
+            to: Some(to.into()),
+            ..self
+        }
+    }
+
+    /// Ask for replies at `address` instead of the back-channel of the request
+    pub fn with_reply_to(self, address: impl Into<String>) -> Self {
+        Self {
+            reply_to: Some(address.into()),
+            ..self
+        }
+    }
+
+    /// Ask for faults at `address`, which are otherwise sent like replies
+    pub fn with_fault_to(self, address: impl Into<String>) -> Self {
+        Self {
+            fault_to: Some(address.into()),
+            ..self
+        }
+    }
+
+    /// Get the version of WS-Addressing
+    pub fn version(&self) -> AddressingVersion {
+        self.version
+    }
+
+    /// Get the `wsa:To` address, if it is not the endpoint of the client
+    pub fn to(&self) -> Option<&str> {
+        self.to.as_deref()
+    }
+
+    /// Get the `wsa:ReplyTo` address, if it is not the anonymous one
+    pub fn reply_to(&self) -> Option<&str> {
+        self.reply_to.as_deref()
+    }
+
+    /// Get the `wsa:FaultTo` address, if any
+    pub fn fault_to(&self) -> Option<&str> {
+        self.fault_to.as_deref()
+    }
+
+    /// Build the header blocks of a request
+    ///
+    /// `wsa:Action` and `wsa:To` must be understood by the receiver.
+    pub fn headers(&self, action: &str, endpoint: &str, message_id: &str) -> Vec<HeaderBlock> {
+        let namespace = self.version.namespace();
+        let element = |name: &str, content: &str| {
+            format!(
+                r#"<wsa:{} xmlns:wsa="{}">{}</wsa:{}>"#,
+                name, namespace, content, name
+            )
+        };
+        let address = |address: &str| format!("<wsa:Address>{}</wsa:Address>", escape(address));
+
+        let mut headers = vec![
+            HeaderBlock::from_xml(element("Action", &escape(action))).with_must_understand(true),
+            HeaderBlock::from_xml(element("MessageID", &escape(message_id))),
+            HeaderBlock::from_xml(element(
+                "ReplyTo",
+                &address(self.reply_to.as_deref().unwrap_or(self.version.anonymous())),
+            )),
+        ];
+        if let Some(fault_to) = &self.fault_to {
+            headers.push(HeaderBlock::from_xml(element(
+                "FaultTo",
+                &address(fault_to),
+            )));
+        }
+        headers.push(
+            HeaderBlock::from_xml(element(
+                "To",
+                &escape(self.to.as_deref().unwrap_or(endpoint)),
+            ))
+            .with_must_understand(true),
+        );
+        headers
+    }
+
+    /// Check that a response relates to the request with `message_id`
+    ///
+    /// Responses without `wsa:RelatesTo` reply relationship are accepted, as
+    /// sent by services ignoring WS-Addressing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoapError::AddressingError`] if the response replies to
+    /// other messages only.
+    pub fn verify_relates_to(&self, xml: &str, message_id: &str) -> SoapResult<()> {
+        let replies = self.replies_to(xml)?;
+        if replies.is_empty() || replies.iter().any(|id| id == message_id) {
+            return Ok(());
+        }
+        Err(SoapError::AddressingError(format!(
+            "Response relates to {} instead of {}",
+            replies.join(", "),
+            message_id
+        )))
+    }
+
+    /// Get the message IDs a response replies to by `wsa:RelatesTo` header blocks
+    fn replies_to(&self, xml: &str) -> SoapResult<Vec<String>> {
+        let namespace = self.version.namespace().as_bytes();
+        let mut reader = NsReader::from_str(xml);
+        let mut replies = Vec::new();
+        let mut depth = 0;
+        let mut in_header = false;
+        loop {
+            let (resolved, event) = reader.read_resolved_event()?;
+            let ns = match resolved {
+                ResolveResult::Bound(Namespace(ns)) => ns,
+                _ => &[],
+            };
+            match event {
+                Event::Start(e) => {
+                    depth += 1;
+                    let local_name = e.local_name();
+                    let in_envelope = ns == SOAP_11_ENVELOPE_NS.as_bytes()
+                        || ns == SOAP_12_ENVELOPE_NS.as_bytes();
+                    match (depth, local_name.as_ref()) {
+                        (2, b"Header") if in_envelope => in_header = true,
+                        // Headers precede the body
+                        (2, b"Body") if in_envelope => break,
+                        (3, b"RelatesTo") if in_header && ns == namespace => {
+                            let mut reply = true;
+                            for attribute in e.attributes() {
+                                let attribute = attribute.map_err(quick_xml::Error::from)?;
+                                if attribute.key.local_name().as_ref() == b"RelationshipType" {
+                                    reply = self.version.is_reply(&attribute.unescape_value()?);
+                                }
+                            }
+                            let text = reader.read_text(e.name())?;
+                            depth -= 1;
+                            if reply {
+                                replies.push(
+                                    unescape(text.trim())
+                                        .map_err(quick_xml::Error::from)?
+                                        .into_owned(),
+                                );
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::End(_) => {
+                    depth -= 1;
+                    if depth == 1 {
+                        in_header = false;
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(replies)
+    }
+}
+
+/// Create a `wsa:MessageID`, a random (version 4) UUID URN
+pub fn message_id() -> SoapResult<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| SoapError::AddressingError(format!("Failed to create message ID: {}", e)))?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    Ok(format!(
+        "urn:uuid:{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envelope::SoapVersion;
+
+    const MESSAGE_ID: &str = "urn:uuid:6b29fc40-ca47-4067-b31d-00dd010662da";
+
+    fn response(relates_to: &str) -> String {
+        format!(
+            r#"<s:Envelope xmlns:s="http://www.w3.org/2003/05/soap-envelope" xmlns:a="{}"><s:Header><a:Action s:mustUnderstand="1">http://tempuri.org/IService/PingResponse</a:Action>{}</s:Header><s:Body><PingResponse xmlns="http://tempuri.org/"/></s:Body></s:Envelope>"#,
+            WSA_NAMESPACE, relates_to
+        )
+    }
+
+    #[test]
+    fn test_message_id() {
+        let id = message_id().unwrap();
+        assert_eq!(id.len(), 45);
+        assert!(id.starts_with("urn:uuid:"));
+        assert_eq!(&id[23..24], "4");
+        assert_ne!(id, message_id().unwrap());
+    }
+
+    #[test]
+    fn test_headers() {
+        let headers = WsAddressing::new()
+            .with_fault_to("http://example.com/faults?a&b")
+            .headers(
+                "http://tempuri.org/IService/Ping",
+                "http://example.com/soap",
+                MESSAGE_ID,
+            );
+        let names: Vec<_> = headers.iter().map(HeaderBlock::name).collect();
+        assert_eq!(names, ["Action", "MessageID", "ReplyTo", "FaultTo", "To"]);
+        assert!(headers[0].must_understand());
+        assert!(!headers[1].must_understand());
+        assert!(headers[4].must_understand());
+        assert_eq!(
+            headers[0].to_xml(SoapVersion::Soap12),
+            format!(
+                r#"<wsa:Action xmlns:wsa="{}" env:mustUnderstand="true">http://tempuri.org/IService/Ping</wsa:Action>"#,
+                WSA_NAMESPACE
+            )
+        );
+        assert!(headers[2]
+            .xml()
+            .contains("<wsa:Address>http://www.w3.org/2005/08/addressing/anonymous</wsa:Address>"));
+        assert!(headers[3]
+            .xml()
+            .contains("<wsa:Address>http://example.com/faults?a&amp;b</wsa:Address>"));
+
+        let headers = WsAddressing::new()
+            .with_version(AddressingVersion::Submission)
+            .with_to("urn:ledger")
+            .headers("urn:ping", "http://example.com/soap", MESSAGE_ID);
+        assert!(headers[0].xml().contains(WSA_SUBMISSION_NAMESPACE));
+        assert!(headers[3].xml().contains(">urn:ledger</wsa:To>"));
+    }
+
+    #[test]
+    fn test_verify_relates_to() {
+        let addressing = WsAddressing::new();
+        let reply = format!("<a:RelatesTo>{}</a:RelatesTo>", MESSAGE_ID);
+        addressing
+            .verify_relates_to(&response(&reply), MESSAGE_ID)
+            .unwrap();
+        // Without a RelatesTo, e.g. from a service ignoring WS-Addressing
+        addressing
+            .verify_relates_to(&response(""), MESSAGE_ID)
+            .unwrap();
+
+        let other = "<a:RelatesTo>urn:uuid:00000000-0000-4000-8000-000000000000</a:RelatesTo>";
+        let error = addressing
+            .verify_relates_to(&response(other), MESSAGE_ID)
+            .unwrap_err();
+        assert!(matches!(error, SoapError::AddressingError(_)));
+
+        // Relations other than replies don't count
+        let related = format!(
+            r#"<a:RelatesTo RelationshipType="urn:example:audit">{}</a:RelatesTo>"#,
+            MESSAGE_ID
+        );
+        assert!(addressing
+            .verify_relates_to(&response(&format!("{}{}", related, other)), MESSAGE_ID)
+            .is_err());
+    }
+}
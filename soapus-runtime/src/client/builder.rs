@@ -0,0 +1,187 @@
+//! Builder for configuring a SOAP client
+
+#[cfg(feature = "ws-addressing")]
+use crate::addressing::WsAddressing;
+use crate::envelope::SoapVersion;
+use crate::header::HeaderBlock;
+#[cfg(feature = "ws-security")]
+use crate::security::{SamlAssertion, SamlProvider, SamlSource, WsSecurity};
+use reqwest::Client;
+use std::time::Duration;
+
+use super::SoapClient;
+
+/// Builder for configuring a SOAP client
+///
+/// Provides a fluent interface for setting up a SOAP client with custom settings.
+///
+/// # Example
+///
+/// ```no_run
+/// use soapus_runtime::{SoapClient, SoapVersion};
+/// use std::time::Duration;
+///
+/// let client = SoapClient::builder("http://example.com/soap")
+///     .soap_version(SoapVersion::Soap12)
+///     .timeout(Duration::from_secs(60))
+///     .soap_action("http://example.com/MyOperation")
+///     .build();
+/// ```
+pub struct SoapClientBuilder {
+    endpoint: String,
+    soap_version: SoapVersion,
+    soap_action: Option<String>,
+    timeout: Duration,
+    http_client: Option<Client>,
+    headers: Vec<HeaderBlock>,
+    #[cfg(feature = "ws-security")]
+    ws_security: Option<WsSecurity>,
+    #[cfg(feature = "ws-security")]
+    saml: Option<SamlSource>,
+    #[cfg(feature = "ws-addressing")]
+    ws_addressing: Option<WsAddressing>,
+}
+
+impl SoapClientBuilder {
+    /// Create a new builder with the given endpoint
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            soap_version: SoapVersion::Soap11,
+            soap_action: None,
+            timeout: Duration::from_secs(30),
+            http_client: None,
+            headers: Vec::new(),
+            #[cfg(feature = "ws-security")]
+            ws_security: None,
+            #[cfg(feature = "ws-security")]
+            saml: None,
+            #[cfg(feature = "ws-addressing")]
+            ws_addressing: None,
+        }
+    }
+
+    /// Set the SOAP protocol version
+    pub fn soap_version(mut self, version: SoapVersion) -> Self {
+        self.soap_version = version;
+        self
+    }
+
+    /// Set the SOAPAction header value
+    pub fn soap_action(mut self, action: impl Into<String>) -> Self {
+        self.soap_action = Some(action.into());
+        self
+    }
+
+    /// Set the request timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Add a header block sent with every request
+    pub fn header(mut self, header: HeaderBlock) -> Self {
+        self.headers.push(header);
+        self
+    }
+
+    /// Authenticate every request with WS-Security
+    ///
+    /// See [`WsSecurity`] for the available tokens.
+    #[cfg(feature = "ws-security")]
+    pub fn ws_security(mut self, ws_security: WsSecurity) -> Self {
+        self.ws_security = Some(ws_security);
+        self
+    }
+
+    /// Send a pre-signed SAML 2.0 assertion in the `wsse:Security` header
+    ///
+    /// The assertion is added to the [`WsSecurity`] settings of the client
+    /// (see [`WsSecurity::with_saml_assertion`]).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use soapus_runtime::security::SamlAssertion;
+    /// use soapus_runtime::SoapClient;
+    ///
+    /// # fn example(issued: String) -> soapus_runtime::SoapResult<()> {
+    /// let client = SoapClient::builder("http://example.com/soap")
+    ///     .saml_assertion(SamlAssertion::new(issued)?)
+    ///     .build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "ws-security")]
+    pub fn saml_assertion(mut self, assertion: SamlAssertion) -> Self {
+        self.saml = Some(SamlSource::Assertion(assertion));
+        self
+    }
+
+    /// Send SAML 2.0 assertions of `provider`, renewed before they expire
+    ///
+    /// See [`WsSecurity::with_saml_provider`].
+    #[cfg(feature = "ws-security")]
+    pub fn saml_provider(mut self, provider: impl SamlProvider + 'static) -> Self {
+        self.saml = Some(SamlSource::provider(provider));
+        self
+    }
+
+    /// Send WS-Addressing headers with every request
+    ///
+    /// See [`WsAddressing`] for the available settings.
+    #[cfg(feature = "ws-addressing")]
+    pub fn ws_addressing(mut self, ws_addressing: WsAddressing) -> Self {
+        self.ws_addressing = Some(ws_addressing);
+        self
+    }
+
+    /// Set a custom HTTP client
+    ///
+    /// This allows you to configure the underlying reqwest client with custom settings
+    /// such as proxies, authentication, or custom certificates.
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Build the SOAP client
+    pub fn build(self) -> SoapClient {
+        #[cfg(feature = "ws-security")]
+        let ws_security = match self.saml {
+            Some(saml) => Some(self.ws_security.unwrap_or_default().with_saml(saml)),
+            None => self.ws_security,
+        };
+        SoapClient {
+            endpoint: self.endpoint,
+            http_client: self.http_client.unwrap_or_default(),
+            soap_version: self.soap_version,
+            soap_action: self.soap_action,
+            timeout: self.timeout,
+            headers: self.headers,
+            #[cfg(feature = "ws-security")]
+            ws_security,
+            #[cfg(feature = "ws-addressing")]
+            ws_addressing: self.ws_addressing,
+            #[cfg(feature = "ws-addressing")]
+            addressing_action: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_builder() {
+        let client = SoapClient::builder("http://example.com/soap")
+            .soap_version(SoapVersion::Soap12)
+            .soap_action("http://example.com/MyAction")
+            .timeout(Duration::from_secs(60))
+            .build();
+
+        assert_eq!(client.endpoint(), "http://example.com/soap");
+        assert_eq!(client.soap_version(), SoapVersion::Soap12);
+    }
+}
@@ -0,0 +1,406 @@
+//! SOAP calls over HTTP
+
+use crate::envelope::{SoapEnvelope, SoapVersion};
+use crate::error::{SoapError, SoapResult};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "tracing")]
+use tracing::{debug, info, instrument, warn};
+
+use super::{SoapClient, SoapResponse};
+
+impl SoapClient {
+    /// Make a SOAP call
+    ///
+    /// This method performs the complete SOAP request/response cycle:
+    /// 1. Serializes the request to XML and wraps it in a SOAP envelope
+    /// 2. Sends an HTTP POST request to the endpoint
+    /// 3. Checks for HTTP errors and SOAP faults
+    /// 4. Parses and deserializes the response
+    ///
+    /// # Type Parameters
+    ///
+    /// * `Req` - The request type (must implement `Serialize`)
+    /// * `Resp` - The response type (must implement `Deserialize`)
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - The SOAP operation name (used for SOAPAction header)
+    /// * `request` - The request body to send
+    ///
+    /// # Returns
+    ///
+    /// The deserialized response or a `SoapError`
+    #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(endpoint = %self.endpoint, soap_version = ?self.soap_version)))]
+    pub async fn call<Req, Resp>(&self, operation: &str, request: &Req) -> SoapResult<Resp>
+    where
+        Req: Serialize,
+        Resp: for<'de> Deserialize<'de>,
+    {
+        #[cfg(feature = "tracing")]
+        info!(operation = %operation, "Initiating SOAP call");
+
+        #[cfg(feature = "metrics")]
+        let start = std::time::Instant::now();
+
+        let result = self
+            .call_with_soap_action(operation, None, None, true, request)
+            .await;
+
+        #[cfg(feature = "metrics")]
+        {
+            let duration = start.elapsed();
+            metrics::histogram!("soap_request_duration_seconds", duration.as_secs_f64());
+
+            metrics::increment_counter!("soap_requests_total");
+
+            if result.is_err() {
+                metrics::increment_counter!("soap_errors_total");
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(_) => info!(operation = %operation, "SOAP call completed successfully"),
+            Err(e) => warn!(operation = %operation, error = %e, "SOAP call failed"),
+        }
+
+        result
+    }
+
+    /// Call a SOAP operation with explicit SOAPAction header
+    ///
+    /// This method is similar to `call` but allows specifying a custom SOAPAction
+    /// header value, which is often required for .NET and other SOAP services.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `Req` - The request type (must implement `Serialize`)
+    /// * `Resp` - The response type (must implement `Deserialize`)
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - The SOAP operation name
+    /// * `soap_action` - The SOAPAction header value (if None, uses operation name)
+    /// * `namespace` - The XML namespace for the request body element (if None, no namespace is added)
+    /// * `request` - The request body to send
+    ///
+    /// # Returns
+    ///
+    /// The deserialized response or a `SoapError`
+    #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(endpoint = %self.endpoint, soap_version = ?self.soap_version)))]
+    pub async fn call_with_soap_action<Req, Resp>(
+        &self,
+        operation: &str,
+        soap_action: Option<&str>,
+        namespace: Option<&str>,
+        element_form_qualified: bool,
+        request: &Req,
+    ) -> SoapResult<Resp>
+    where
+        Req: Serialize,
+        Resp: for<'de> Deserialize<'de>,
+    {
+        self.call_with_response_headers(
+            operation,
+            soap_action,
+            namespace,
+            element_form_qualified,
+            request,
+        )
+        .await
+        .map(|response| response.body)
+    }
+
+    /// Call a SOAP operation and keep the header blocks of the response
+    ///
+    /// Like [`call_with_soap_action`](Self::call_with_soap_action), the
+    /// response also holds the children of the response's `<soap:Header>`,
+    /// e.g. a session token to send with later calls.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use serde::{Deserialize, Serialize};
+    /// # use soapus_runtime::{SoapClient, SoapResponse, SoapResult};
+    /// # #[derive(Serialize)]
+    /// # struct Login {}
+    /// # #[derive(Deserialize)]
+    /// # struct LoginResponse {}
+    /// #[derive(Deserialize)]
+    /// struct SessionHeader {
+    ///     #[serde(rename = "SessionId")]
+    ///     session_id: String,
+    /// }
+    ///
+    /// # async fn example(mut client: SoapClient) -> SoapResult<()> {
+    /// let response: SoapResponse<LoginResponse> = client
+    ///     .call_with_response_headers("Login", None, Some("urn:sessions"), true, &Login {})
+    ///     .await?;
+    /// if let Some(session) = response.header::<SessionHeader>("SessionHeader") {
+    ///     println!("Session {}", session?.session_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(endpoint = %self.endpoint, soap_version = ?self.soap_version)))]
+    pub async fn call_with_response_headers<Req, Resp>(
+        &self,
+        operation: &str,
+        soap_action: Option<&str>,
+        namespace: Option<&str>,
+        element_form_qualified: bool,
+        request: &Req,
+    ) -> SoapResult<SoapResponse<Resp>>
+    where
+        Req: Serialize,
+        Resp: for<'de> Deserialize<'de>,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(operation = %operation, soap_action = ?soap_action, namespace = ?namespace, element_form_qualified = %element_form_qualified, "Building SOAP envelope");
+
+        // Build SOAP envelope with namespace if provided
+        let envelope = SoapEnvelope::build_with_namespace(
+            request,
+            self.soap_version,
+            namespace,
+            element_form_qualified,
+        )?;
+
+        #[cfg(feature = "tracing")]
+        debug!(envelope_size = envelope.len(), "SOAP envelope built");
+
+        let response_text = self.send(operation, soap_action, envelope).await?;
+
+        // Parse response
+        #[cfg(feature = "tracing")]
+        debug!("Parsing SOAP response");
+
+        let parsed_response = SoapEnvelope::parse_response(&response_text)?;
+        let headers = SoapEnvelope::parse_response_headers(&response_text)?;
+
+        #[cfg(feature = "tracing")]
+        debug!(
+            header_count = headers.len(),
+            "SOAP response parsed successfully"
+        );
+
+        Ok(SoapResponse {
+            body: parsed_response,
+            headers,
+        })
+    }
+
+    /// Call an RPC/encoded operation
+    ///
+    /// The request is serialized as element of `namespace` with the SOAP
+    /// encoding as `encodingStyle`, its fields are expected to carry their
+    /// own `xsi:type` annotations (see [`encoding`](crate::encoding)). Values
+    /// of the response referenced with `href` are resolved before it is
+    /// deserialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - The SOAP operation name
+    /// * `soap_action` - The SOAPAction header value (if None, uses operation name)
+    /// * `namespace` - The namespace of the operation element (`<soap:body namespace="...">`)
+    /// * `request` - The request body to send
+    #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(endpoint = %self.endpoint, soap_version = ?self.soap_version)))]
+    pub async fn call_encoded<Req, Resp>(
+        &self,
+        operation: &str,
+        soap_action: Option<&str>,
+        namespace: &str,
+        request: &Req,
+    ) -> SoapResult<Resp>
+    where
+        Req: Serialize,
+        Resp: for<'de> Deserialize<'de>,
+    {
+        let envelope = SoapEnvelope::build_encoded(request, self.soap_version, namespace)?;
+
+        #[cfg(feature = "tracing")]
+        debug!(
+            envelope_size = envelope.len(),
+            "Encoded SOAP envelope built"
+        );
+
+        let response_text = self.send(operation, soap_action, envelope).await?;
+        SoapEnvelope::parse_encoded_response(&response_text)
+    }
+
+    /// Call a document style operation with several body parts
+    ///
+    /// The fields of the request are sent as separate children of the SOAP
+    /// body, each qualified with the namespace at the same position of
+    /// `namespaces`. All children of the response body are deserialized
+    /// together as the fields of the response type.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - The SOAP operation name
+    /// * `soap_action` - The SOAPAction header value (if None, uses operation name)
+    /// * `namespaces` - The namespaces of the part elements, in order
+    /// * `element_form_qualified` - Whether the children of the part elements are qualified
+    /// * `request` - The parts to send
+    #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(endpoint = %self.endpoint, soap_version = ?self.soap_version)))]
+    pub async fn call_parts<Req, Resp>(
+        &self,
+        operation: &str,
+        soap_action: Option<&str>,
+        namespaces: &[&str],
+        element_form_qualified: bool,
+        request: &Req,
+    ) -> SoapResult<Resp>
+    where
+        Req: Serialize,
+        Resp: for<'de> Deserialize<'de>,
+    {
+        let envelope = SoapEnvelope::build_parts(
+            request,
+            self.soap_version,
+            namespaces,
+            element_form_qualified,
+        )?;
+
+        #[cfg(feature = "tracing")]
+        debug!(
+            envelope_size = envelope.len(),
+            "Multi-part SOAP envelope built"
+        );
+
+        let response_text = self.send(operation, soap_action, envelope).await?;
+        SoapEnvelope::parse_parts_response(&response_text)
+    }
+
+    /// Post an envelope and return the response, failing on HTTP errors and SOAP faults
+    async fn send(
+        &self,
+        operation: &str,
+        soap_action: Option<&str>,
+        envelope: String,
+    ) -> SoapResult<String> {
+        let action = self.action(operation, soap_action);
+        #[cfg(feature = "ws-addressing")]
+        let (request_headers, message_id) = self.addressing_headers(action)?;
+        #[cfg(not(feature = "ws-addressing"))]
+        let request_headers = Vec::new();
+
+        // Prepare HTTP request
+        let mut http_request = self
+            .http_client
+            .post(&self.endpoint)
+            .timeout(self.timeout)
+            .body(self.add_headers(envelope, request_headers).await?);
+
+        // Set Content-Type based on SOAP version
+        http_request = match self.soap_version {
+            SoapVersion::Soap11 => http_request.header("Content-Type", "text/xml; charset=utf-8"),
+            SoapVersion::Soap12 => {
+                http_request.header("Content-Type", "application/soap+xml; charset=utf-8")
+            }
+        };
+
+        // Set SOAPAction header for SOAP 1.1
+        if self.soap_version == SoapVersion::Soap11 {
+            http_request = http_request.header("SOAPAction", format!("\"{}\"", action));
+        }
+
+        // Send request
+        #[cfg(feature = "tracing")]
+        info!(endpoint = %self.endpoint, "Sending HTTP POST request");
+
+        let response = match http_request.send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                warn!(endpoint = %self.endpoint, error = %e, "HTTP request failed");
+                return Err(e.into());
+            }
+        };
+
+        // Check HTTP status
+        let status = response.status();
+
+        #[cfg(feature = "tracing")]
+        debug!(status = %status, "Received HTTP response");
+        if !status.is_success() && status != StatusCode::INTERNAL_SERVER_ERROR {
+            // SOAP faults can come with 500 status, so we allow that
+            return Err(SoapError::HttpError(
+                response.error_for_status().unwrap_err(),
+            ));
+        }
+
+        // Get response body
+        let response_text = response.text().await?;
+
+        #[cfg(feature = "tracing")]
+        debug!(
+            response_size = response_text.len(),
+            "Received response body"
+        );
+
+        #[cfg(feature = "ws-security")]
+        let response_text = match &self.ws_security {
+            Some(ws_security) if ws_security.response_decryption().is_some() => {
+                // A fault in plain text is reported as is instead of failing decryption
+                if let Err(e) = SoapEnvelope::check_for_fault(&response_text) {
+                    #[cfg(feature = "tracing")]
+                    warn!(error = %e, "Unencrypted SOAP fault detected in response");
+                    return Err(e);
+                }
+                ws_security.decrypt_response(response_text)?
+            }
+            _ => response_text,
+        };
+
+        #[cfg(feature = "metrics")]
+        metrics::histogram!("soap_response_size_bytes", response_text.len() as f64);
+
+        // Check for SOAP faults
+        if let Err(e) = SoapEnvelope::check_for_fault(&response_text) {
+            #[cfg(feature = "tracing")]
+            warn!(error = %e, "SOAP fault detected in response");
+            return Err(e);
+        }
+
+        #[cfg(feature = "ws-security")]
+        if let Some(ws_security) = &self.ws_security {
+            ws_security.verify_response(&response_text)?;
+        }
+
+        #[cfg(feature = "ws-addressing")]
+        if let (Some(ws_addressing), Some(message_id)) = (&self.ws_addressing, &message_id) {
+            ws_addressing.verify_relates_to(&response_text, message_id)?;
+        }
+
+        Ok(response_text)
+    }
+
+    /// Make a SOAP call without deserializing the response
+    ///
+    /// This is useful for debugging or when you want to handle the raw XML response yourself.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - The SOAP operation name
+    /// * `request` - The request body to send
+    ///
+    /// # Returns
+    ///
+    /// The raw XML response as a string
+    #[cfg_attr(feature = "tracing", instrument(skip(self, request), fields(endpoint = %self.endpoint)))]
+    pub async fn call_raw<Req>(&self, operation: &str, request: &Req) -> SoapResult<String>
+    where
+        Req: Serialize,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(operation = %operation, "Building SOAP envelope for raw call");
+
+        // Build SOAP envelope
+        let envelope = SoapEnvelope::build(request, self.soap_version)?;
+
+        self.send(operation, None, envelope).await
+    }
+}
@@ -0,0 +1,401 @@
+//! WS-Security and WS-Addressing settings and the header blocks of requests
+
+#[cfg(feature = "ws-addressing")]
+use crate::addressing::{self, WsAddressing};
+use crate::envelope::SoapEnvelope;
+use crate::error::SoapResult;
+use crate::header::HeaderBlock;
+#[cfg(feature = "ws-security")]
+use crate::security::{SecurityPolicy, WsSecurity};
+
+use super::SoapClient;
+
+impl SoapClient {
+    /// Set the WS-Security settings
+    ///
+    /// A `wsse:Security` header is built anew for every request, preceding
+    /// the other header blocks.
+    #[cfg(feature = "ws-security")]
+    pub fn set_ws_security(&mut self, ws_security: WsSecurity) {
+        self.ws_security = Some(ws_security);
+    }
+
+    /// Get the WS-Security settings
+    #[cfg(feature = "ws-security")]
+    pub fn ws_security(&self) -> Option<&WsSecurity> {
+        self.ws_security.as_ref()
+    }
+
+    /// Get a copy of the client whose WS-Security settings meet a policy
+    ///
+    /// Generated clients call operations with a WS-SecurityPolicy attached
+    /// in the WSDL this way. See [`WsSecurity::with_policy`] for the settings
+    /// that are added and the ones that must be configured.
+    #[cfg(feature = "ws-security")]
+    pub fn with_security_policy(&self, policy: &SecurityPolicy) -> SoapResult<Self> {
+        let mut client = self.clone();
+        let ws_security = client.ws_security.take().unwrap_or_default();
+        client.ws_security = Some(ws_security.with_policy(policy)?);
+        Ok(client)
+    }
+
+    /// Set the WS-Addressing settings
+    ///
+    /// The `wsa` header blocks are built anew for every request, following
+    /// a `wsse:Security` header and preceding the other header blocks.
+    #[cfg(feature = "ws-addressing")]
+    pub fn set_ws_addressing(&mut self, ws_addressing: WsAddressing) {
+        self.ws_addressing = Some(ws_addressing);
+    }
+
+    /// Get the WS-Addressing settings
+    #[cfg(feature = "ws-addressing")]
+    pub fn ws_addressing(&self) -> Option<&WsAddressing> {
+        self.ws_addressing.as_ref()
+    }
+
+    /// Get a copy of the client sending WS-Addressing headers
+    ///
+    /// Default [`WsAddressing`] settings are used unless the client has
+    /// some. Generated clients call operations of bindings using
+    /// WS-Addressing this way.
+    #[cfg(feature = "ws-addressing")]
+    pub fn with_addressing_required(&self) -> Self {
+        let mut client = self.clone();
+        client
+            .ws_addressing
+            .get_or_insert_with(WsAddressing::default);
+        client
+    }
+
+    /// Get a copy of the client sending `action` as `wsa:Action`
+    ///
+    /// Without, the SOAPAction of a call is sent. Generated clients use the
+    /// `wsam:Action` or `wsaw:Action` of an operation's input in the WSDL.
+    /// The action is only sent with WS-Addressing settings, and then replaces
+    /// the SOAPAction of the call as well.
+    #[cfg(feature = "ws-addressing")]
+    pub fn with_addressing_action(&self, action: impl Into<String>) -> Self {
+        let mut client = self.clone();
+        client.addressing_action = Some(action.into());
+        client
+    }
+
+    /// Get the action of a call, sent as SOAPAction and as `wsa:Action`
+    ///
+    /// With WS-Addressing, the action set by [`Self::with_addressing_action`]
+    /// takes precedence so that both headers carry the same action.
+    pub(super) fn action<'a>(
+        &'a self,
+        operation: &'a str,
+        soap_action: Option<&'a str>,
+    ) -> &'a str {
+        #[cfg(feature = "ws-addressing")]
+        if let (Some(_), Some(action)) = (&self.ws_addressing, &self.addressing_action) {
+            return action;
+        }
+        soap_action
+            .or(self.soap_action.as_deref())
+            .unwrap_or(operation)
+    }
+
+    /// Build the WS-Addressing header blocks of a request, if any
+    ///
+    /// Returns them with the `wsa:MessageID` the response must relate to.
+    #[cfg(feature = "ws-addressing")]
+    pub(super) fn addressing_headers(
+        &self,
+        action: &str,
+    ) -> SoapResult<(Vec<HeaderBlock>, Option<String>)> {
+        let Some(ws_addressing) = &self.ws_addressing else {
+            return Ok((Vec::new(), None));
+        };
+        let message_id = addressing::message_id()?;
+        let headers = ws_addressing.headers(action, &self.endpoint, &message_id);
+        Ok((headers, Some(message_id)))
+    }
+
+    /// Add the header blocks of a request and of this client to an envelope
+    ///
+    /// `request_headers` follow a `wsse:Security` header and precede the
+    /// headers of the client. With WS-Security, the envelope is signed after
+    /// the headers are added.
+    pub(super) async fn add_headers(
+        &self,
+        envelope: String,
+        request_headers: Vec<HeaderBlock>,
+    ) -> SoapResult<String> {
+        let mut headers = request_headers;
+        headers.extend(self.headers.iter().cloned());
+
+        #[cfg(feature = "ws-security")]
+        if let Some(ws_security) = &self.ws_security {
+            headers.insert(0, ws_security.header().await?);
+            let envelope = SoapEnvelope::add_headers(&envelope, self.soap_version, &headers);
+            return ws_security.secure_envelope(envelope, self.soap_version);
+        }
+
+        if headers.is_empty() {
+            Ok(envelope)
+        } else {
+            Ok(SoapEnvelope::add_headers(
+                &envelope,
+                self.soap_version,
+                &headers,
+            ))
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "ws-security", feature = "ws-addressing")))]
+mod tests {
+    use super::*;
+    use crate::envelope::SoapVersion;
+    use serde::Serialize;
+
+    #[cfg(feature = "ws-security")]
+    #[tokio::test]
+    async fn test_ws_security_header() {
+        use crate::security::{PasswordType, UsernameToken};
+
+        let client = SoapClient::builder("http://example.com/soap")
+            .header(HeaderBlock::new("TraceId", "4711").unwrap())
+            .ws_security(WsSecurity::new().with_username_token(UsernameToken::new(
+                "admin",
+                "secret",
+                PasswordType::Text,
+            )))
+            .build();
+
+        #[derive(Serialize)]
+        struct Ping {}
+
+        let envelope = SoapEnvelope::build(&Ping {}, SoapVersion::Soap11).unwrap();
+        let envelope = client.add_headers(envelope, Vec::new()).await.unwrap();
+        let security = envelope.find("<wsse:Security").unwrap();
+        let trace = envelope.find("<TraceId>").unwrap();
+        assert!(security < trace);
+        assert!(envelope.contains("<wsse:Username>admin</wsse:Username>"));
+    }
+
+    #[cfg(feature = "ws-security")]
+    #[tokio::test]
+    async fn test_ws_security_signature() {
+        use crate::security::{verify_signature, X509Credentials};
+        use std::time::Duration;
+
+        let credentials = X509Credentials::from_pem(
+            include_bytes!("../../../testdata/security/client.crt"),
+            include_bytes!("../../../testdata/security/client.key"),
+        )
+        .unwrap();
+        let client = SoapClient::builder("http://example.com/soap")
+            .soap_version(SoapVersion::Soap12)
+            .ws_security(
+                WsSecurity::new()
+                    .with_signature(credentials.clone())
+                    .with_timestamp(Duration::from_secs(300)),
+            )
+            .build();
+
+        #[derive(Serialize)]
+        struct Ping {}
+
+        let envelope = SoapEnvelope::build(&Ping {}, SoapVersion::Soap12).unwrap();
+        let envelope = client.add_headers(envelope, Vec::new()).await.unwrap();
+        assert!(envelope.contains(r##"<ds:Reference URI="#TS-1">"##));
+        verify_signature(&envelope, credentials.certificate()).unwrap();
+    }
+
+    #[cfg(feature = "ws-security")]
+    #[tokio::test]
+    async fn test_ws_security_encryption() {
+        use crate::security::{decrypt, EncryptionAlgorithm, X509Credentials};
+
+        let server = X509Credentials::from_pem(
+            include_bytes!("../../../testdata/security/server.crt"),
+            include_bytes!("../../../testdata/security/server.key"),
+        )
+        .unwrap();
+        let client = SoapClient::builder("http://example.com/soap")
+            .ws_security(
+                WsSecurity::new()
+                    .with_encryption(server.certificate().clone(), EncryptionAlgorithm::Aes256Gcm),
+            )
+            .build();
+
+        #[derive(Serialize)]
+        struct Ping {
+            id: u32,
+        }
+
+        let envelope = SoapEnvelope::build(&Ping { id: 7 }, SoapVersion::Soap11).unwrap();
+        let envelope = client.add_headers(envelope, Vec::new()).await.unwrap();
+        assert!(!envelope.contains("<Ping>"));
+        assert!(envelope.contains("<xenc:EncryptedKey"));
+        let decrypted = decrypt(&envelope, &server).unwrap();
+        assert!(decrypted.contains("<soap:Body><Ping><id>7</id></Ping></soap:Body>"));
+    }
+
+    #[cfg(feature = "ws-security")]
+    #[tokio::test]
+    async fn test_plain_fault_with_response_decryption() {
+        use crate::error::SoapError;
+        use crate::security::X509Credentials;
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        const FAULT: &str = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body><soap:Fault><faultcode>soap:Client</faultcode><faultstring>Unknown account</faultstring><detail><AccountFault xmlns="urn:accounts"><Id>42</Id></AccountFault></detail></soap:Fault></soap:Body></soap:Envelope>"#;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/soap", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            reader.read_exact(&mut vec![0; content_length]).unwrap();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 500 Internal Server Error\r\nContent-Type: text/xml; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                FAULT.len(),
+                FAULT
+            )
+            .unwrap();
+        });
+
+        let credentials = X509Credentials::from_pem(
+            include_bytes!("../../../testdata/security/client.crt"),
+            include_bytes!("../../../testdata/security/client.key"),
+        )
+        .unwrap();
+        let client = SoapClient::builder(endpoint)
+            .ws_security(WsSecurity::new().with_response_decryption(credentials))
+            .build();
+
+        #[derive(Serialize)]
+        struct Ping {}
+
+        // The fault keeps its detail instead of failing as an unencrypted Body
+        match client.call_raw("Ping", &Ping {}).await {
+            Err(SoapError::SoapFault(fault)) => {
+                assert_eq!(fault.message(), "Unknown account");
+                assert!(fault.detail.unwrap().contains("<Id>42</Id>"));
+            }
+            other => panic!("expected a SOAP fault, got {:?}", other),
+        }
+        server.join().unwrap();
+    }
+
+    #[cfg(feature = "ws-security")]
+    #[tokio::test]
+    async fn test_saml_assertion() {
+        use crate::security::{verify_signature, SamlAssertion, X509Credentials};
+
+        let credentials = X509Credentials::from_pem(
+            include_bytes!("../../../testdata/security/client.crt"),
+            include_bytes!("../../../testdata/security/client.key"),
+        )
+        .unwrap();
+        let assertion = SamlAssertion::new(include_str!(
+            "../../../testdata/security/saml-assertion.xml"
+        ))
+        .unwrap();
+        // The assertion applies regardless of the order of the settings
+        let client = SoapClient::builder("http://example.com/soap")
+            .saml_assertion(assertion.clone())
+            .ws_security(WsSecurity::new().with_signature(credentials.clone()))
+            .build();
+
+        #[derive(Serialize)]
+        struct Ping {}
+
+        let envelope = SoapEnvelope::build(&Ping {}, SoapVersion::Soap11).unwrap();
+        let envelope = client.add_headers(envelope, Vec::new()).await.unwrap();
+        assert!(envelope.contains(assertion.xml()));
+
+        // The request is signed apart from the signature of the assertion
+        let signed_request = envelope.replacen(assertion.xml(), "", 1);
+        verify_signature(&signed_request, credentials.certificate()).unwrap();
+    }
+
+    #[cfg(feature = "ws-addressing")]
+    #[tokio::test]
+    async fn test_ws_addressing_headers() {
+        let client = SoapClient::builder("http://example.com/soap")
+            .header(HeaderBlock::new("TraceId", "4711").unwrap())
+            .build();
+        let (headers, message_id) = client.addressing_headers("urn:ping").unwrap();
+        assert!(headers.is_empty() && message_id.is_none());
+
+        // The action is only used with WS-Addressing settings
+        let with_action = client.with_addressing_action("http://tempuri.org/IService/Ping");
+        assert_eq!(with_action.action("Ping", Some("urn:ping")), "urn:ping");
+
+        // Generated clients require WS-Addressing and set the action of the WSDL,
+        // which is sent as SOAPAction as well
+        let client = with_action.with_addressing_required();
+        assert_eq!(client.ws_addressing(), Some(&WsAddressing::default()));
+        let action = client.action("Ping", Some("urn:ping"));
+        assert_eq!(action, "http://tempuri.org/IService/Ping");
+        let (headers, message_id) = client.addressing_headers(action).unwrap();
+        let message_id = message_id.unwrap();
+
+        #[derive(Serialize)]
+        struct Ping {}
+
+        let envelope = SoapEnvelope::build(&Ping {}, SoapVersion::Soap11).unwrap();
+        let envelope = client.add_headers(envelope, headers).await.unwrap();
+        assert!(envelope.contains(">http://tempuri.org/IService/Ping</wsa:Action>"));
+        assert!(envelope.contains(&format!(">{}</wsa:MessageID>", message_id)));
+        assert!(envelope.contains(">http://example.com/soap</wsa:To>"));
+        assert!(envelope.find("<wsa:To").unwrap() < envelope.find("<TraceId>").unwrap());
+    }
+
+    #[cfg(feature = "ws-security")]
+    #[test]
+    fn test_with_security_policy() {
+        use crate::error::SoapError;
+        use crate::security::{PasswordType, UsernameToken, DEFAULT_TIMESTAMP_TTL};
+
+        let client = SoapClient::new("http://example.com/soap");
+        let policy = SecurityPolicy {
+            timestamp: true,
+            ..Default::default()
+        };
+        let secured = client.with_security_policy(&policy).unwrap();
+        assert_eq!(
+            secured.ws_security().unwrap().timestamp_ttl(),
+            Some(DEFAULT_TIMESTAMP_TTL)
+        );
+        assert!(client.ws_security().is_none());
+
+        let policy = SecurityPolicy {
+            username_token: true,
+            ..policy
+        };
+        assert!(matches!(
+            client.with_security_policy(&policy),
+            Err(SoapError::SecurityError(_))
+        ));
+        let client = SoapClient::builder("http://example.com/soap")
+            .ws_security(WsSecurity::new().with_username_token(UsernameToken::new(
+                "admin",
+                "secret",
+                PasswordType::Text,
+            )))
+            .build();
+        assert!(client.with_security_policy(&policy).is_ok());
+    }
+}
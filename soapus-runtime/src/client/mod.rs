@@ -0,0 +1,303 @@
+//! SOAP HTTP client implementation
+//!
+//! This module provides the main `SoapClient` for making SOAP requests over HTTP.
+//! It handles envelope construction, HTTP communication, and response parsing.
+
+mod builder;
+mod call;
+mod headers;
+
+pub use builder::SoapClientBuilder;
+
+#[cfg(feature = "ws-addressing")]
+use crate::addressing::WsAddressing;
+use crate::envelope::SoapVersion;
+use crate::error::SoapResult;
+use crate::header::HeaderBlock;
+#[cfg(feature = "ws-security")]
+use crate::security::WsSecurity;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// SOAP client for making HTTP requests
+///
+/// This client handles the complete SOAP request/response cycle:
+/// 1. Serialize request body to XML
+/// 2. Wrap in SOAP envelope
+/// 3. Send HTTP POST request
+/// 4. Parse response envelope
+/// 5. Check for SOAP faults
+/// 6. Deserialize response body
+///
+/// # Example
+///
+/// ```no_run
+/// use soapus_runtime::SoapClient;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize)]
+/// struct MyRequest {
+///     name: String,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct MyResponse {
+///     result: String,
+/// }
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = SoapClient::new("http://example.com/soap");
+/// let request = MyRequest { name: "test".to_string() };
+/// let response: MyResponse = client.call("MyOperation", &request).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SoapClient {
+    /// The SOAP endpoint URL
+    endpoint: String,
+    /// HTTP client for making requests
+    http_client: Client,
+    /// SOAP protocol version to use
+    soap_version: SoapVersion,
+    /// SOAPAction header value (optional)
+    soap_action: Option<String>,
+    /// Request timeout
+    timeout: Duration,
+    /// Header blocks sent with every request
+    headers: Vec<HeaderBlock>,
+    /// WS-Security settings, adding a `wsse:Security` header to every request
+    #[cfg(feature = "ws-security")]
+    ws_security: Option<WsSecurity>,
+    /// WS-Addressing settings, adding `wsa` headers to every request
+    #[cfg(feature = "ws-addressing")]
+    ws_addressing: Option<WsAddressing>,
+    /// `wsa:Action` sent instead of the SOAPAction
+    #[cfg(feature = "ws-addressing")]
+    addressing_action: Option<String>,
+}
+
+impl SoapClient {
+    /// Create a new SOAP client with default settings
+    ///
+    /// Uses SOAP 1.1 by default with a 30-second timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The SOAP service endpoint URL
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            http_client: Client::new(),
+            soap_version: SoapVersion::Soap11,
+            soap_action: None,
+            timeout: Duration::from_secs(30),
+            headers: Vec::new(),
+            #[cfg(feature = "ws-security")]
+            ws_security: None,
+            #[cfg(feature = "ws-addressing")]
+            ws_addressing: None,
+            #[cfg(feature = "ws-addressing")]
+            addressing_action: None,
+        }
+    }
+
+    /// Create a new SOAP client builder for advanced configuration
+    pub fn builder(endpoint: impl Into<String>) -> SoapClientBuilder {
+        SoapClientBuilder::new(endpoint)
+    }
+
+    /// Get the endpoint URL
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Set the SOAP version to use
+    pub fn set_soap_version(&mut self, version: SoapVersion) {
+        self.soap_version = version;
+    }
+
+    /// Get the current SOAP version
+    pub fn soap_version(&self) -> SoapVersion {
+        self.soap_version
+    }
+
+    /// Set the SOAPAction header value
+    ///
+    /// This is required for SOAP 1.1 operations. For SOAP 1.2, it's optional.
+    pub fn set_soap_action(&mut self, action: impl Into<String>) {
+        self.soap_action = Some(action.into());
+    }
+
+    /// Set the request timeout
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Add a header block sent with every request
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use soapus_runtime::{HeaderBlock, SoapClient};
+    ///
+    /// #[derive(Serialize)]
+    /// struct ApiKey {
+    ///     key: String,
+    /// }
+    ///
+    /// let mut client = SoapClient::new("http://example.com/soap");
+    /// let header = HeaderBlock::from_value(&ApiKey { key: "secret".to_string() })
+    ///     .unwrap()
+    ///     .with_must_understand(true);
+    /// client.add_header(header);
+    /// ```
+    pub fn add_header(&mut self, header: HeaderBlock) {
+        self.headers.push(header);
+    }
+
+    /// Remove all header blocks sent with every request
+    pub fn clear_headers(&mut self) {
+        self.headers.clear();
+    }
+
+    /// Get a copy of the client sending additional header blocks
+    ///
+    /// This is how headers are sent with a single call, the blocks follow
+    /// the ones of this client. Generated clients use this to send the
+    /// headers declared in the WSDL binding of an operation.
+    ///
+    /// ```no_run
+    /// # use soapus_runtime::{HeaderBlock, SoapClient, SoapResult};
+    /// # #[derive(serde::Serialize)]
+    /// # struct Ping {}
+    /// # async fn example(client: SoapClient) -> SoapResult<()> {
+    /// let header = HeaderBlock::new("TraceId", "4711")?;
+    /// let response: String = client.with_headers([header]).call("Ping", &Ping {}).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_headers(&self, headers: impl IntoIterator<Item = HeaderBlock>) -> Self {
+        let mut client = self.clone();
+        client.headers.extend(headers);
+        client
+    }
+
+    /// Get the header blocks sent with every request
+    pub fn headers(&self) -> &[HeaderBlock] {
+        &self.headers
+    }
+}
+
+/// The deserialized body of a SOAP response together with its header blocks
+#[derive(Debug, Clone)]
+pub struct SoapResponse<T> {
+    /// The deserialized body
+    pub body: T,
+    /// The children of the response's `<soap:Header>`, in document order
+    pub headers: Vec<HeaderBlock>,
+}
+
+impl<T> SoapResponse<T> {
+    /// Deserialize the first header block with the given local name
+    ///
+    /// Returns `None` if the response has no such header block.
+    pub fn header<H>(&self, name: &str) -> Option<SoapResult<H>>
+    where
+        H: for<'de> Deserialize<'de>,
+    {
+        self.headers
+            .iter()
+            .find(|header| header.name() == name)
+            .map(HeaderBlock::deserialize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_creation() {
+        let client = SoapClient::new("http://example.com/soap");
+        assert_eq!(client.endpoint(), "http://example.com/soap");
+        assert_eq!(client.soap_version(), SoapVersion::Soap11);
+    }
+
+    #[test]
+    fn test_set_soap_version() {
+        let mut client = SoapClient::new("http://example.com/soap");
+        assert_eq!(client.soap_version(), SoapVersion::Soap11);
+
+        client.set_soap_version(SoapVersion::Soap12);
+        assert_eq!(client.soap_version(), SoapVersion::Soap12);
+    }
+
+    #[test]
+    fn test_set_soap_action() {
+        let mut client = SoapClient::new("http://example.com/soap");
+        client.set_soap_action("http://example.com/MyAction");
+        assert_eq!(
+            client.soap_action,
+            Some("http://example.com/MyAction".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_timeout() {
+        let mut client = SoapClient::new("http://example.com/soap");
+        client.set_timeout(Duration::from_secs(120));
+        assert_eq!(client.timeout, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_with_headers() {
+        let client = SoapClient::new("http://example.com/soap");
+        let header = HeaderBlock::new("SessionId", "abc").unwrap();
+
+        let with_headers = client.with_headers([header.clone()]);
+        assert_eq!(with_headers.headers(), &[header]);
+        assert!(client.headers().is_empty());
+    }
+
+    #[test]
+    fn test_client_wide_headers() {
+        let first = HeaderBlock::new("ApiKey", "secret").unwrap();
+        let second = HeaderBlock::new("TraceId", "4711").unwrap();
+
+        let mut client = SoapClient::builder("http://example.com/soap")
+            .header(first.clone())
+            .build();
+        client.add_header(second.clone());
+        assert_eq!(client.headers(), &[first.clone(), second.clone()]);
+
+        // Headers of a single call follow the client-wide ones
+        let third = HeaderBlock::new("Locale", "de").unwrap();
+        let with_headers = client.with_headers([third.clone()]);
+        assert_eq!(with_headers.headers(), &[first, second, third]);
+
+        client.clear_headers();
+        assert!(client.headers().is_empty());
+    }
+
+    #[test]
+    fn test_response_header() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct SessionHeader {
+            #[serde(rename = "SessionId")]
+            session_id: String,
+        }
+
+        let response = SoapResponse {
+            body: (),
+            headers: vec![HeaderBlock::from_xml(
+                r#"<s:SessionHeader xmlns:s="urn:s"><s:SessionId>42</s:SessionId></s:SessionHeader>"#,
+            )],
+        };
+        let session: SessionHeader = response.header("SessionHeader").unwrap().unwrap();
+        assert_eq!(session.session_id, "42");
+        assert!(response.header::<SessionHeader>("Other").is_none());
+    }
+}
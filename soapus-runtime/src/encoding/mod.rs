@@ -0,0 +1,330 @@
+//! SOAP encoding for RPC/encoded operations
+//!
+//! Operations bound with `<soap:body use="encoded"/>` follow the encoding rules
+//! of SOAP 1.1 section 5 instead of a schema: every value names its type in an
+//! `xsi:type` attribute, and arrays are `soapenc:Array`s declaring their item
+//! type and length:
+//!
+//! ```xml
+//! <ns:getQuotes xmlns:ns="urn:quotes"
+//!     soap:encodingStyle="http://schemas.xmlsoap.org/soap/encoding/">
+//!   <symbols xsi:type="soapenc:Array" soapenc:arrayType="xsd:string[2]">
+//!     <item xsi:type="xsd:string">IBM</item>
+//!     <item xsi:type="xsd:string">SAP</item>
+//!   </symbols>
+//!   <limit xsi:type="xsd:int">10</limit>
+//! </ns:getQuotes>
+//! ```
+//!
+//! Generated code serializes fields with [`serialize_typed`] and
+//! [`serialize_array`], relying on [`SoapEnvelope::build_encoded`] to declare
+//! the `xsi`, `xsd` and `soapenc` prefixes. Responses may serialize a value
+//! once and refer to it with `href="#id"` wherever it is used;
+//! [`resolve_multi_refs`] copies such values into place before the response
+//! is deserialized, arrays are read with [`deserialize_array`].
+//!
+//! [`SoapEnvelope::build_encoded`]: crate::SoapEnvelope::build_encoded
+
+mod multi_ref;
+mod typed;
+
+pub use multi_ref::resolve_multi_refs;
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde::{Deserialize, Deserializer};
+use typed::{Typed, TypedSerializer, XsiType};
+
+/// SOAP 1.1 encoding namespace, the value of `encodingStyle`
+pub const SOAP_ENCODING_NAMESPACE: &str = "http://schemas.xmlsoap.org/soap/encoding/";
+
+/// XML Schema namespace, whose types are written with the `xsd` prefix
+pub const XSD_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+
+/// Key of the declaration of the prefix used for types of other namespaces
+const TYPE_NAMESPACE_KEY: &str = "@xmlns:tns";
+
+/// Key of the `xsi:type` attribute
+const TYPE_KEY: &str = "@xsi:type";
+
+/// Key of the `xsi:nil` attribute marking absent values
+const NIL_KEY: &str = "@xsi:nil";
+
+/// Key of the text content of an element
+const TEXT_KEY: &str = "$text";
+
+/// Name of the elements holding the items of an array
+const ITEM_ELEMENT: &str = "item";
+
+/// Serialize a field value with an `xsi:type` attribute naming its type
+///
+/// `namespace` and `type_name` identify the XSD type of the value. Works for
+/// structs, simple values and enumerations, as well as `Option`s and `Vec`s
+/// of them, where each element gets the attribute. `None` is written as
+/// element with `xsi:nil="true"`.
+///
+/// Intended for `#[serde(serialize_with = "...")]` through a function of the
+/// generated struct that supplies the type.
+pub fn serialize_typed<T, S>(
+    value: &T,
+    namespace: &str,
+    type_name: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + ?Sized,
+    S: Serializer,
+{
+    value.serialize(TypedSerializer {
+        inner: serializer,
+        type_: XsiType::new(namespace, type_name),
+    })
+}
+
+/// Serialize items as a `soapenc:Array` of `namespace`:`type_name` values
+///
+/// The items become `<item>` elements, each with its `xsi:type`.
+pub fn serialize_array<T, S>(
+    items: &[T],
+    namespace: &str,
+    type_name: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    let type_ = XsiType::new(namespace, type_name);
+    let mut element = serializer.serialize_struct("", items.len() + 3)?;
+    if let Some(namespace) = type_.declaration {
+        element.serialize_field(TYPE_NAMESPACE_KEY, namespace)?;
+    }
+    element.serialize_field(TYPE_KEY, "soapenc:Array")?;
+    element.serialize_field(
+        "@soapenc:arrayType",
+        &format!("{}[{}]", type_.name, items.len()),
+    )?;
+    for value in items {
+        element.serialize_field(
+            ITEM_ELEMENT,
+            &Typed {
+                value,
+                type_: &type_,
+            },
+        )?;
+    }
+    element.end()
+}
+
+/// Serialize optional items as a `soapenc:Array`, see [`serialize_array`]
+pub fn serialize_optional_array<T, S>(
+    items: &Option<Vec<T>>,
+    namespace: &str,
+    type_name: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    match items {
+        Some(items) => serialize_array(items, namespace, type_name, serializer),
+        None => serialize_nil(serializer),
+    }
+}
+
+/// Write an absent value as element with `xsi:nil="true"`
+fn serialize_nil<S: Serializer>(serializer: S) -> Result<S::Ok, S::Error> {
+    let mut element = serializer.serialize_struct("", 1)?;
+    element.serialize_field(NIL_KEY, "true")?;
+    element.end()
+}
+
+/// Deserialize the items of a `soapenc:Array`, whatever their element names
+///
+/// Intended for `#[serde(deserialize_with = "...")]`.
+pub fn deserialize_array<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Array::deserialize(deserializer).map(|array| array.items)
+}
+
+/// Deserialize an optional `soapenc:Array`, see [`deserialize_array`]
+///
+/// An element with `xsi:nil="true"` is read as `None`.
+pub fn deserialize_optional_array<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<Array<T>>::deserialize(deserializer).map(|array| {
+        array
+            .filter(|array| array.nil.as_deref() != Some("true"))
+            .map(|array| array.items)
+    })
+}
+
+/// Element content of a `soapenc:Array`
+#[derive(Deserialize)]
+struct Array<T> {
+    /// `xsi:nil`, quick-xml drops the prefix of attribute names
+    #[serde(rename = "@nil", default)]
+    nil: Option<String>,
+    #[serde(rename = "$value", default = "Vec::new")]
+    items: Vec<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Quote {
+        #[serde(serialize_with = "Quote::serialize_symbol")]
+        symbol: String,
+        #[serde(serialize_with = "Quote::serialize_price")]
+        price: f64,
+    }
+
+    impl Quote {
+        fn serialize_symbol<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: Serialize + ?Sized,
+            S: Serializer,
+        {
+            serialize_typed(value, XSD_NAMESPACE, "string", serializer)
+        }
+
+        fn serialize_price<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: Serialize + ?Sized,
+            S: Serializer,
+        {
+            serialize_typed(value, XSD_NAMESPACE, "double", serializer)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename = "getQuotes")]
+    struct GetQuotes {
+        #[serde(
+            serialize_with = "GetQuotes::serialize_symbols",
+            deserialize_with = "deserialize_array"
+        )]
+        symbols: Vec<String>,
+        #[serde(serialize_with = "GetQuotes::serialize_last")]
+        last: Quote,
+        #[serde(
+            serialize_with = "GetQuotes::serialize_history",
+            deserialize_with = "deserialize_optional_array",
+            default
+        )]
+        history: Option<Vec<Quote>>,
+    }
+
+    impl GetQuotes {
+        fn serialize_symbols<S: Serializer>(
+            value: &[String],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize_array(value, XSD_NAMESPACE, "string", serializer)
+        }
+
+        fn serialize_last<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            T: Serialize + ?Sized,
+            S: Serializer,
+        {
+            serialize_typed(value, "urn:quotes", "Quote", serializer)
+        }
+
+        fn serialize_history<S: Serializer>(
+            value: &Option<Vec<Quote>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize_optional_array(value, "urn:quotes", "Quote", serializer)
+        }
+    }
+
+    fn quote(symbol: &str, price: f64) -> Quote {
+        Quote {
+            symbol: symbol.to_string(),
+            price,
+        }
+    }
+
+    #[test]
+    fn test_serialize_encoded() {
+        let request = GetQuotes {
+            symbols: vec!["IBM".to_string(), "SAP".to_string()],
+            last: quote("IBM", 1.5),
+            history: Some(vec![quote("SAP", 2.0)]),
+        };
+
+        let xml = quick_xml::se::to_string(&request).unwrap();
+        assert_eq!(
+            xml,
+            "<getQuotes>\
+             <symbols xsi:type=\"soapenc:Array\" soapenc:arrayType=\"xsd:string[2]\">\
+             <item xsi:type=\"xsd:string\">IBM</item><item xsi:type=\"xsd:string\">SAP</item>\
+             </symbols>\
+             <last xmlns:tns=\"urn:quotes\" xsi:type=\"tns:Quote\">\
+             <symbol xsi:type=\"xsd:string\">IBM</symbol><price xsi:type=\"xsd:double\">1.5</price>\
+             </last>\
+             <history xmlns:tns=\"urn:quotes\" xsi:type=\"soapenc:Array\" soapenc:arrayType=\"tns:Quote[1]\">\
+             <item xmlns:tns=\"urn:quotes\" xsi:type=\"tns:Quote\">\
+             <symbol xsi:type=\"xsd:string\">SAP</symbol><price xsi:type=\"xsd:double\">2</price>\
+             </item></history>\
+             </getQuotes>"
+        );
+
+        // The annotations are ignored when reading the values back
+        let parsed: GetQuotes = quick_xml::de::from_str(&xml).unwrap();
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn test_serialize_nil() {
+        let request = GetQuotes {
+            symbols: Vec::new(),
+            last: quote("IBM", 1.5),
+            history: None,
+        };
+
+        let xml = quick_xml::se::to_string(&request).unwrap();
+        assert!(xml
+            .contains("<symbols xsi:type=\"soapenc:Array\" soapenc:arrayType=\"xsd:string[0]\"/>"));
+        assert!(xml.ends_with("<history xsi:nil=\"true\"/></getQuotes>"));
+
+        let parsed: GetQuotes = quick_xml::de::from_str(&xml).unwrap();
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn test_deserialize_empty_and_missing_arrays() {
+        let parsed: GetQuotes = quick_xml::de::from_str(
+            "<getQuotes><symbols soapenc:arrayType=\"xsd:string[0]\"/>\
+             <last><symbol>IBM</symbol><price>1</price></last></getQuotes>",
+        )
+        .unwrap();
+        assert!(parsed.symbols.is_empty());
+        assert_eq!(parsed.history, None);
+    }
+
+    #[test]
+    fn test_resolve_multi_refs() {
+        let body = r##"<ns1:getQuotesResponse xmlns:ns1="urn:quotes"><symbols href="#id0"/><last href="#id1"/></ns1:getQuotesResponse><multiRef id="id0" soapenc:root="0" xsi:type="soapenc:Array"><item href="#id2"/><item>SAP</item></multiRef><multiRef id="id1" soapenc:root="0" xsi:type="ns2:Quote"><symbol href="#id2"/><price>1.5</price></multiRef><multiRef id="id2" soapenc:root="0" xsi:type="xsd:string">IBM &amp; Co</multiRef>"##;
+
+        let resolved = resolve_multi_refs(body).unwrap();
+        assert_eq!(
+            resolved,
+            r#"<ns1:getQuotesResponse xmlns:ns1="urn:quotes"><symbols xsi:type="soapenc:Array"><item xsi:type="xsd:string">IBM &amp; Co</item><item>SAP</item></symbols><last xsi:type="ns2:Quote"><symbol xsi:type="xsd:string">IBM &amp; Co</symbol><price>1.5</price></last></ns1:getQuotesResponse>"#
+        );
+
+        let parsed: GetQuotes = quick_xml::de::from_str(&resolved).unwrap();
+        assert_eq!(parsed.symbols, ["IBM & Co", "SAP"]);
+        assert_eq!(parsed.last, quote("IBM & Co", 1.5));
+    }
+}
@@ -0,0 +1,248 @@
+//! Resolution of `href` references to multi-ref values in encoded responses
+
+use crate::error::{SoapError, SoapResult};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+
+/// Copy the values referenced with `href="#id"` into the referring elements
+///
+/// Encoded responses may serialize values as independent elements following
+/// the response element, each with an `id`, and refer to them from where
+/// they are used:
+///
+/// ```xml
+/// <ns:getQuoteResponse>
+///   <return href="#id0"/>
+/// </ns:getQuoteResponse>
+/// <multiRef id="id0" xsi:type="ns:Quote">
+///   <symbol xsi:type="xsd:string">IBM</symbol>
+/// </multiRef>
+/// ```
+///
+/// A referring element gets the attributes and content of the referenced
+/// one, and top-level elements with an `id` are removed, leaving XML the
+/// deserializer can read like an inline value. `xml` is the content of the
+/// SOAP body.
+///
+/// # Errors
+///
+/// Returns [`SoapError::InvalidResponse`] for references to unknown ids and
+/// for values referring to themselves.
+pub fn resolve_multi_refs(xml: &str) -> SoapResult<String> {
+    let roots = parse_nodes(xml)?;
+
+    let mut ids = HashMap::new();
+    collect_ids(&roots, &mut ids);
+
+    let mut output = String::with_capacity(xml.len());
+    let mut visiting = Vec::new();
+    for node in &roots {
+        if let Node::Element(element) = node {
+            if element.attribute("id").is_some() {
+                continue;
+            }
+        }
+        write_node(node, &ids, &mut visiting, &mut output)?;
+    }
+    Ok(output)
+}
+
+/// A node of the body content, with text and attribute values kept escaped
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+impl Element {
+    fn new(start: &BytesStart) -> SoapResult<Self> {
+        let attributes = start
+            .attributes()
+            .map(|attribute| {
+                let attribute = attribute.map_err(|e| SoapError::XmlError(e.to_string()))?;
+                Ok((
+                    String::from_utf8_lossy(attribute.key.as_ref()).into_owned(),
+                    String::from_utf8_lossy(&attribute.value).into_owned(),
+                ))
+            })
+            .collect::<SoapResult<_>>()?;
+        Ok(Self {
+            name: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+            attributes,
+            children: Vec::new(),
+        })
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parse XML into a tree, the content of the SOAP body may have several roots
+fn parse_nodes(xml: &str) -> SoapResult<Vec<Node>> {
+    let mut reader = Reader::from_str(xml);
+    let mut roots = Vec::new();
+    let mut open: Vec<Element> = Vec::new();
+
+    fn add(node: Node, open: &mut [Element], roots: &mut Vec<Node>) {
+        match open.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => roots.push(node),
+        }
+    }
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => open.push(Element::new(&e)?),
+            Event::Empty(e) => add(Node::Element(Element::new(&e)?), &mut open, &mut roots),
+            Event::End(_) => {
+                let element = open.pop().ok_or_else(|| {
+                    SoapError::XmlError("unexpected end tag in SOAP body".to_string())
+                })?;
+                add(Node::Element(element), &mut open, &mut roots);
+            }
+            Event::Text(e) => add(
+                Node::Text(String::from_utf8_lossy(&e).into_owned()),
+                &mut open,
+                &mut roots,
+            ),
+            Event::CData(e) => add(
+                Node::Text(format!("<![CDATA[{}]]>", String::from_utf8_lossy(&e))),
+                &mut open,
+                &mut roots,
+            ),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(roots)
+}
+
+/// Index the elements carrying an `id` attribute, at any depth
+fn collect_ids<'a>(nodes: &'a [Node], ids: &mut HashMap<&'a str, &'a Element>) {
+    for node in nodes {
+        if let Node::Element(element) = node {
+            if let Some(id) = element.attribute("id") {
+                ids.insert(id, element);
+            }
+            collect_ids(&element.children, ids);
+        }
+    }
+}
+
+/// Write a node, replacing references by the referenced values
+fn write_node<'a>(
+    node: &'a Node,
+    ids: &HashMap<&'a str, &'a Element>,
+    visiting: &mut Vec<&'a str>,
+    output: &mut String,
+) -> SoapResult<()> {
+    let element = match node {
+        Node::Text(text) => {
+            output.push_str(text);
+            return Ok(());
+        }
+        Node::Element(element) => element,
+    };
+
+    let Some(id) = element
+        .attribute("href")
+        .and_then(|href| href.strip_prefix('#'))
+    else {
+        return write_element(
+            element,
+            &element.attributes,
+            &element.children,
+            ids,
+            visiting,
+            output,
+        );
+    };
+
+    let target = ids
+        .get(id)
+        .ok_or_else(|| SoapError::InvalidResponse(format!("unresolved multi-reference #{}", id)))?;
+    if visiting.contains(&id) {
+        return Err(SoapError::InvalidResponse(format!(
+            "cyclic multi-reference #{}",
+            id
+        )));
+    }
+
+    // The referring element keeps its own attributes, the referenced one adds its type
+    let mut attributes: Vec<(String, String)> = element
+        .attributes
+        .iter()
+        .filter(|(key, _)| key != "href")
+        .cloned()
+        .collect();
+    for (key, value) in &target.attributes {
+        let is_identity = key == "id" || key.rsplit(':').next() == Some("root");
+        if !is_identity && attributes.iter().all(|(existing, _)| existing != key) {
+            attributes.push((key.clone(), value.clone()));
+        }
+    }
+
+    visiting.push(id);
+    write_element(
+        element,
+        &attributes,
+        &target.children,
+        ids,
+        visiting,
+        output,
+    )?;
+    visiting.pop();
+    Ok(())
+}
+
+fn write_element<'a>(
+    element: &Element,
+    attributes: &[(String, String)],
+    children: &'a [Node],
+    ids: &HashMap<&'a str, &'a Element>,
+    visiting: &mut Vec<&'a str>,
+    output: &mut String,
+) -> SoapResult<()> {
+    output.push('<');
+    output.push_str(&element.name);
+    for (key, value) in attributes {
+        output.push_str(&format!(" {}=\"{}\"", key, value.replace('"', "&quot;")));
+    }
+    if children.is_empty() {
+        output.push_str("/>");
+        return Ok(());
+    }
+    output.push('>');
+    for child in children {
+        write_node(child, ids, visiting, output)?;
+    }
+    output.push_str(&format!("</{}>", element.name));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_multi_refs_errors() {
+        assert!(matches!(
+            resolve_multi_refs(r##"<r><a href="#missing"/></r>"##),
+            Err(SoapError::InvalidResponse(_))
+        ));
+        assert!(matches!(
+            resolve_multi_refs(r##"<r><a href="#id0"/></r><b id="id0"><c href="#id0"/></b>"##),
+            Err(SoapError::InvalidResponse(_))
+        ));
+    }
+}
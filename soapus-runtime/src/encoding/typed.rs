@@ -0,0 +1,252 @@
+//! Serializer adding `xsi:type` attributes to the elements of encoded values
+
+use super::{
+    serialize_nil, SOAP_ENCODING_NAMESPACE, TEXT_KEY, TYPE_KEY, TYPE_NAMESPACE_KEY, XSD_NAMESPACE,
+};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, SerializeStruct, Serializer};
+
+/// Type name of an `xsi:type` attribute, with the namespace it has to declare
+#[derive(Clone)]
+pub(super) struct XsiType<'a> {
+    pub(super) name: String,
+    pub(super) declaration: Option<&'a str>,
+}
+
+impl<'a> XsiType<'a> {
+    /// Types of XML Schema and of the SOAP encoding use the prefixes of the
+    /// envelope, those of other namespaces are declared with the element.
+    pub(super) fn new(namespace: &'a str, type_name: &str) -> Self {
+        match namespace {
+            XSD_NAMESPACE => Self {
+                name: format!("xsd:{}", type_name),
+                declaration: None,
+            },
+            SOAP_ENCODING_NAMESPACE => Self {
+                name: format!("soapenc:{}", type_name),
+                declaration: None,
+            },
+            "" => Self {
+                name: type_name.to_string(),
+                declaration: None,
+            },
+            _ => Self {
+                name: format!("tns:{}", type_name),
+                declaration: Some(namespace),
+            },
+        }
+    }
+
+    /// Number of attributes written for the type
+    fn len(&self) -> usize {
+        1 + usize::from(self.declaration.is_some())
+    }
+
+    fn write<S: SerializeStruct>(&self, element: &mut S) -> Result<(), S::Error> {
+        if let Some(namespace) = self.declaration {
+            element.serialize_field(TYPE_NAMESPACE_KEY, namespace)?;
+        }
+        element.serialize_field(TYPE_KEY, &self.name)
+    }
+}
+
+/// A value serialized with [`serialize_typed`](super::serialize_typed)
+pub(super) struct Typed<'a, T: ?Sized> {
+    pub(super) value: &'a T,
+    pub(super) type_: &'a XsiType<'a>,
+}
+
+impl<T: Serialize + ?Sized> Serialize for Typed<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(TypedSerializer {
+            inner: serializer,
+            type_: self.type_.clone(),
+        })
+    }
+}
+
+/// Serializer adding the `xsi:type` attribute to the element written by `inner`
+pub(super) struct TypedSerializer<'a, S> {
+    pub(super) inner: S,
+    pub(super) type_: XsiType<'a>,
+}
+
+impl<S: Serializer> TypedSerializer<'_, S> {
+    /// Write a simple value as text of an element carrying the attribute
+    fn text<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        let mut element = self.inner.serialize_struct("", self.type_.len() + 1)?;
+        self.type_.write(&mut element)?;
+        element.serialize_field(TEXT_KEY, value)?;
+        element.end()
+    }
+}
+
+/// Sequence serializer adding the attribute to each item
+pub(super) struct TypedSeq<'a, S> {
+    inner: S,
+    type_: XsiType<'a>,
+}
+
+impl<S: SerializeSeq> SerializeSeq for TypedSeq<'_, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        self.inner.serialize_element(&Typed {
+            value,
+            type_: &self.type_,
+        })
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+macro_rules! serialize_text {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(
+            fn $method(self, value: $type) -> Result<S::Ok, S::Error> {
+                self.text(&value)
+            }
+        )*
+    };
+}
+
+impl<'a, S: Serializer> Serializer for TypedSerializer<'a, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = TypedSeq<'a, S::SerializeSeq>;
+    type SerializeTuple = S::SerializeTuple;
+    type SerializeTupleStruct = S::SerializeTupleStruct;
+    type SerializeTupleVariant = S::SerializeTupleVariant;
+    type SerializeMap = S::SerializeMap;
+    type SerializeStruct = S::SerializeStruct;
+    type SerializeStructVariant = S::SerializeStructVariant;
+
+    serialize_text! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_bytes(value)
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        serialize_nil(self.inner)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        // Enumeration values are written as text
+        self.text(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_newtype_variant(name, variant_index, variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Ok(TypedSeq {
+            inner: self.inner.serialize_seq(len)?,
+            type_: self.type_,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        self.inner.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        self.inner.serialize_tuple_struct(name, len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        self.inner
+            .serialize_tuple_variant(name, variant_index, variant, len)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        let mut map = self
+            .inner
+            .serialize_map(len.map(|len| len + self.type_.len()))?;
+        if let Some(namespace) = self.type_.declaration {
+            map.serialize_entry(TYPE_NAMESPACE_KEY, namespace)?;
+        }
+        map.serialize_entry(TYPE_KEY, &self.type_.name)?;
+        Ok(map)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        let mut element = self.inner.serialize_struct(name, len + self.type_.len())?;
+        self.type_.write(&mut element)?;
+        Ok(element)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        self.inner
+            .serialize_struct_variant(name, variant_index, variant, len)
+    }
+}
@@ -0,0 +1,499 @@
+//! Building SOAP 1.1 and 1.2 envelopes around request bodies
+
+use super::{SoapEnvelope, SoapVersion, SOAP_11_ENVELOPE_NS, SOAP_12_ENVELOPE_NS};
+use crate::error::SoapResult;
+use crate::header::HeaderBlock;
+use serde::Serialize;
+
+#[cfg(feature = "tracing")]
+use tracing::debug;
+
+impl SoapEnvelope {
+    /// Build a SOAP envelope with the given body
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The request body to serialize
+    /// * `version` - SOAP protocol version to use
+    ///
+    /// # Returns
+    ///
+    /// The complete SOAP envelope as an XML string
+    pub fn build<T>(body: &T, version: SoapVersion) -> SoapResult<String>
+    where
+        T: Serialize,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(soap_version = ?version, "Building SOAP envelope");
+
+        Self::build_with_namespace(body, version, None, true)
+    }
+
+    /// Build a SOAP envelope with optional namespace on the body element
+    ///
+    /// # Arguments
+    ///
+    /// * `element_form_qualified` - If false, namespace is only added to root element,
+    ///   not inherited by children (for elementFormDefault="unqualified" in XSD)
+    pub fn build_with_namespace<T>(
+        body: &T,
+        version: SoapVersion,
+        namespace: Option<&str>,
+        element_form_qualified: bool,
+    ) -> SoapResult<String>
+    where
+        T: Serialize,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(soap_version = ?version, namespace = ?namespace, element_form_qualified = %element_form_qualified, "Building SOAP envelope with namespace");
+
+        match version {
+            SoapVersion::Soap11 => Self::build_soap11(body, namespace, element_form_qualified),
+            SoapVersion::Soap12 => Self::build_soap12(body, namespace, element_form_qualified),
+        }
+    }
+
+    /// Build a SOAP 1.1 envelope
+    ///
+    /// Format:
+    /// ```xml
+    /// <?xml version="1.0" encoding="UTF-8"?>
+    /// <soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+    ///   <soap:Body>
+    ///     <!-- serialized body content -->
+    ///   </soap:Body>
+    /// </soap:Envelope>
+    /// ```
+    pub fn build_soap11<T>(
+        body: &T,
+        namespace: Option<&str>,
+        element_form_qualified: bool,
+    ) -> SoapResult<String>
+    where
+        T: Serialize,
+    {
+        #[cfg(feature = "tracing")]
+        debug!("Serializing request body to XML");
+
+        let body_xml = if let Some(ns) = namespace {
+            if element_form_qualified {
+                // Serialize with namespace - quick-xml will qualify all child elements
+                Self::serialize_to_xml_with_namespace(body, ns)?
+            } else {
+                // Serialize without namespace, then add namespace PREFIX to root element
+                // Using a prefix (ns:element) instead of default namespace (xmlns="...")
+                // prevents child elements from inheriting the namespace
+                let xml = Self::serialize_to_xml(body)?;
+                Self::add_namespace_prefix_to_root(&xml, ns, "ns")
+            }
+        } else {
+            Self::serialize_to_xml(body)?
+        };
+
+        #[cfg(feature = "tracing")]
+        debug!(body_xml_size = body_xml.len(), "Building SOAP 1.1 envelope");
+
+        // Build envelope manually to avoid escaping the body XML
+        let envelope = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><soap:Envelope xmlns:soap="{}"><soap:Body>{}</soap:Body></soap:Envelope>"#,
+            SOAP_11_ENVELOPE_NS, body_xml
+        );
+
+        Ok(envelope)
+    }
+
+    /// Build a SOAP 1.2 envelope
+    ///
+    /// Format:
+    /// ```xml
+    /// <?xml version="1.0" encoding="UTF-8"?>
+    /// <env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope">
+    ///   <env:Body>
+    ///     <!-- serialized body content -->
+    ///   </env:Body>
+    /// </env:Envelope>
+    /// ```
+    pub fn build_soap12<T>(
+        body: &T,
+        namespace: Option<&str>,
+        element_form_qualified: bool,
+    ) -> SoapResult<String>
+    where
+        T: Serialize,
+    {
+        #[cfg(feature = "tracing")]
+        debug!("Serializing request body to XML");
+
+        let body_xml = if let Some(ns) = namespace {
+            if element_form_qualified {
+                // Serialize with namespace - quick-xml will qualify all child elements
+                Self::serialize_to_xml_with_namespace(body, ns)?
+            } else {
+                // Serialize without namespace, then add namespace PREFIX to root element
+                // Using a prefix (ns:element) instead of default namespace (xmlns="...")
+                // prevents child elements from inheriting the namespace
+                let xml = Self::serialize_to_xml(body)?;
+                Self::add_namespace_prefix_to_root(&xml, ns, "ns")
+            }
+        } else {
+            Self::serialize_to_xml(body)?
+        };
+
+        #[cfg(feature = "tracing")]
+        debug!(body_xml_size = body_xml.len(), "Building SOAP 1.2 envelope");
+
+        // Build envelope manually to avoid escaping the body XML
+        let envelope = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><env:Envelope xmlns:env="{}"><env:Body>{}</env:Body></env:Envelope>"#,
+            SOAP_12_ENVELOPE_NS, body_xml
+        );
+
+        Ok(envelope)
+    }
+
+    /// Build a SOAP envelope for an RPC/encoded operation
+    ///
+    /// The body element is qualified with `namespace` and declares the SOAP
+    /// encoding as its `encodingStyle`, its children stay unqualified. The
+    /// envelope declares the `xsi`, `xsd` and `soapenc` prefixes used by the
+    /// annotations of [`encoding`](crate::encoding). The values follow the
+    /// SOAP 1.1 encoding rules with either SOAP version.
+    ///
+    /// Format:
+    /// ```xml
+    /// <soap:Envelope xmlns:soap="..." xmlns:xsi="..." xmlns:xsd="..." xmlns:soapenc="...">
+    ///   <soap:Body>
+    ///     <ns:getQuote xmlns:ns="..." soap:encodingStyle="http://schemas.xmlsoap.org/soap/encoding/">
+    ///       <symbol xsi:type="xsd:string">IBM</symbol>
+    ///     </ns:getQuote>
+    ///   </soap:Body>
+    /// </soap:Envelope>
+    /// ```
+    pub fn build_encoded<T>(body: &T, version: SoapVersion, namespace: &str) -> SoapResult<String>
+    where
+        T: Serialize,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(soap_version = ?version, namespace = %namespace, "Building encoded SOAP envelope");
+
+        let (prefix, envelope_namespace) = match version {
+            SoapVersion::Soap11 => ("soap", SOAP_11_ENVELOPE_NS),
+            SoapVersion::Soap12 => ("env", SOAP_12_ENVELOPE_NS),
+        };
+
+        let xml = Self::serialize_to_xml(body)?;
+        let body_xml = Self::add_attribute_to_root(
+            &Self::add_namespace_prefix_to_root(&xml, namespace, "ns"),
+            &format!(
+                "{}:encodingStyle=\"{}\"",
+                prefix,
+                crate::encoding::SOAP_ENCODING_NAMESPACE
+            ),
+        );
+
+        // Build envelope manually to avoid escaping the body XML
+        let envelope = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><{prefix}:Envelope xmlns:{prefix}="{}" xmlns:xsi="{}" xmlns:xsd="{}" xmlns:soapenc="{}"><{prefix}:Body>{}</{prefix}:Body></{prefix}:Envelope>"#,
+            envelope_namespace,
+            crate::xsi::XSI_NAMESPACE,
+            crate::encoding::XSD_NAMESPACE,
+            crate::encoding::SOAP_ENCODING_NAMESPACE,
+            body_xml,
+            prefix = prefix,
+        );
+
+        Ok(envelope)
+    }
+
+    /// Build a SOAP envelope whose body holds several elements
+    ///
+    /// Used for document style messages with more than one body part. The
+    /// fields of `parts` are serialized as consecutive children of the body,
+    /// the element of `parts` itself is dropped. Each child is qualified with
+    /// the namespace at the same position of `namespaces`, like the body
+    /// element of [`build_with_namespace`](Self::build_with_namespace).
+    /// Children without namespace or with an empty one are left unqualified.
+    ///
+    /// Format:
+    /// ```xml
+    /// <soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+    ///   <soap:Body>
+    ///     <Order xmlns="...">...</Order>
+    ///     <Customer xmlns="...">...</Customer>
+    ///   </soap:Body>
+    /// </soap:Envelope>
+    /// ```
+    pub fn build_parts<T>(
+        parts: &T,
+        version: SoapVersion,
+        namespaces: &[&str],
+        element_form_qualified: bool,
+    ) -> SoapResult<String>
+    where
+        T: Serialize,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(soap_version = ?version, namespaces = ?namespaces, element_form_qualified = %element_form_qualified, "Building multi-part SOAP envelope");
+
+        let xml = Self::serialize_to_xml(parts)?;
+        let mut body_xml = String::with_capacity(xml.len());
+        for (index, child) in Self::root_children(&xml)?.into_iter().enumerate() {
+            match namespaces.get(index).filter(|ns| !ns.is_empty()) {
+                Some(ns) if element_form_qualified => {
+                    body_xml.push_str(&Self::add_namespace_to_root(child, ns))
+                }
+                Some(ns) => body_xml.push_str(&Self::add_namespace_prefix_to_root(child, ns, "ns")),
+                None => body_xml.push_str(child),
+            }
+        }
+
+        let (prefix, envelope_namespace) = match version {
+            SoapVersion::Soap11 => ("soap", SOAP_11_ENVELOPE_NS),
+            SoapVersion::Soap12 => ("env", SOAP_12_ENVELOPE_NS),
+        };
+
+        // Build envelope manually to avoid escaping the body XML
+        let envelope = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><{prefix}:Envelope xmlns:{prefix}="{}"><{prefix}:Body>{}</{prefix}:Body></{prefix}:Envelope>"#,
+            envelope_namespace,
+            body_xml,
+            prefix = prefix,
+        );
+
+        Ok(envelope)
+    }
+
+    /// Split the content of the root element of an XML string into its child elements
+    ///
+    /// Converts `<Root><A/><B>..</B></Root>` to `["<A/>", "<B>..</B>"]`,
+    /// text between the children is dropped.
+    pub(super) fn root_children(xml: &str) -> SoapResult<Vec<&str>> {
+        use quick_xml::events::Event;
+        use quick_xml::Reader;
+
+        let mut reader = Reader::from_str(xml);
+        let mut children = Vec::new();
+        let mut in_root = false;
+        loop {
+            let start = reader.buffer_position() as usize;
+            match reader.read_event()? {
+                Event::Start(_) if !in_root => in_root = true,
+                Event::Start(e) => {
+                    reader.read_to_end(e.name())?;
+                    children.push(&xml[start..reader.buffer_position() as usize]);
+                }
+                Event::Empty(_) if in_root => {
+                    children.push(&xml[start..reader.buffer_position() as usize]);
+                }
+                Event::Empty(_) | Event::End(_) | Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(children)
+    }
+
+    /// Add header blocks to an envelope built by this module
+    ///
+    /// The blocks are written into a `Header` element preceding the `Body`.
+    ///
+    /// Format:
+    /// ```xml
+    /// <soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+    ///   <soap:Header>
+    ///     <AuthHeader xmlns="...">...</AuthHeader>
+    ///   </soap:Header>
+    ///   <soap:Body>...</soap:Body>
+    /// </soap:Envelope>
+    /// ```
+    pub fn add_headers(envelope: &str, version: SoapVersion, headers: &[HeaderBlock]) -> String {
+        let prefix = match version {
+            SoapVersion::Soap11 => "soap",
+            SoapVersion::Soap12 => "env",
+        };
+        let body_tag = format!("<{}:Body>", prefix);
+        let Some(pos) = envelope.find(&body_tag) else {
+            return envelope.to_string();
+        };
+
+        let blocks: String = headers
+            .iter()
+            .map(|header| header.to_xml(version))
+            .collect();
+        format!(
+            "{}<{prefix}:Header>{}</{prefix}:Header>{}",
+            &envelope[..pos],
+            blocks,
+            &envelope[pos..],
+            prefix = prefix,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct TestRequest {
+        name: String,
+        value: i32,
+    }
+
+    #[test]
+    fn test_build_soap11_envelope() {
+        let request = TestRequest {
+            name: "test".to_string(),
+            value: 42,
+        };
+
+        let envelope = SoapEnvelope::build_soap11(&request, None, true).unwrap();
+        println!("SOAP 1.1 Envelope:\n{}", envelope);
+
+        assert!(envelope.contains("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(envelope.contains("<soap:Envelope"));
+        assert!(envelope.contains(&format!("xmlns:soap=\"{}\"", SOAP_11_ENVELOPE_NS)));
+        assert!(envelope.contains("<soap:Body>"));
+        assert!(envelope.contains("</soap:Body>"));
+        assert!(envelope.contains("</soap:Envelope>"));
+        assert!(envelope.contains("<name>test</name>"));
+        assert!(envelope.contains("<value>42</value>"));
+    }
+
+    #[test]
+    fn test_build_soap12_envelope() {
+        let request = TestRequest {
+            name: "test".to_string(),
+            value: 42,
+        };
+
+        let envelope = SoapEnvelope::build_soap12(&request, None, true).unwrap();
+        println!("SOAP 1.2 Envelope:\n{}", envelope);
+
+        assert!(envelope.contains("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(envelope.contains("<env:Envelope"));
+        assert!(envelope.contains(&format!("xmlns:env=\"{}\"", SOAP_12_ENVELOPE_NS)));
+        assert!(envelope.contains("<env:Body>"));
+        assert!(envelope.contains("</env:Body>"));
+        assert!(envelope.contains("</env:Envelope>"));
+        assert!(envelope.contains("<name>test</name>"));
+        assert!(envelope.contains("<value>42</value>"));
+    }
+
+    #[test]
+    fn test_build_with_version() {
+        let request = TestRequest {
+            name: "test".to_string(),
+            value: 42,
+        };
+
+        let envelope11 = SoapEnvelope::build(&request, SoapVersion::Soap11).unwrap();
+        assert!(envelope11.contains("soap:Envelope"));
+
+        let envelope12 = SoapEnvelope::build(&request, SoapVersion::Soap12).unwrap();
+        assert!(envelope12.contains("env:Envelope"));
+    }
+
+    #[test]
+    fn test_build_encoded_envelope() {
+        #[derive(Serialize)]
+        #[serde(rename = "getQuote")]
+        struct GetQuote {
+            #[serde(serialize_with = "serialize_symbol")]
+            symbol: String,
+        }
+
+        fn serialize_symbol<S: serde::Serializer>(
+            value: &str,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            crate::encoding::serialize_typed(
+                value,
+                crate::encoding::XSD_NAMESPACE,
+                "string",
+                serializer,
+            )
+        }
+
+        let request = GetQuote {
+            symbol: "IBM".to_string(),
+        };
+        let envelope =
+            SoapEnvelope::build_encoded(&request, SoapVersion::Soap11, "urn:quotes").unwrap();
+
+        assert!(envelope.contains("xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\""));
+        assert!(envelope.contains("xmlns:xsd=\"http://www.w3.org/2001/XMLSchema\""));
+        assert!(envelope.contains("xmlns:soapenc=\"http://schemas.xmlsoap.org/soap/encoding/\""));
+        assert!(envelope.contains(
+            "<soap:Body><ns:getQuote xmlns:ns=\"urn:quotes\" \
+             soap:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <symbol xsi:type=\"xsd:string\">IBM</symbol></ns:getQuote></soap:Body>"
+        ));
+    }
+
+    #[test]
+    fn test_build_parts_envelope() {
+        #[derive(Serialize)]
+        struct Parts {
+            #[serde(rename = "Order")]
+            order: Order,
+            #[serde(rename = "Priority")]
+            priority: u8,
+        }
+
+        #[derive(Serialize)]
+        struct Order {
+            id: String,
+        }
+
+        let parts = Parts {
+            order: Order {
+                id: "A1".to_string(),
+            },
+            priority: 2,
+        };
+
+        let envelope = SoapEnvelope::build_parts(
+            &parts,
+            SoapVersion::Soap11,
+            &["urn:orders", "urn:common"],
+            true,
+        )
+        .unwrap();
+        assert!(envelope.contains(
+            "<soap:Body><Order xmlns=\"urn:orders\"><id>A1</id></Order>\
+             <Priority xmlns=\"urn:common\">2</Priority></soap:Body>"
+        ));
+
+        // Unqualified children, missing namespaces leave the part as is
+        let envelope =
+            SoapEnvelope::build_parts(&parts, SoapVersion::Soap12, &["urn:orders"], false).unwrap();
+        assert!(envelope.contains(
+            "<env:Body><ns:Order xmlns:ns=\"urn:orders\"><id>A1</id></ns:Order>\
+             <Priority>2</Priority></env:Body>"
+        ));
+    }
+
+    #[test]
+    fn test_add_headers() {
+        let request = TestRequest {
+            name: "test".to_string(),
+            value: 42,
+        };
+        let header = HeaderBlock::new("SessionId", "abc").unwrap();
+
+        let envelope = SoapEnvelope::build(&request, SoapVersion::Soap11).unwrap();
+        let envelope = SoapEnvelope::add_headers(
+            &envelope,
+            SoapVersion::Soap11,
+            std::slice::from_ref(&header),
+        );
+        assert!(envelope.contains(
+            "<soap:Header><SessionId>abc</SessionId></soap:Header><soap:Body><TestRequest>"
+        ));
+
+        let envelope = SoapEnvelope::build(&request, SoapVersion::Soap12).unwrap();
+        let envelope = SoapEnvelope::add_headers(&envelope, SoapVersion::Soap12, &[header]);
+        assert!(envelope.contains("<env:Header><SessionId>abc</SessionId></env:Header><env:Body>"));
+    }
+}
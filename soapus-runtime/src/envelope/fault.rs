@@ -0,0 +1,342 @@
+//! Detection of SOAP 1.1 and 1.2 faults in responses
+
+use super::{SoapEnvelope, SoapVersion, SOAP_11_ENVELOPE_NS, SOAP_12_ENVELOPE_NS};
+use crate::error::{FaultReason, SoapError, SoapFault, SoapResult};
+
+#[cfg(feature = "tracing")]
+use tracing::debug;
+
+impl SoapEnvelope {
+    /// Check if a SOAP response contains a fault
+    ///
+    /// A `Fault` element of the SOAP 1.1 or 1.2 envelope namespace is returned
+    /// as [`SoapError::SoapFault`], see [`SoapFault`] for what is kept of it.
+    pub fn check_for_fault(xml: &str) -> SoapResult<()> {
+        #[cfg(feature = "tracing")]
+        debug!("Checking SOAP response for faults");
+
+        use quick_xml::events::Event;
+        use quick_xml::name::{Namespace, ResolveResult};
+        use quick_xml::NsReader;
+
+        let mut reader = NsReader::from_str(xml);
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) if e.local_name().as_ref() == b"Fault" => {
+                    let version = match reader.resolve_element(e.name()).0 {
+                        ResolveResult::Bound(Namespace(ns))
+                            if ns == SOAP_11_ENVELOPE_NS.as_bytes() =>
+                        {
+                            SoapVersion::Soap11
+                        }
+                        ResolveResult::Bound(Namespace(ns))
+                            if ns == SOAP_12_ENVELOPE_NS.as_bytes() =>
+                        {
+                            SoapVersion::Soap12
+                        }
+                        // A payload element that happens to be named Fault
+                        _ => continue,
+                    };
+                    let fault = Self::read_fault(&mut reader, xml, version)?;
+                    return Err(SoapError::SoapFault(Box::new(fault)));
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the content of a `Fault` element, up to and including its end tag
+    fn read_fault(
+        reader: &mut quick_xml::NsReader<&[u8]>,
+        xml: &str,
+        version: SoapVersion,
+    ) -> SoapResult<SoapFault> {
+        use quick_xml::events::Event;
+
+        let mut fault = SoapFault {
+            version,
+            ..SoapFault::default()
+        };
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => match e.local_name().as_ref() {
+                    b"faultcode" => fault.code = Self::read_text(reader, &e)?,
+                    b"faultstring" => fault.reasons.push(FaultReason {
+                        lang: Self::xml_lang(&e)?,
+                        text: Self::read_text(reader, &e)?,
+                    }),
+                    b"faultactor" | b"Role" => fault.role = Some(Self::read_text(reader, &e)?),
+                    b"Node" => fault.node = Some(Self::read_text(reader, &e)?),
+                    b"Code" => Self::read_fault_code(reader, &mut fault)?,
+                    b"Reason" => loop {
+                        match reader.read_event()? {
+                            Event::Start(e) if e.local_name().as_ref() == b"Text" => {
+                                fault.reasons.push(FaultReason {
+                                    lang: Self::xml_lang(&e)?,
+                                    text: Self::read_text(reader, &e)?,
+                                });
+                            }
+                            Event::Start(e) => {
+                                reader.read_to_end(e.name())?;
+                            }
+                            Event::End(_) | Event::Eof => break,
+                            _ => {}
+                        }
+                    },
+                    b"detail" | b"Detail" => {
+                        // Keep the content as written, including nested elements
+                        let span = reader.read_to_end(e.name())?;
+                        let content = xml[span.start as usize..span.end as usize].trim();
+                        if !content.is_empty() {
+                            fault.detail = Some(content.to_string());
+                        }
+                    }
+                    _ => {
+                        reader.read_to_end(e.name())?;
+                    }
+                },
+                Event::End(_) | Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(fault)
+    }
+
+    /// Read the `Value`s of a SOAP 1.2 `Code` and its nested `Subcode`s
+    fn read_fault_code(
+        reader: &mut quick_xml::NsReader<&[u8]>,
+        fault: &mut SoapFault,
+    ) -> SoapResult<()> {
+        use quick_xml::events::Event;
+
+        // Subcodes nest, each one's Value follows the Value of its parent
+        let mut depth = 0;
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) if e.local_name().as_ref() == b"Value" => {
+                    let value = Self::read_text(reader, &e)?;
+                    if depth == 0 {
+                        fault.code = value;
+                    } else {
+                        fault.subcodes.push(value);
+                    }
+                }
+                Event::Start(e) if e.local_name().as_ref() == b"Subcode" => depth += 1,
+                Event::Start(e) => {
+                    reader.read_to_end(e.name())?;
+                }
+                Event::End(_) if depth > 0 => depth -= 1,
+                Event::End(_) | Event::Eof => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the trimmed, unescaped text content of an element
+    fn read_text(
+        reader: &mut quick_xml::NsReader<&[u8]>,
+        start: &quick_xml::events::BytesStart,
+    ) -> SoapResult<String> {
+        let text = reader.read_text(start.name())?;
+        let text = quick_xml::escape::unescape(text.trim())
+            .map_err(|e| SoapError::XmlError(e.to_string()))?;
+        Ok(text.into_owned())
+    }
+
+    /// Get the `xml:lang` attribute of an element
+    fn xml_lang(start: &quick_xml::events::BytesStart) -> SoapResult<Option<String>> {
+        match start.try_get_attribute("xml:lang")? {
+            Some(attr) => Ok(Some(
+                attr.unescape_value()
+                    .map_err(|e| SoapError::XmlError(e.to_string()))?
+                    .into_owned(),
+            )),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_check_for_fault_no_fault() {
+        let response_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <TestResponse>
+      <result>success</result>
+    </TestResponse>
+  </soap:Body>
+</soap:Envelope>"#;
+
+        let result = SoapEnvelope::check_for_fault(response_xml);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_check_for_fault_with_fault() {
+        let fault_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <soap:Fault>
+      <faultcode>soap:Server</faultcode>
+      <faultstring>Internal Server Error</faultstring>
+    </soap:Fault>
+  </soap:Body>
+</soap:Envelope>"#;
+
+        let result = SoapEnvelope::check_for_fault(fault_xml);
+        assert!(result.is_err());
+
+        if let Err(SoapError::SoapFault(fault)) = result {
+            assert_eq!(fault.version, SoapVersion::Soap11);
+            assert_eq!(fault.code, "soap:Server");
+            assert_eq!(fault.message(), "Internal Server Error");
+        } else {
+            panic!("Expected SoapFault error");
+        }
+    }
+
+    #[test]
+    fn test_check_for_fault_with_detail() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct OrderNotFoundFault {
+            #[serde(rename = "orderId")]
+            order_id: String,
+        }
+
+        let fault_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <soap:Fault>
+      <faultcode>soap:Client</faultcode>
+      <faultstring>Order not found</faultstring>
+      <detail>
+        <tns:OrderNotFoundFault xmlns:tns="urn:orders">
+          <tns:orderId>A&amp;1</tns:orderId>
+        </tns:OrderNotFoundFault>
+      </detail>
+    </soap:Fault>
+  </soap:Body>
+</soap:Envelope>"#;
+
+        let error = SoapEnvelope::check_for_fault(fault_xml).unwrap_err();
+        let fault = error.fault().expect("Expected SoapFault error");
+        assert_eq!(fault.code, "soap:Client");
+        assert_eq!(fault.message(), "Order not found");
+        assert!(fault
+            .detail
+            .as_deref()
+            .unwrap()
+            .starts_with("<tns:OrderNotFoundFault xmlns:tns=\"urn:orders\">"));
+
+        assert_eq!(
+            error.fault_detail("OrderNotFoundFault"),
+            Some(OrderNotFoundFault {
+                order_id: "A&1".to_string()
+            })
+        );
+        assert_eq!(
+            error.fault_detail::<OrderNotFoundFault>("AccessDenied"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_check_for_fault_soap11_actor() {
+        let fault_xml = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <soap:Fault>
+      <faultcode>soap:Client</faultcode>
+      <faultstring xml:lang="en">Bad &amp; invalid</faultstring>
+      <faultactor>http://example.com/gateway</faultactor>
+      <detail/>
+    </soap:Fault>
+  </soap:Body>
+</soap:Envelope>"#;
+
+        let error = SoapEnvelope::check_for_fault(fault_xml).unwrap_err();
+        assert_eq!(
+            error.fault(),
+            Some(&SoapFault {
+                version: SoapVersion::Soap11,
+                code: "soap:Client".to_string(),
+                reasons: vec![FaultReason {
+                    text: "Bad & invalid".to_string(),
+                    lang: Some("en".to_string()),
+                }],
+                role: Some("http://example.com/gateway".to_string()),
+                ..SoapFault::default()
+            })
+        );
+        assert_eq!(error.to_string(), "SOAP fault: soap:Client - Bad & invalid");
+    }
+
+    #[test]
+    fn test_check_for_fault_soap12() {
+        let fault_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope">
+  <env:Body>
+    <env:Fault>
+      <env:Code>
+        <env:Value>env:Sender</env:Value>
+        <env:Subcode>
+          <env:Value>m:InvalidOrder</env:Value>
+          <env:Subcode><env:Value>m:UnknownProduct</env:Value></env:Subcode>
+        </env:Subcode>
+      </env:Code>
+      <env:Reason>
+        <env:Text xml:lang="en">Invalid order</env:Text>
+        <env:Text xml:lang="de">Ungültige Bestellung</env:Text>
+      </env:Reason>
+      <env:Node>http://example.com/gateway</env:Node>
+      <env:Role>http://www.w3.org/2003/05/soap-envelope/role/next</env:Role>
+      <env:Detail><AccessDenied><reason>expired</reason></AccessDenied></env:Detail>
+    </env:Fault>
+  </env:Body>
+</env:Envelope>"#;
+
+        let error = SoapEnvelope::check_for_fault(fault_xml).unwrap_err();
+        let fault = error.fault().expect("Expected SoapFault error");
+        assert_eq!(fault.version, SoapVersion::Soap12);
+        assert_eq!(fault.code, "env:Sender");
+        assert_eq!(fault.subcodes, ["m:InvalidOrder", "m:UnknownProduct"]);
+        assert_eq!(fault.message(), "Invalid order");
+        assert_eq!(fault.reason("de"), Some("Ungültige Bestellung"));
+        assert_eq!(fault.reason("fr"), None);
+        assert_eq!(fault.node.as_deref(), Some("http://example.com/gateway"));
+        assert_eq!(
+            fault.role.as_deref(),
+            Some("http://www.w3.org/2003/05/soap-envelope/role/next")
+        );
+        assert_eq!(
+            fault.detail.as_deref(),
+            Some("<AccessDenied><reason>expired</reason></AccessDenied>")
+        );
+        assert_eq!(
+            error.to_string(),
+            "SOAP fault: env:Sender / m:InvalidOrder / m:UnknownProduct - Invalid order"
+        );
+    }
+
+    #[test]
+    fn test_check_for_fault_ignores_payload_named_fault() {
+        let response_xml = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <ReportResponse xmlns="urn:reports"><Fault><code>E1</code></Fault></ReportResponse>
+  </soap:Body>
+</soap:Envelope>"#;
+
+        assert!(SoapEnvelope::check_for_fault(response_xml).is_ok());
+    }
+}
@@ -0,0 +1,136 @@
+//! SOAP envelope building and parsing
+//!
+//! This module handles the construction of SOAP envelopes for both SOAP 1.1 and 1.2.
+//! It serializes request bodies to XML and wraps them in the appropriate SOAP envelope structure,
+//! and extracts bodies, header blocks and faults from responses.
+
+mod build;
+mod fault;
+mod parse;
+mod xml;
+
+// Standard SOAP envelope namespace URIs as defined by W3C SOAP specification
+pub(crate) const SOAP_11_ENVELOPE_NS: &str = "http://schemas.xmlsoap.org/soap/envelope/";
+pub(crate) const SOAP_12_ENVELOPE_NS: &str = "http://www.w3.org/2003/05/soap-envelope";
+
+/// SOAP protocol version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SoapVersion {
+    /// SOAP 1.1
+    #[default]
+    Soap11,
+    /// SOAP 1.2
+    Soap12,
+}
+
+/// SOAP envelope builder
+pub struct SoapEnvelope;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn test_default_soap_version() {
+        assert_eq!(SoapVersion::default(), SoapVersion::Soap11);
+    }
+
+    #[test]
+    fn test_choice_round_trip() {
+        // Shape of the code generated for an xsd:choice nested in a sequence
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Card {
+            number: String,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        enum PaymentChoice {
+            #[serde(rename = "card")]
+            Card(Card),
+            #[serde(rename = "iban")]
+            Iban(String),
+        }
+
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Payment {
+            amount: f64,
+            #[serde(rename = "$value", default)]
+            choice: Vec<PaymentChoice>,
+            reference: String,
+        }
+
+        let payment = Payment {
+            amount: 12.5,
+            choice: vec![
+                PaymentChoice::Iban("DE89370400440532013000".to_string()),
+                PaymentChoice::Card(Card {
+                    number: "4111".to_string(),
+                }),
+            ],
+            reference: "R-1".to_string(),
+        };
+
+        let envelope = SoapEnvelope::build_soap11(&payment, Some("urn:test"), true).unwrap();
+        assert!(envelope.contains(
+            "<amount>12.5</amount><iban>DE89370400440532013000</iban><card><number>4111</number></card><reference>R-1</reference>"
+        ));
+
+        let parsed: Payment = SoapEnvelope::parse_response(&envelope).unwrap();
+        assert_eq!(parsed, payment);
+    }
+
+    #[test]
+    fn test_simple_content_round_trip() {
+        // Shape of the code generated for xsd:simpleContent with attributes
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Amount {
+            #[serde(rename = "@currency")]
+            currency: String,
+            #[serde(rename = "@precision", skip_serializing_if = "Option::is_none")]
+            precision: Option<i32>,
+            #[serde(rename = "$text")]
+            value: f64,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Label {
+            #[serde(rename = "@lang", skip_serializing_if = "Option::is_none")]
+            lang: Option<String>,
+            #[serde(rename = "$text", default)]
+            value: String,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Balance {
+            total: Amount,
+            label: Vec<Label>,
+        }
+
+        let balance = Balance {
+            total: Amount {
+                currency: "EUR".to_string(),
+                precision: None,
+                value: 12.5,
+            },
+            label: vec![
+                Label {
+                    lang: Some("en".to_string()),
+                    value: "Fees & charges".to_string(),
+                },
+                Label {
+                    lang: None,
+                    value: String::new(),
+                },
+            ],
+        };
+
+        let envelope = SoapEnvelope::build_soap11(&balance, Some("urn:test"), true).unwrap();
+        assert!(envelope.contains(
+            "<total currency=\"EUR\">12.5</total><label lang=\"en\">Fees &amp; charges</label><label/>"
+        ));
+
+        let parsed: Balance = SoapEnvelope::parse_response(&envelope).unwrap();
+        assert_eq!(parsed, balance);
+    }
+}
@@ -0,0 +1,409 @@
+//! Extraction of the Body and the header blocks of SOAP responses
+
+use super::{SoapEnvelope, SOAP_11_ENVELOPE_NS, SOAP_12_ENVELOPE_NS};
+use crate::error::{SoapError, SoapResult};
+use crate::header::HeaderBlock;
+
+#[cfg(feature = "tracing")]
+use tracing::debug;
+
+impl SoapEnvelope {
+    /// Parse a SOAP response and extract the body content
+    ///
+    /// This function extracts the content between `<soap:Body>` or `<env:Body>` tags
+    /// and deserializes it into the expected response type.
+    pub fn parse_response<T>(xml: &str) -> SoapResult<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(response_size = xml.len(), "Parsing SOAP response");
+
+        // Fix invalid XML: replace unescaped & with &amp;
+        // This handles server bugs where text contains unescaped ampersands
+        let fixed_xml = Self::fix_unescaped_ampersands(xml);
+
+        use quick_xml::events::Event;
+        use quick_xml::Reader;
+
+        let mut reader = Reader::from_str(&fixed_xml);
+
+        let mut buf = Vec::new();
+        let mut in_body = false;
+        let mut body_content = String::new();
+        let mut depth = 0;
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => {
+                    let name = e.name();
+                    let local_name = name.as_ref();
+
+                    // Check if this is a Body element (SOAP 1.1 or 1.2)
+                    if local_name.ends_with(b"Body") {
+                        in_body = true;
+                        depth = 0;
+                    } else if in_body {
+                        depth += 1;
+                        // Capture the start tag
+                        let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                        body_content.push('<');
+                        body_content.push_str(&tag);
+
+                        // Add attributes
+                        for attr in e.attributes().flatten() {
+                            body_content.push(' ');
+                            body_content.push_str(&String::from_utf8_lossy(attr.key.as_ref()));
+                            body_content.push_str("=\"");
+                            body_content.push_str(&String::from_utf8_lossy(&attr.value));
+                            body_content.push('"');
+                        }
+                        body_content.push('>');
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    let name = e.name();
+                    let local_name = name.as_ref();
+
+                    if local_name.ends_with(b"Body") && in_body && depth == 0 {
+                        // End of Body - we're done
+                        break;
+                    } else if in_body {
+                        depth -= 1;
+                        let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                        body_content.push_str("</");
+                        body_content.push_str(&tag);
+                        body_content.push('>');
+                    }
+                }
+                Ok(Event::Text(e)) if in_body => {
+                    body_content.push_str(&e.unescape().unwrap_or_default());
+                }
+                Ok(Event::Empty(e)) if in_body => {
+                    let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                    body_content.push('<');
+                    body_content.push_str(&tag);
+
+                    // Add attributes
+                    for attr in e.attributes().flatten() {
+                        body_content.push(' ');
+                        body_content.push_str(&String::from_utf8_lossy(attr.key.as_ref()));
+                        body_content.push_str("=\"");
+                        body_content.push_str(&String::from_utf8_lossy(&attr.value));
+                        body_content.push('"');
+                    }
+                    body_content.push_str("/>");
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(SoapError::XmlError(e.to_string())),
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        if body_content.is_empty() {
+            return Err(SoapError::InvalidResponse(
+                "No body content found in SOAP response".to_string(),
+            ));
+        }
+
+        #[cfg(feature = "tracing")]
+        debug!(
+            body_content_size = body_content.len(),
+            "Extracted body content from SOAP response"
+        );
+
+        // Fix unescaped ampersands in body content before deserialization
+        let fixed_body_content = Self::fix_unescaped_ampersands(&body_content);
+
+        // Deserialize the body content
+        quick_xml::de::from_str(&fixed_body_content)
+            .map_err(|e| SoapError::DeserializationError(e.to_string()))
+    }
+
+    /// Parse the response of an RPC/encoded operation
+    ///
+    /// Like [`parse_response`](Self::parse_response), after copying values
+    /// referenced with `href` into place, see
+    /// [`resolve_multi_refs`](crate::encoding::resolve_multi_refs).
+    pub fn parse_encoded_response<T>(xml: &str) -> SoapResult<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(response_size = xml.len(), "Parsing encoded SOAP response");
+
+        let body_content = Self::body_content(xml)?;
+        let resolved = crate::encoding::resolve_multi_refs(&body_content)?;
+        quick_xml::de::from_str(&resolved)
+            .map_err(|e| SoapError::DeserializationError(e.to_string()))
+    }
+
+    /// Parse the response of a document style operation with several body parts
+    ///
+    /// Unlike [`parse_response`](Self::parse_response), all children of the
+    /// body are deserialized together, as the fields of `T`.
+    pub fn parse_parts_response<T>(xml: &str) -> SoapResult<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        #[cfg(feature = "tracing")]
+        debug!(
+            response_size = xml.len(),
+            "Parsing multi-part SOAP response"
+        );
+
+        let body_content = Self::body_content(xml)?;
+        quick_xml::de::from_str(&format!("<Body>{}</Body>", body_content))
+            .map_err(|e| SoapError::DeserializationError(e.to_string()))
+    }
+
+    /// Get the header blocks of a SOAP response
+    ///
+    /// Each child of the `Header` element of a SOAP 1.1 or 1.2 envelope
+    /// becomes a [`HeaderBlock`] holding its raw XML, including any SOAP
+    /// attributes. Namespace prefixes declared outside of a block are not
+    /// carried over. A response without `Header` has no header blocks.
+    pub fn parse_response_headers(xml: &str) -> SoapResult<Vec<HeaderBlock>> {
+        use quick_xml::events::Event;
+        use quick_xml::name::{Namespace, ResolveResult};
+        use quick_xml::NsReader;
+
+        let fixed_xml = Self::fix_unescaped_ampersands(xml);
+        let mut reader = NsReader::from_str(&fixed_xml);
+
+        let header_content = loop {
+            match reader.read_event()? {
+                Event::Start(e) if e.local_name().as_ref() == b"Header" => {
+                    let in_envelope = matches!(
+                        reader.resolve_element(e.name()).0,
+                        ResolveResult::Bound(Namespace(ns))
+                            if ns == SOAP_11_ENVELOPE_NS.as_bytes()
+                                || ns == SOAP_12_ENVELOPE_NS.as_bytes()
+                    );
+                    if in_envelope {
+                        let span = reader.read_to_end(e.name())?;
+                        break &fixed_xml[span.start as usize..span.end as usize];
+                    }
+                }
+                // Headers precede the body
+                Event::Start(e) if e.local_name().as_ref() == b"Body" => return Ok(Vec::new()),
+                Event::Eof => return Ok(Vec::new()),
+                _ => {}
+            }
+        };
+
+        let header = format!("<Header>{}</Header>", header_content);
+        Ok(Self::root_children(&header)?
+            .into_iter()
+            .map(HeaderBlock::from_xml)
+            .collect())
+    }
+
+    /// Get the raw content of the `Body` element of a SOAP 1.1 or 1.2 envelope
+    fn body_content(xml: &str) -> SoapResult<String> {
+        use quick_xml::events::Event;
+        use quick_xml::name::{Namespace, ResolveResult};
+        use quick_xml::NsReader;
+
+        let fixed_xml = Self::fix_unescaped_ampersands(xml);
+        let mut reader = NsReader::from_str(&fixed_xml);
+
+        let body_content = loop {
+            match reader.read_event()? {
+                Event::Start(e) if e.local_name().as_ref() == b"Body" => {
+                    let in_envelope = matches!(
+                        reader.resolve_element(e.name()).0,
+                        ResolveResult::Bound(Namespace(ns))
+                            if ns == SOAP_11_ENVELOPE_NS.as_bytes()
+                                || ns == SOAP_12_ENVELOPE_NS.as_bytes()
+                    );
+                    if in_envelope {
+                        let span = reader.read_to_end(e.name())?;
+                        break fixed_xml[span.start as usize..span.end as usize].trim();
+                    }
+                }
+                Event::Eof => break "",
+                _ => {}
+            }
+        };
+
+        if body_content.is_empty() {
+            return Err(SoapError::InvalidResponse(
+                "No body content found in SOAP response".to_string(),
+            ));
+        }
+        Ok(body_content.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct TestResponse {
+        result: String,
+    }
+
+    #[test]
+    fn test_parse_soap11_response() {
+        let response_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <TestResponse>
+      <result>success</result>
+    </TestResponse>
+  </soap:Body>
+</soap:Envelope>"#;
+
+        let response: TestResponse = SoapEnvelope::parse_response(response_xml).unwrap();
+        assert_eq!(response.result, "success");
+    }
+
+    #[test]
+    fn test_parse_soap12_response() {
+        let response_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope">
+  <env:Body>
+    <TestResponse>
+      <result>success</result>
+    </TestResponse>
+  </env:Body>
+</env:Envelope>"#;
+
+        let response: TestResponse = SoapEnvelope::parse_response(response_xml).unwrap();
+        assert_eq!(response.result, "success");
+    }
+
+    #[test]
+    fn test_parse_encoded_response() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Quote {
+            symbol: String,
+            price: f64,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct GetQuoteResponse {
+            #[serde(rename = "return")]
+            result: Quote,
+        }
+
+        let xml = r##"<?xml version="1.0"?>
+<soapenv:Envelope xmlns:soapenv="http://schemas.xmlsoap.org/soap/envelope/"
+                  xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <soapenv:Body>
+    <ns1:getQuoteResponse soapenv:encodingStyle="http://schemas.xmlsoap.org/soap/encoding/"
+                          xmlns:ns1="urn:quotes">
+      <return href="#id0"/>
+    </ns1:getQuoteResponse>
+    <multiRef id="id0" soapenc:root="0" xsi:type="ns2:Quote" xmlns:ns2="urn:quotes"
+              xmlns:soapenc="http://schemas.xmlsoap.org/soap/encoding/">
+      <symbol xsi:type="xsd:string">IBM</symbol>
+      <price xsi:type="xsd:double">1.5</price>
+    </multiRef>
+  </soapenv:Body>
+</soapenv:Envelope>"##;
+
+        let response: GetQuoteResponse = SoapEnvelope::parse_encoded_response(xml).unwrap();
+        assert_eq!(
+            response.result,
+            Quote {
+                symbol: "IBM".to_string(),
+                price: 1.5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_parts_response() {
+        #[derive(Debug, Deserialize)]
+        struct Parts {
+            #[serde(rename = "Receipt")]
+            receipt: Receipt,
+            #[serde(rename = "Total")]
+            total: f64,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Receipt {
+            number: String,
+        }
+
+        let xml = r#"<?xml version="1.0"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <o:Receipt xmlns:o="urn:orders"><o:number>R-7</o:number></o:Receipt>
+    <Total xmlns="urn:common">12.5</Total>
+  </soap:Body>
+</soap:Envelope>"#;
+
+        let response: Parts = SoapEnvelope::parse_parts_response(xml).unwrap();
+        assert_eq!(response.receipt.number, "R-7");
+        assert_eq!(response.total, 12.5);
+    }
+
+    #[test]
+    fn test_parse_response_headers() {
+        let xml = r#"<?xml version="1.0"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+    <soap:Header>
+        <SessionHeader xmlns="urn:sessions" soap:mustUnderstand="1"><SessionId>42</SessionId></SessionHeader>
+        <Expires xmlns="urn:sessions"/>
+    </soap:Header>
+    <soap:Body>
+        <TestResponse><result>Success</result><total>1</total></TestResponse>
+    </soap:Body>
+</soap:Envelope>"#;
+
+        let headers = SoapEnvelope::parse_response_headers(xml).unwrap();
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[0].name(), "SessionHeader");
+        assert_eq!(
+            headers[0].xml(),
+            r#"<SessionHeader xmlns="urn:sessions" soap:mustUnderstand="1"><SessionId>42</SessionId></SessionHeader>"#
+        );
+        assert_eq!(headers[1].xml(), r#"<Expires xmlns="urn:sessions"/>"#);
+
+        // A response without Header has no header blocks
+        let xml = r#"<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope"><env:Body><TestResponse/></env:Body></env:Envelope>"#;
+        assert!(SoapEnvelope::parse_response_headers(xml)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_response_with_unescaped_ampersand() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct TestResponse {
+            name: String,
+        }
+
+        // Simulate a SOAP response with unescaped ampersand (like from Rotorsoft)
+        let soap_xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+    <soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+      <soap:Body>
+        <TestResponse>
+          <name>Ville 1&4 (E)</name>
+        </TestResponse>
+      </soap:Body>
+    </soap:Envelope>"#;
+
+        let result: Result<TestResponse, _> = SoapEnvelope::parse_response(soap_xml);
+
+        match &result {
+            Ok(_) => {}
+            Err(e) => eprintln!("Parse error: {}", e),
+        }
+
+        assert!(
+            result.is_ok(),
+            "Should successfully parse despite unescaped ampersand: {:?}",
+            result.err()
+        );
+        let response = result.unwrap();
+        assert_eq!(response.name, "Ville 1&4 (E)");
+    }
+}
@@ -0,0 +1,214 @@
+//! Serialization of request bodies and fixes to their XML
+
+use super::SoapEnvelope;
+use crate::error::{SoapError, SoapResult};
+use serde::Serialize;
+
+impl SoapEnvelope {
+    /// Serialize a value to XML string using quick-xml
+    pub(super) fn serialize_to_xml<T>(value: &T) -> SoapResult<String>
+    where
+        T: Serialize,
+    {
+        quick_xml::se::to_string(value).map_err(|e| SoapError::SerializationError(e.to_string()))
+    }
+
+    /// Serialize a value to XML with namespace on the root element
+    ///
+    /// This adds the xmlns attribute to the root element, which is required
+    /// by many SOAP services (especially .NET-based ones).
+    pub fn serialize_to_xml_with_namespace<T>(value: &T, namespace: &str) -> SoapResult<String>
+    where
+        T: Serialize,
+    {
+        // First serialize with quick-xml
+        let xml = quick_xml::se::to_string(value)
+            .map_err(|e| SoapError::SerializationError(e.to_string()))?;
+
+        // Add namespace to root element
+        let xml_with_ns = Self::add_namespace_to_root(&xml, namespace);
+
+        Ok(xml_with_ns)
+    }
+
+    /// Add namespace declaration to the root element of an XML string
+    ///
+    /// Converts `<Add>...</Add>` to `<Add xmlns="...">...</Add>`
+    pub(crate) fn add_namespace_to_root(xml: &str, namespace: &str) -> String {
+        Self::add_attribute_to_root(xml, &format!("xmlns=\"{}\"", namespace))
+    }
+
+    /// Add an attribute to the root element of an XML string
+    ///
+    /// Converts `<Add>...</Add>` to `<Add name="value">...</Add>`
+    pub(crate) fn add_attribute_to_root(xml: &str, attribute: &str) -> String {
+        // Find the end of the first opening tag
+        if let Some(pos) = xml.find('>') {
+            // Self-closing tag: <Tag/> -> <Tag name="value"/>
+            let insert_pos = if pos > 0 && xml.as_bytes()[pos - 1] == b'/' {
+                pos - 1
+            } else {
+                pos
+            };
+            let mut result = String::with_capacity(xml.len() + attribute.len() + 1);
+            result.push_str(&xml[..insert_pos]);
+            result.push(' ');
+            result.push_str(attribute);
+            result.push_str(&xml[insert_pos..]);
+            result
+        } else {
+            // Invalid XML or empty - return as-is
+            xml.to_string()
+        }
+    }
+
+    /// Add namespace prefix to the root element of an XML string
+    ///
+    /// Converts `<Add>...</Add>` to `<prefix:Add xmlns:prefix="...">...</prefix:Add>`
+    /// This prevents child elements from inheriting the namespace (for elementFormDefault="unqualified")
+    pub(crate) fn add_namespace_prefix_to_root(xml: &str, namespace: &str, prefix: &str) -> String {
+        // Find the opening and closing tags
+        if let Some(start_pos) = xml.find('<') {
+            if let Some(end_pos) = xml.find('>') {
+                // Extract tag name
+                let tag_content = &xml[start_pos + 1..end_pos];
+                let tag_name = tag_content.split_whitespace().next().unwrap_or(tag_content);
+
+                // Check if self-closing
+                let is_self_closing = xml.as_bytes()[end_pos - 1] == b'/';
+
+                if is_self_closing {
+                    // Self-closing: <Tag/> -> <prefix:Tag xmlns:prefix="..."/>
+                    let insert_pos = end_pos - 1;
+                    format!(
+                        "<{}:{} xmlns:{}=\"{}\"{}",
+                        prefix,
+                        tag_name,
+                        prefix,
+                        namespace,
+                        &xml[insert_pos..]
+                    )
+                } else {
+                    // Find closing tag
+                    let closing_tag = format!("</{}>", tag_name);
+                    if let Some(close_pos) = xml.rfind(&closing_tag) {
+                        // <Tag>content</Tag> -> <prefix:Tag xmlns:prefix="...">content</prefix:Tag>
+                        let content = &xml[end_pos + 1..close_pos];
+                        format!(
+                            "<{}:{} xmlns:{}=\"{}\">{}</{}:{}>",
+                            prefix, tag_name, prefix, namespace, content, prefix, tag_name
+                        )
+                    } else {
+                        // Fallback to default namespace
+                        Self::add_namespace_to_root(xml, namespace)
+                    }
+                }
+            } else {
+                xml.to_string()
+            }
+        } else {
+            xml.to_string()
+        }
+    }
+
+    /// Fix unescaped ampersands in XML
+    ///
+    /// Replaces `&` with `&amp;` unless it's part of a valid entity reference.
+    /// This handles server bugs where text contains unescaped ampersands like "1&4".
+    pub(super) fn fix_unescaped_ampersands(xml: &str) -> String {
+        let mut result = String::with_capacity(xml.len());
+        let mut chars = xml.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            if ch == '&' {
+                // Check if this is a valid entity reference
+                let lookahead: String = chars.clone().take(10).collect();
+                if lookahead.starts_with("amp;")
+                    || lookahead.starts_with("lt;")
+                    || lookahead.starts_with("gt;")
+                    || lookahead.starts_with("quot;")
+                    || lookahead.starts_with("apos;")
+                    || lookahead.starts_with("#")
+                {
+                    // Valid entity, keep as-is
+                    result.push(ch);
+                } else {
+                    // Invalid, escape it
+                    result.push_str("&amp;");
+                }
+            } else {
+                result.push(ch);
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[test]
+    fn test_fix_unescaped_ampersands() {
+        // Test valid entities - should remain unchanged
+        assert_eq!(
+            SoapEnvelope::fix_unescaped_ampersands("&amp; &lt; &gt; &quot; &apos;"),
+            "&amp; &lt; &gt; &quot; &apos;"
+        );
+
+        // Test numeric entities - should remain unchanged
+        assert_eq!(
+            SoapEnvelope::fix_unescaped_ampersands("&#123; &#xAB;"),
+            "&#123; &#xAB;"
+        );
+
+        // Test unescaped ampersand - should be escaped
+        assert_eq!(SoapEnvelope::fix_unescaped_ampersands("1&4"), "1&amp;4");
+
+        // Test real-world example from Rotorsoft
+        assert_eq!(
+            SoapEnvelope::fix_unescaped_ampersands("<name>Ville 1&4 (E)</name>"),
+            "<name>Ville 1&amp;4 (E)</name>"
+        );
+
+        // Test mixed content
+        assert_eq!(
+            SoapEnvelope::fix_unescaped_ampersands("A&B &amp; C&D"),
+            "A&amp;B &amp; C&amp;D"
+        );
+    }
+
+    #[test]
+    fn test_element_form_qualified() {
+        #[derive(Serialize)]
+        struct TestRequest {
+            user_name: String,
+            password: String,
+        }
+
+        let request = TestRequest {
+            user_name: "admin".to_string(),
+            password: "secret".to_string(),
+        };
+
+        // Test with element_form_qualified = true (default)
+        let envelope_qualified =
+            SoapEnvelope::build_soap11(&request, Some("urn:test"), true).unwrap();
+
+        // Should have namespace on root and children inherit it
+        assert!(envelope_qualified.contains("<TestRequest xmlns=\"urn:test\">"));
+
+        // Test with element_form_qualified = false (unqualified children)
+        let envelope_unqualified =
+            SoapEnvelope::build_soap11(&request, Some("urn:test"), false).unwrap();
+
+        // Should have namespace prefix on root element
+        assert!(envelope_unqualified.contains("<ns:TestRequest xmlns:ns=\"urn:test\">"));
+        assert!(envelope_unqualified.contains("</ns:TestRequest>"));
+        // Child elements should have NO prefix (no namespace)
+        assert!(envelope_unqualified.contains("<user_name>admin</user_name>"));
+        assert!(envelope_unqualified.contains("<password>secret</password>"));
+    }
+}
@@ -1,5 +1,7 @@
 //! Error types for SOAP client runtime
 
+use crate::envelope::SoapVersion;
+use std::fmt;
 use thiserror::Error;
 
 /// Result type for SOAP operations
@@ -17,12 +19,8 @@ pub enum SoapError {
     XmlError(String),
 
     /// SOAP fault received from server
-    #[error("SOAP fault: {code} - {message}")]
-    SoapFault {
-        code: String,
-        message: String,
-        detail: Option<String>,
-    },
+    #[error("SOAP fault: {0}")]
+    SoapFault(Box<SoapFault>),
 
     /// Serialization error
     #[error("Serialization error: {0}")]
@@ -44,11 +42,181 @@ pub enum SoapError {
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
+    /// WS-Security processing failed
+    #[error("WS-Security error: {0}")]
+    SecurityError(String),
+
+    /// WS-Addressing processing failed
+    #[error("WS-Addressing error: {0}")]
+    AddressingError(String),
+
     /// Other errors
     #[error(transparent)]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
 
+impl SoapError {
+    /// Get the SOAP fault, if this error is one
+    pub fn fault(&self) -> Option<&SoapFault> {
+        match self {
+            SoapError::SoapFault(fault) => Some(fault),
+            _ => None,
+        }
+    }
+
+    /// Deserialize the detail of a SOAP fault
+    ///
+    /// Returns `None` unless this is a [`SoapError::SoapFault`] whose detail
+    /// starts with an element of the local name `element` that deserializes
+    /// into `T`. Generated clients use this to map the faults declared for
+    /// an operation to their types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use soapus_runtime::{FaultReason, SoapError, SoapFault};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct OrderNotFound {
+    ///     #[serde(rename = "orderId")]
+    ///     order_id: String,
+    /// }
+    ///
+    /// let error = SoapError::SoapFault(Box::new(SoapFault {
+    ///     code: "soap:Client".to_string(),
+    ///     reasons: vec![FaultReason::new("Order not found")],
+    ///     detail: Some("<OrderNotFound><orderId>42</orderId></OrderNotFound>".to_string()),
+    ///     ..SoapFault::default()
+    /// }));
+    /// let detail: OrderNotFound = error.fault_detail("OrderNotFound").unwrap();
+    /// assert_eq!(detail.order_id, "42");
+    /// ```
+    pub fn fault_detail<T>(&self, element: &str) -> Option<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        use quick_xml::events::Event;
+        use quick_xml::Reader;
+
+        let detail = self.fault()?.detail.as_deref()?;
+
+        // Only the first element of the detail is considered
+        let mut reader = Reader::from_str(detail);
+        let matches = loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    break e.local_name().as_ref() == element.as_bytes()
+                }
+                Ok(Event::Eof) | Err(_) => break false,
+                _ => {}
+            }
+        };
+        if !matches {
+            return None;
+        }
+
+        quick_xml::de::from_str(detail).ok()
+    }
+}
+
+/// A SOAP fault, as sent by the server in place of a response
+///
+/// Models both fault formats. SOAP 1.1:
+///
+/// ```xml
+/// <soap:Fault>
+///   <faultcode>soap:Server</faultcode>
+///   <faultstring>Internal error</faultstring>
+///   <faultactor>http://example.com/gateway</faultactor>
+///   <detail>...</detail>
+/// </soap:Fault>
+/// ```
+///
+/// SOAP 1.2:
+///
+/// ```xml
+/// <env:Fault>
+///   <env:Code>
+///     <env:Value>env:Sender</env:Value>
+///     <env:Subcode><env:Value>m:InvalidOrder</env:Value></env:Subcode>
+///   </env:Code>
+///   <env:Reason>
+///     <env:Text xml:lang="en">Invalid order</env:Text>
+///     <env:Text xml:lang="de">Ungültige Bestellung</env:Text>
+///   </env:Reason>
+///   <env:Node>http://example.com/gateway</env:Node>
+///   <env:Role>http://www.w3.org/2003/05/soap-envelope/role/next</env:Role>
+///   <env:Detail>...</env:Detail>
+/// </env:Fault>
+/// ```
+///
+/// Codes are kept as written, including their namespace prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SoapFault {
+    /// SOAP version of the envelope the fault was received in
+    pub version: SoapVersion,
+    /// Fault code: `faultcode` (1.1) or `Code/Value` (1.2)
+    pub code: String,
+    /// Chain of `Code/Subcode/Value`s (1.2), outermost first
+    pub subcodes: Vec<String>,
+    /// Human readable reasons: `faultstring` (1.1) or each `Reason/Text` (1.2)
+    pub reasons: Vec<FaultReason>,
+    /// URI of the node that generated the fault: `Node` (1.2)
+    pub node: Option<String>,
+    /// Role the faulting node operated in: `faultactor` (1.1) or `Role` (1.2)
+    pub role: Option<String>,
+    /// Content of the `detail` (1.1) or `Detail` (1.2) element as raw XML
+    pub detail: Option<String>,
+}
+
+impl SoapFault {
+    /// Get the first reason text, or an empty string if there is none
+    pub fn message(&self) -> &str {
+        self.reasons
+            .first()
+            .map(|reason| reason.text.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Get the reason text in the given language (`xml:lang`, e.g. "en")
+    pub fn reason(&self, lang: &str) -> Option<&str> {
+        self.reasons
+            .iter()
+            .find(|reason| reason.lang.as_deref() == Some(lang))
+            .map(|reason| reason.text.as_str())
+    }
+}
+
+impl fmt::Display for SoapFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)?;
+        for subcode in &self.subcodes {
+            write!(f, " / {}", subcode)?;
+        }
+        write!(f, " - {}", self.message())
+    }
+}
+
+/// A reason text of a SOAP fault
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FaultReason {
+    /// The text
+    pub text: String,
+    /// Language of the text (`xml:lang`), if declared
+    pub lang: Option<String>,
+}
+
+impl FaultReason {
+    /// Create a reason without language
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            lang: None,
+        }
+    }
+}
+
 impl From<quick_xml::Error> for SoapError {
     fn from(err: quick_xml::Error) -> Self {
         SoapError::XmlError(err.to_string())
@@ -0,0 +1,271 @@
+//! SOAP header blocks
+//!
+//! A header block is a child element of the `<soap:Header>` of an envelope.
+//! Blocks are serialized when they are created, so that values of different
+//! types can be sent together. The `mustUnderstand`, `actor`/`role` and
+//! `relay` attributes are added when the envelope is built, with the prefix
+//! of its SOAP version.
+
+use crate::envelope::{SoapEnvelope, SoapVersion};
+use crate::error::{SoapError, SoapResult};
+use serde::Serialize;
+
+/// A child element of the `<soap:Header>` of an envelope
+///
+/// # Example
+///
+/// ```
+/// use serde::Serialize;
+/// use soapus_runtime::HeaderBlock;
+///
+/// #[derive(Serialize)]
+/// struct AuthHeader {
+///     #[serde(rename = "Username")]
+///     username: String,
+/// }
+///
+/// let header = HeaderBlock::new("AuthHeader", &AuthHeader { username: "admin".to_string() })
+///     .unwrap()
+///     .with_namespace("http://tempuri.org/", true);
+/// assert_eq!(
+///     header.xml(),
+///     r#"<AuthHeader xmlns="http://tempuri.org/"><Username>admin</Username></AuthHeader>"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderBlock {
+    xml: String,
+    must_understand: bool,
+    role: Option<String>,
+    relay: bool,
+}
+
+impl HeaderBlock {
+    /// Serialize a value as a header block named `name`
+    ///
+    /// The name replaces the one of the value's type, so that simple values
+    /// can be sent as header blocks as well.
+    pub fn new<T>(name: &str, value: &T) -> SoapResult<Self>
+    where
+        T: Serialize + ?Sized,
+    {
+        let xml = quick_xml::se::to_string_with_root(name, value)
+            .map_err(|e| SoapError::SerializationError(e.to_string()))?;
+        Ok(Self::from_xml(xml))
+    }
+
+    /// Serialize a value as a header block named after its type
+    ///
+    /// The element is named like the root element of the value in a SOAP
+    /// body, after the type or its `#[serde(rename)]`.
+    pub fn from_value<T>(value: &T) -> SoapResult<Self>
+    where
+        T: Serialize,
+    {
+        let xml = quick_xml::se::to_string(value)
+            .map_err(|e| SoapError::SerializationError(e.to_string()))?;
+        Ok(Self::from_xml(xml))
+    }
+
+    /// Create a header block from the XML of its element
+    ///
+    /// The XML is sent as is, it must be a single well-formed element.
+    pub fn from_xml(xml: impl Into<String>) -> Self {
+        Self {
+            xml: xml.into(),
+            must_understand: false,
+            role: None,
+            relay: false,
+        }
+    }
+
+    /// Qualify the header block with a namespace
+    ///
+    /// # Arguments
+    ///
+    /// * `element_form_qualified` - If false, the namespace is only added to
+    ///   the header element, not inherited by its children (for
+    ///   elementFormDefault="unqualified" in XSD)
+    pub fn with_namespace(self, namespace: &str, element_form_qualified: bool) -> Self {
+        let xml = if element_form_qualified {
+            SoapEnvelope::add_namespace_to_root(&self.xml, namespace)
+        } else {
+            SoapEnvelope::add_namespace_prefix_to_root(&self.xml, namespace, "ns")
+        };
+        Self { xml, ..self }
+    }
+
+    /// Require the receiver to process the header block (`mustUnderstand`)
+    pub fn with_must_understand(self, must_understand: bool) -> Self {
+        Self {
+            must_understand,
+            ..self
+        }
+    }
+
+    /// Target the header block at an intermediary
+    ///
+    /// Sent as `actor` with SOAP 1.1 and as `role` with SOAP 1.2.
+    pub fn with_role(self, role: impl Into<String>) -> Self {
+        Self {
+            role: Some(role.into()),
+            ..self
+        }
+    }
+
+    /// Let an intermediary relay the header block if it does not process it
+    ///
+    /// The `relay` attribute only exists in SOAP 1.2, it is left out of SOAP
+    /// 1.1 envelopes.
+    pub fn with_relay(self, relay: bool) -> Self {
+        Self { relay, ..self }
+    }
+
+    /// Whether the receiver must process the header block
+    pub fn must_understand(&self) -> bool {
+        self.must_understand
+    }
+
+    /// Get the actor or role the header block is targeted at
+    pub fn role(&self) -> Option<&str> {
+        self.role.as_deref()
+    }
+
+    /// Whether an intermediary may relay the header block
+    pub fn relay(&self) -> bool {
+        self.relay
+    }
+
+    /// Get the XML of the header block, without the SOAP attributes
+    pub fn xml(&self) -> &str {
+        &self.xml
+    }
+
+    /// Get the local name of the header element
+    pub fn name(&self) -> &str {
+        let tag = self.xml.trim_start().trim_start_matches('<');
+        let end = tag
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(tag.len());
+        let name = &tag[..end];
+        name.rsplit(':').next().unwrap_or(name)
+    }
+
+    /// Deserialize the header block
+    ///
+    /// This is mostly useful for the header blocks of a response, see
+    /// [`SoapResponse`](crate::client::SoapResponse).
+    pub fn deserialize<T>(&self) -> SoapResult<T>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        quick_xml::de::from_str(&self.xml)
+            .map_err(|e| SoapError::DeserializationError(e.to_string()))
+    }
+
+    /// Get the XML of the header block in an envelope of the given SOAP version
+    ///
+    /// The SOAP attributes are qualified with the prefix of the envelope
+    /// namespace used by [`SoapEnvelope`].
+    pub fn to_xml(&self, version: SoapVersion) -> String {
+        let prefix = match version {
+            SoapVersion::Soap11 => "soap",
+            SoapVersion::Soap12 => "env",
+        };
+        let mut xml = self.xml.clone();
+        if self.must_understand {
+            let value = match version {
+                SoapVersion::Soap11 => "1",
+                SoapVersion::Soap12 => "true",
+            };
+            xml = SoapEnvelope::add_attribute_to_root(
+                &xml,
+                &format!("{}:mustUnderstand=\"{}\"", prefix, value),
+            );
+        }
+        if let Some(role) = &self.role {
+            let name = match version {
+                SoapVersion::Soap11 => "actor",
+                SoapVersion::Soap12 => "role",
+            };
+            xml = SoapEnvelope::add_attribute_to_root(
+                &xml,
+                &format!("{}:{}=\"{}\"", prefix, name, escape_attribute(role)),
+            );
+        }
+        if self.relay && version == SoapVersion::Soap12 {
+            xml = SoapEnvelope::add_attribute_to_root(&xml, &format!("{}:relay=\"true\"", prefix));
+        }
+        xml
+    }
+}
+
+/// Escape a value for use in a double-quoted XML attribute
+fn escape_attribute(value: &str) -> String {
+    quick_xml::escape::escape(value).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_header_block() {
+        let header = HeaderBlock::new("SessionId", "abc&123").unwrap();
+        assert_eq!(header.xml(), "<SessionId>abc&amp;123</SessionId>");
+    }
+
+    #[test]
+    fn test_header_block_attributes() {
+        let header = HeaderBlock::new("Token", "abc")
+            .unwrap()
+            .with_must_understand(true)
+            .with_role("http://example.com/gateway")
+            .with_relay(true);
+
+        assert_eq!(
+            header.to_xml(SoapVersion::Soap11),
+            r#"<Token soap:mustUnderstand="1" soap:actor="http://example.com/gateway">abc</Token>"#
+        );
+        assert_eq!(
+            header.to_xml(SoapVersion::Soap12),
+            r#"<Token env:mustUnderstand="true" env:role="http://example.com/gateway" env:relay="true">abc</Token>"#
+        );
+        assert_eq!(header.xml(), "<Token>abc</Token>");
+    }
+
+    #[test]
+    fn test_header_block_from_value() {
+        #[derive(Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct SessionHeader {
+            #[serde(rename = "SessionId")]
+            session_id: String,
+        }
+
+        let session = SessionHeader {
+            session_id: "42".to_string(),
+        };
+        let header = HeaderBlock::from_value(&session).unwrap();
+        assert_eq!(header.name(), "SessionHeader");
+        assert_eq!(header.deserialize::<SessionHeader>().unwrap(), session);
+
+        let header = HeaderBlock::from_xml(r#"<s:SessionHeader xmlns:s="urn:s"/>"#);
+        assert_eq!(header.name(), "SessionHeader");
+    }
+
+    #[test]
+    fn test_unqualified_header_block() {
+        #[derive(Serialize)]
+        struct Session {
+            id: u32,
+        }
+
+        let header = HeaderBlock::new("Session", &Session { id: 7 })
+            .unwrap()
+            .with_namespace("urn:sessions", false);
+        assert_eq!(
+            header.xml(),
+            r#"<ns:Session xmlns:ns="urn:sessions"><id>7</id></ns:Session>"#
+        );
+    }
+}
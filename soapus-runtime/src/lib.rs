@@ -70,18 +70,31 @@
 //! - `tracing` (default) - Structured logging and distributed tracing support
 //! - `opentelemetry` - OpenTelemetry/Jaeger integration for distributed tracing
 //! - `metrics` - Prometheus metrics collection
+//! - `ws-security` - WS-Security headers, signatures and encryption
+//! - `ws-addressing` - WS-Addressing headers and message correlation
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![warn(rustdoc::broken_intra_doc_links)]
 // Note: missing_docs is intentionally not enabled for internal structures
 
+#[cfg(feature = "ws-addressing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ws-addressing")))]
+pub mod addressing;
 pub mod client;
+pub mod encoding;
 pub mod envelope;
 pub mod error;
+pub mod header;
+pub mod namespace;
+#[cfg(feature = "ws-security")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ws-security")))]
+pub mod security;
+pub mod xsi;
 
-pub use client::SoapClient;
+pub use client::{SoapClient, SoapResponse};
 pub use envelope::{SoapEnvelope, SoapVersion};
-pub use error::{SoapError, SoapResult};
+pub use error::{FaultReason, SoapError, SoapFault, SoapResult};
+pub use header::HeaderBlock;
 
 // Re-export commonly used types
 pub use serde::{Deserialize, Serialize};
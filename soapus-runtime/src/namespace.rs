@@ -0,0 +1,316 @@
+//! Serialization of elements belonging to a namespace of their own
+//!
+//! Elements referenced from another schema (`<xsd:element ref="cust:Customer"/>`)
+//! are in the target namespace of that schema, not in the one of the enclosing
+//! element. Generated code serializes such fields with [`serialize_qualified`],
+//! which declares the namespace as default namespace on the element itself:
+//!
+//! ```xml
+//! <Order xmlns="http://example.com/orders">
+//!   <Customer xmlns="http://example.com/customers">
+//!     <name>Jane</name>
+//!   </Customer>
+//! </Order>
+//! ```
+//!
+//! Deserialization needs no counterpart, elements are matched by local name.
+
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, SerializeStruct, Serializer};
+
+/// Key of the default namespace declaration, written as XML attribute
+const XMLNS_KEY: &str = "@xmlns";
+
+/// Key of the text content of an element
+const TEXT_KEY: &str = "$text";
+
+/// Serialize a field value as element(s) of `namespace`
+///
+/// Works for structs, simple values and enumerations, as well as `Option`s
+/// and `Vec`s of them, where each element gets the declaration.
+///
+/// Intended for `#[serde(serialize_with = "...")]` through a function of the
+/// generated struct that supplies the namespace.
+pub fn serialize_qualified<T, S>(
+    value: &T,
+    namespace: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize + ?Sized,
+    S: Serializer,
+{
+    value.serialize(QualifiedSerializer {
+        inner: serializer,
+        namespace,
+    })
+}
+
+/// A value serialized with [`serialize_qualified`]
+struct Qualified<'a, T: ?Sized> {
+    value: &'a T,
+    namespace: &'a str,
+}
+
+impl<T: Serialize + ?Sized> Serialize for Qualified<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_qualified(self.value, self.namespace, serializer)
+    }
+}
+
+/// Serializer adding the namespace declaration to the element written by `inner`
+struct QualifiedSerializer<'a, S> {
+    inner: S,
+    namespace: &'a str,
+}
+
+impl<S: Serializer> QualifiedSerializer<'_, S> {
+    /// Write a simple value as text of an element carrying the declaration
+    fn text<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        let mut element = self.inner.serialize_struct("", 2)?;
+        element.serialize_field(XMLNS_KEY, self.namespace)?;
+        element.serialize_field(TEXT_KEY, value)?;
+        element.end()
+    }
+}
+
+/// Sequence serializer declaring the namespace on each item
+struct QualifiedSeq<'a, S> {
+    inner: S,
+    namespace: &'a str,
+}
+
+impl<S: SerializeSeq> SerializeSeq for QualifiedSeq<'_, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        self.inner.serialize_element(&Qualified {
+            value,
+            namespace: self.namespace,
+        })
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+
+macro_rules! serialize_text {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(
+            fn $method(self, value: $type) -> Result<S::Ok, S::Error> {
+                self.text(&value)
+            }
+        )*
+    };
+}
+
+impl<'a, S: Serializer> Serializer for QualifiedSerializer<'a, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = QualifiedSeq<'a, S::SerializeSeq>;
+    type SerializeTuple = S::SerializeTuple;
+    type SerializeTupleStruct = S::SerializeTupleStruct;
+    type SerializeTupleVariant = S::SerializeTupleVariant;
+    type SerializeMap = S::SerializeMap;
+    type SerializeStruct = S::SerializeStruct;
+    type SerializeStructVariant = S::SerializeStructVariant;
+
+    serialize_text! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_bytes(value)
+    }
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_none()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_struct(name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        // Enumeration values are written as text
+        self.text(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner
+            .serialize_newtype_variant(name, variant_index, variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Ok(QualifiedSeq {
+            inner: self.inner.serialize_seq(len)?,
+            namespace: self.namespace,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        self.inner.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        self.inner.serialize_tuple_struct(name, len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        self.inner
+            .serialize_tuple_variant(name, variant_index, variant, len)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        let mut map = self.inner.serialize_map(len.map(|len| len + 1))?;
+        map.serialize_entry(XMLNS_KEY, self.namespace)?;
+        Ok(map)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        let mut element = self.inner.serialize_struct(name, len + 1)?;
+        element.serialize_field(XMLNS_KEY, self.namespace)?;
+        Ok(element)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        self.inner
+            .serialize_struct_variant(name, variant_index, variant, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Customer {
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Tier {
+        #[serde(rename = "gold")]
+        Gold,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        id: String,
+        #[serde(rename = "Customer", serialize_with = "Order::serialize_customer")]
+        customer: Customer,
+        #[serde(rename = "Note", serialize_with = "Order::serialize_note")]
+        note: Vec<String>,
+        #[serde(rename = "Tier", serialize_with = "Order::serialize_tier")]
+        tier: Option<Tier>,
+    }
+
+    impl Order {
+        fn serialize_customer<S: Serializer>(
+            value: &Customer,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize_qualified(value, "urn:customers", serializer)
+        }
+
+        fn serialize_note<S: Serializer>(
+            value: &Vec<String>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize_qualified(value, "urn:notes", serializer)
+        }
+
+        fn serialize_tier<S: Serializer>(
+            value: &Option<Tier>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize_qualified(value, "urn:tiers", serializer)
+        }
+    }
+
+    #[test]
+    fn test_serialize_qualified() {
+        let order = Order {
+            id: "1".to_string(),
+            customer: Customer {
+                name: "Jane".to_string(),
+            },
+            note: vec!["a".to_string(), "b".to_string()],
+            tier: Some(Tier::Gold),
+        };
+
+        let xml = quick_xml::se::to_string(&order).unwrap();
+        assert_eq!(
+            xml,
+            "<Order><id>1</id>\
+             <Customer xmlns=\"urn:customers\"><name>Jane</name></Customer>\
+             <Note xmlns=\"urn:notes\">a</Note><Note xmlns=\"urn:notes\">b</Note>\
+             <Tier xmlns=\"urn:tiers\">gold</Tier></Order>"
+        );
+
+        // The declarations are ignored when reading the elements back
+        let parsed: Order = quick_xml::de::from_str(&xml).unwrap();
+        assert_eq!(parsed, order);
+    }
+}
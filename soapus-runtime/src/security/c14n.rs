@@ -0,0 +1,376 @@
+//! Exclusive XML canonicalization (`xml-exc-c14n`, without comments)
+//!
+//! Canonicalizes a single element of a document as selected by a reference
+//! of a signature. Only the namespace declarations visibly used by an element
+//! or its attributes are rendered, and only where the nearest rendered
+//! ancestor does not already declare them.
+
+use crate::error::{SoapError, SoapResult};
+use quick_xml::escape::unescape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// URI of the exclusive canonicalization algorithm
+pub const EXC_C14N: &str = "http://www.w3.org/2001/10/xml-exc-c14n#";
+
+/// Namespace bound to the `xml` prefix
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The element of a document to canonicalize
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Target<'a> {
+    /// The only element with an `Id` attribute (e.g. `wsu:Id`) of this value
+    Id(&'a str),
+    /// The first element with this namespace and local name
+    Element {
+        namespace: &'a str,
+        local_name: &'a str,
+    },
+}
+
+/// Namespace declarations of one element, as (prefix, namespace) pairs
+///
+/// The default namespace has the empty prefix.
+type Declarations = Vec<(String, String)>;
+
+/// Canonicalize the target element of `xml`
+///
+/// `inclusive_prefixes` are rendered wherever they are in scope, as listed
+/// by an `ec:InclusiveNamespaces` `PrefixList` (`#default` is the default
+/// namespace). An `Id` target must be unique in the document, so that a
+/// reference cannot be redirected by a second element with the same Id.
+pub(crate) fn canonicalize(
+    xml: &str,
+    target: Target<'_>,
+    inclusive_prefixes: &[&str],
+) -> SoapResult<String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().expand_empty_elements = true;
+
+    let inclusive: Vec<&str> = inclusive_prefixes
+        .iter()
+        .map(|prefix| if *prefix == "#default" { "" } else { prefix })
+        .collect();
+
+    // Declarations of the open elements and of the open rendered elements
+    let mut scope: Vec<Declarations> = Vec::new();
+    let mut rendered: Vec<Declarations> = Vec::new();
+    let mut output = String::new();
+    let mut depth = 0usize;
+    let mut found = false;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let (declarations, attributes) = read_attributes(&e)?;
+                scope.push(declarations);
+
+                if depth == 0 {
+                    if !is_target(&e, &attributes, &scope, target)? {
+                        continue;
+                    }
+                    if found {
+                        return Err(duplicate_id(target));
+                    }
+                    found = true;
+                }
+                depth += 1;
+                write_start(
+                    &e,
+                    &attributes,
+                    &scope,
+                    &mut rendered,
+                    &inclusive,
+                    &mut output,
+                )?;
+            }
+            Event::End(e) => {
+                scope.pop();
+                if depth > 0 {
+                    output.push_str("</");
+                    output.push_str(as_str(e.name().as_ref())?);
+                    output.push('>');
+                    rendered.pop();
+                    depth -= 1;
+                    // Only an Id target is checked for duplicates
+                    if depth == 0 && matches!(target, Target::Element { .. }) {
+                        return Ok(output);
+                    }
+                }
+            }
+            Event::Text(e) if depth > 0 => {
+                let raw = normalize_line_breaks(as_str(&e)?);
+                let text = unescape(&raw).map_err(quick_xml::Error::from)?;
+                output.push_str(&escape_text(&text));
+            }
+            Event::CData(e) if depth > 0 => {
+                output.push_str(&escape_text(&normalize_line_breaks(as_str(&e)?)));
+            }
+            Event::PI(e) if depth > 0 => {
+                output.push_str("<?");
+                output.push_str(as_str(&e)?);
+                output.push_str("?>");
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if found {
+        Ok(output)
+    } else {
+        Err(SoapError::SecurityError(match target {
+            Target::Id(id) => format!("No element with Id \"{}\"", id),
+            Target::Element {
+                namespace,
+                local_name,
+            } => format!("No element {{{}}}{}", namespace, local_name),
+        }))
+    }
+}
+
+/// Split the attributes of an element into namespace declarations and others
+///
+/// Attribute values are normalized the way an XML parser does: whitespace
+/// characters become spaces, then references are resolved.
+fn read_attributes(e: &BytesStart<'_>) -> SoapResult<(Declarations, Vec<(String, String)>)> {
+    let mut declarations = Vec::new();
+    let mut attributes = Vec::new();
+    for attribute in e.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        let name = as_str(attribute.key.as_ref())?.to_string();
+        let raw = as_str(&attribute.value)?.replace(['\t', '\n', '\r'], " ");
+        let value = unescape(&raw).map_err(quick_xml::Error::from)?.into_owned();
+        if name == "xmlns" {
+            declarations.push((String::new(), value));
+        } else if let Some(prefix) = name.strip_prefix("xmlns:") {
+            declarations.push((prefix.to_string(), value));
+        } else {
+            attributes.push((name, value));
+        }
+    }
+    Ok((declarations, attributes))
+}
+
+/// Check whether an element is the one to canonicalize
+fn is_target(
+    e: &BytesStart<'_>,
+    attributes: &[(String, String)],
+    scope: &[Declarations],
+    target: Target<'_>,
+) -> SoapResult<bool> {
+    match target {
+        Target::Id(id) => Ok(attributes
+            .iter()
+            .any(|(name, value)| is_id_attribute(name) && value == id)),
+        Target::Element {
+            namespace,
+            local_name,
+        } => {
+            let name = e.name();
+            let (prefix, local) = split_name(as_str(name.as_ref())?);
+            Ok(local == local_name && lookup(scope, prefix) == Some(namespace))
+        }
+    }
+}
+
+/// Check whether an attribute identifies its element (`Id`, `wsu:Id`, `ID`)
+pub(crate) fn is_id_attribute(name: &str) -> bool {
+    let (_, local) = split_name(name);
+    local == "Id" || name == "ID"
+}
+
+/// Write the canonical start tag of an element and record its rendered namespaces
+fn write_start(
+    e: &BytesStart<'_>,
+    attributes: &[(String, String)],
+    scope: &[Declarations],
+    rendered: &mut Vec<Declarations>,
+    inclusive: &[&str],
+    output: &mut String,
+) -> SoapResult<()> {
+    let qname = e.name();
+    let name = as_str(qname.as_ref())?;
+
+    // Prefixes visibly utilized by the element and its attributes
+    let mut prefixes: Vec<&str> = vec![split_name(name).0];
+    for (attribute, _) in attributes {
+        let (prefix, _) = split_name(attribute);
+        if !prefix.is_empty() && prefix != "xml" {
+            prefixes.push(prefix);
+        }
+    }
+    prefixes.extend(
+        inclusive
+            .iter()
+            .filter(|prefix| lookup(scope, prefix).is_some()),
+    );
+    prefixes.sort_unstable();
+    prefixes.dedup();
+
+    let mut declarations = Declarations::new();
+    for prefix in prefixes {
+        let namespace = lookup(scope, prefix).unwrap_or_default();
+        if prefix.is_empty() {
+            // The empty default namespace is only rendered to undeclare another one
+            if lookup(rendered, "").unwrap_or_default() != namespace {
+                declarations.push((String::new(), namespace.to_string()));
+            }
+        } else if namespace.is_empty() {
+            return Err(SoapError::SecurityError(format!(
+                "Namespace prefix \"{}\" is not declared",
+                prefix
+            )));
+        } else if lookup(rendered, prefix) != Some(namespace) {
+            declarations.push((prefix.to_string(), namespace.to_string()));
+        }
+    }
+
+    // Attributes are sorted by namespace, then local name
+    let mut sorted: Vec<(&str, &str, &str, &str)> = Vec::new();
+    for (attribute, value) in attributes {
+        let (prefix, local) = split_name(attribute);
+        let namespace = match prefix {
+            "" => "",
+            "xml" => XML_NAMESPACE,
+            _ => lookup(scope, prefix).unwrap_or_default(),
+        };
+        sorted.push((namespace, local, attribute, value));
+    }
+    sorted.sort_unstable();
+
+    output.push('<');
+    output.push_str(name);
+    for (prefix, namespace) in &declarations {
+        if prefix.is_empty() {
+            output.push_str(" xmlns=\"");
+        } else {
+            output.push_str(" xmlns:");
+            output.push_str(prefix);
+            output.push_str("=\"");
+        }
+        output.push_str(&escape_attribute(namespace));
+        output.push('"');
+    }
+    for (_, _, attribute, value) in sorted {
+        output.push(' ');
+        output.push_str(attribute);
+        output.push_str("=\"");
+        output.push_str(&escape_attribute(value));
+        output.push('"');
+    }
+    output.push('>');
+
+    rendered.push(declarations);
+    Ok(())
+}
+
+/// Find the namespace bound to a prefix by the innermost declaration
+fn lookup<'a>(scope: &'a [Declarations], prefix: &str) -> Option<&'a str> {
+    scope
+        .iter()
+        .rev()
+        .flat_map(|declarations| declarations.iter())
+        .find(|(declared, _)| declared == prefix)
+        .map(|(_, namespace)| namespace.as_str())
+}
+
+/// Split a qualified name into prefix and local name
+fn split_name(name: &str) -> (&str, &str) {
+    name.split_once(':').unwrap_or(("", name))
+}
+
+fn as_str(bytes: &[u8]) -> SoapResult<&str> {
+    std::str::from_utf8(bytes).map_err(|e| SoapError::XmlError(e.to_string()))
+}
+
+fn duplicate_id(target: Target<'_>) -> SoapError {
+    SoapError::SecurityError(format!("Several elements match {:?}", target))
+}
+
+/// Replace CR LF and single CRs with LF, as an XML parser does
+fn normalize_line_breaks(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\r' => escaped.push_str("&#xD;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' => escaped.push_str("&#x9;"),
+            '\n' => escaped.push_str("&#xA;"),
+            '\r' => escaped.push_str("&#xD;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = r#"<?xml version="1.0"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/" xmlns:unused="urn:unused" xmlns:wsu="urn:wsu">
+  <!-- ignored -->
+  <soap:Body wsu:Id="Body-1" b="2" a="1">
+    <m:Ping xmlns:m="urn:m" xmlns="urn:default"><Text>a &amp; b &gt; c<![CDATA[<d>]]></Text><Empty/></m:Ping>
+  </soap:Body>
+</soap:Envelope>"#;
+
+    #[test]
+    fn test_canonicalize_by_id() {
+        assert_eq!(
+            canonicalize(DOCUMENT, Target::Id("Body-1"), &[]).unwrap(),
+            "<soap:Body xmlns:soap=\"http://schemas.xmlsoap.org/soap/envelope/\" xmlns:wsu=\"urn:wsu\" a=\"1\" b=\"2\" wsu:Id=\"Body-1\">\n    \
+             <m:Ping xmlns:m=\"urn:m\"><Text xmlns=\"urn:default\">a &amp; b &gt; c&lt;d&gt;</Text><Empty xmlns=\"urn:default\"></Empty></m:Ping>\n  \
+             </soap:Body>"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_by_name_with_inclusive_prefixes() {
+        let target = Target::Element {
+            namespace: "urn:m",
+            local_name: "Ping",
+        };
+        assert_eq!(
+            canonicalize(DOCUMENT, target, &["unused", "#default"]).unwrap(),
+            "<m:Ping xmlns=\"urn:default\" xmlns:m=\"urn:m\" xmlns:unused=\"urn:unused\">\
+             <Text>a &amp; b &gt; c&lt;d&gt;</Text><Empty></Empty></m:Ping>"
+        );
+    }
+
+    #[test]
+    fn test_undeclared_default_namespace() {
+        let xml = r#"<a xmlns="urn:a" Id="x"><b xmlns=""><c/></b></a>"#;
+        assert_eq!(
+            canonicalize(xml, Target::Id("x"), &[]).unwrap(),
+            r#"<a xmlns="urn:a" Id="x"><b xmlns=""><c></c></b></a>"#
+        );
+    }
+
+    #[test]
+    fn test_duplicate_id() {
+        let xml = r#"<a><b Id="x"/><c Id="x"/></a>"#;
+        assert!(canonicalize(xml, Target::Id("x"), &[]).is_err());
+        assert!(canonicalize(xml, Target::Id("y"), &[]).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! `xsd:dateTime` values of timestamps and SAML assertions
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Format a point in time as `xsd:dateTime` in UTC with milliseconds
+///
+/// Times before the Unix epoch are formatted as the epoch.
+pub(crate) fn format_date_time(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    let seconds_of_day = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Parse an `xsd:dateTime` with time zone, e.g. `2026-10-16T12:00:00.000Z`
+///
+/// Returns `None` for malformed times, times without time zone and times
+/// before the Unix epoch.
+pub(crate) fn parse_date_time(text: &str) -> Option<SystemTime> {
+    let (date, time) = text.trim().split_once('T')?;
+    let mut date_fields = date.splitn(3, '-');
+    let year: i64 = date_fields.next()?.parse().ok()?;
+    let month: u32 = date_fields.next()?.parse().ok()?;
+    let day: u32 = date_fields.next()?.parse().ok()?;
+
+    let (time, offset) = if let Some(time) = time.strip_suffix('Z') {
+        (time, 0)
+    } else {
+        let split = time.rfind(['+', '-'])?;
+        let (hours, minutes) = time[split + 1..].split_once(':')?;
+        let offset = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+        let sign = if time[split..].starts_with('-') {
+            -1
+        } else {
+            1
+        };
+        (&time[..split], sign * offset)
+    };
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time_fields = time.splitn(3, ':');
+    let hour: i64 = time_fields.next()?.parse().ok()?;
+    let minute: i64 = time_fields.next()?.parse().ok()?;
+    let second: i64 = time_fields.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 24 || minute > 59 {
+        return None;
+    }
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<9}", fraction).get(..9)?.parse().ok()?
+    };
+
+    let seconds =
+        days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset;
+    let seconds = u64::try_from(seconds).ok()?;
+    Some(UNIX_EPOCH + Duration::new(seconds, nanos))
+}
+
+/// Convert a (year, month, day) date to days since the Unix epoch
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let day_of_year = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Convert days since the Unix epoch to a (year, month, day) date
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-10-16T12:00:00Z
+    fn noon() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_792_152_000)
+    }
+
+    #[test]
+    fn test_format_date_time() {
+        assert_eq!(format_date_time(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_date_time(noon()), "2026-10-16T12:00:00.000Z");
+        assert_eq!(
+            format_date_time(UNIX_EPOCH + Duration::from_millis(951_868_799_250)),
+            "2000-02-29T23:59:59.250Z"
+        );
+    }
+
+    #[test]
+    fn test_parse_date_time() {
+        assert_eq!(parse_date_time("2026-10-16T12:00:00Z"), Some(noon()));
+        assert_eq!(
+            parse_date_time("2026-10-16T14:00:00.000+02:00"),
+            Some(noon())
+        );
+        assert_eq!(
+            parse_date_time("2000-02-29T23:59:59.25Z"),
+            Some(UNIX_EPOCH + Duration::from_millis(951_868_799_250))
+        );
+        assert_eq!(parse_date_time("2026-10-16T12:00:00"), None);
+        assert_eq!(parse_date_time("1969-12-31T23:59:59Z"), None);
+        assert_eq!(parse_date_time("2026-13-01T00:00:00Z"), None);
+    }
+}
@@ -0,0 +1,370 @@
+//! Decryption of XML Encryption in SOAP responses
+//!
+//! Encrypted keys are decrypted with the private key of the recipient,
+//! encrypted data with the AES key they hold.
+
+use super::encryption::{EncryptionAlgorithm, RSA_OAEP_MGF1P, XENC_NAMESPACE};
+use super::signature::{decode_base64, digest_method};
+use super::x509::openssl_error;
+use super::X509Credentials;
+use crate::envelope::{SOAP_11_ENVELOPE_NS, SOAP_12_ENVELOPE_NS};
+use crate::error::{SoapError, SoapResult};
+use openssl::encrypt::Decrypter;
+use openssl::hash::MessageDigest;
+use openssl::rsa::Padding;
+use quick_xml::events::Event;
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+use serde::Deserialize;
+use std::ops::Range;
+
+const RSA_OAEP: &str = "http://www.w3.org/2009/xmlenc11#rsa-oaep";
+const MGF1_SHA1: &str = "http://www.w3.org/2009/xmlenc11#mgf1sha1";
+const MGF1_SHA256: &str = "http://www.w3.org/2009/xmlenc11#mgf1sha256";
+const MGF1_SHA512: &str = "http://www.w3.org/2009/xmlenc11#mgf1sha512";
+
+/// Decrypt all `xenc:EncryptedData` elements of a message
+///
+/// Each is replaced by its decrypted content. The key is taken from the
+/// `ds:KeyInfo` of the data or from the `xenc:EncryptedKey` referencing it,
+/// and must be encrypted for the certificate of `credentials` with RSA-OAEP.
+/// AES-128 and AES-256 in CBC and GCM mode are supported. A message without
+/// encrypted data is returned unchanged.
+pub fn decrypt(xml: &str, credentials: &X509Credentials) -> SoapResult<String> {
+    let keys = element_spans(xml, "EncryptedKey")?
+        .into_iter()
+        .map(|span| parse::<EncryptedKey>(&xml[span]))
+        .collect::<SoapResult<Vec<_>>>()?;
+
+    let mut xml = xml.to_string();
+    while let Some(span) = element_spans(&xml, "EncryptedData")?.into_iter().next() {
+        let data: EncryptedData = parse(&xml[span.clone()])?;
+        let embedded_key = data
+            .key_info
+            .as_ref()
+            .and_then(|key_info| key_info.encrypted_key.as_ref());
+        let Some(encrypted_key) = embedded_key.or_else(|| find_key(&keys, &data)) else {
+            return Err(SoapError::SecurityError(
+                "No key for encrypted data".to_string(),
+            ));
+        };
+
+        let key = unwrap_key(encrypted_key, credentials)?;
+        let algorithm = EncryptionAlgorithm::from_uri(&data.encryption_method.algorithm)?;
+        let content = algorithm.decrypt(&key, &decode_base64(&data.cipher_data.cipher_value)?)?;
+        let content = String::from_utf8(content)
+            .map_err(|_| SoapError::SecurityError("Decrypted content is not UTF-8".to_string()))?;
+        xml.replace_range(span, &content);
+    }
+    Ok(xml)
+}
+
+/// Check that the content of the Body of a SOAP 1.1 or 1.2 envelope is encrypted
+///
+/// The Body that is a child of the Envelope must hold `xenc:EncryptedData`
+/// elements only. A SOAP fault in plain text is accepted, as faults are
+/// reported without checking a signature too.
+pub(crate) fn check_body_encrypted(xml: &str) -> SoapResult<()> {
+    let not_encrypted = || {
+        Err(SoapError::SecurityError(
+            "The SOAP Body of the response is not encrypted".to_string(),
+        ))
+    };
+    let mut reader = NsReader::from_str(xml);
+    let mut depth = 0;
+    let mut in_body = false;
+    let mut encrypted = false;
+    loop {
+        let (namespace, event) = reader.read_resolved_event()?;
+        let namespace = match namespace {
+            ResolveResult::Bound(Namespace(ns)) => ns,
+            _ => b"",
+        };
+        let in_soap_namespace = namespace == SOAP_11_ENVELOPE_NS.as_bytes()
+            || namespace == SOAP_12_ENVELOPE_NS.as_bytes();
+        match &event {
+            Event::Start(e) | Event::Empty(e) if depth == 1 => {
+                in_body = in_soap_namespace && e.local_name().as_ref() == b"Body";
+            }
+            Event::Start(e) | Event::Empty(e) if depth == 2 && in_body => {
+                match e.local_name().as_ref() {
+                    b"EncryptedData" if namespace == XENC_NAMESPACE.as_bytes() => encrypted = true,
+                    b"Fault" if in_soap_namespace => return Ok(()),
+                    _ => return not_encrypted(),
+                }
+            }
+            Event::Text(text)
+                if depth == 2 && in_body && !text.iter().all(u8::is_ascii_whitespace) =>
+            {
+                return not_encrypted();
+            }
+            Event::CData(_) if depth == 2 && in_body => return not_encrypted(),
+            Event::Eof => return not_encrypted(),
+            _ => {}
+        }
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => {
+                depth -= 1;
+                if depth == 1 && in_body {
+                    return if encrypted { Ok(()) } else { not_encrypted() };
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Find the `xenc:EncryptedKey` of encrypted data outside of its `ds:KeyInfo`
+///
+/// The data may reference the key through a `wsse:SecurityTokenReference`,
+/// or the `xenc:ReferenceList` of the key may reference the data. A single
+/// key of the message is taken for data without references.
+fn find_key<'a>(keys: &'a [EncryptedKey], data: &EncryptedData) -> Option<&'a EncryptedKey> {
+    let token_reference = data
+        .key_info
+        .as_ref()
+        .and_then(|key_info| key_info.security_token_reference.as_ref())
+        .and_then(|token_reference| token_reference.reference.as_ref())
+        .and_then(|reference| reference.uri.strip_prefix('#'));
+    if let Some(id) = token_reference {
+        return keys.iter().find(|key| key.id.as_deref() == Some(id));
+    }
+
+    let referencing = data.id.as_deref().and_then(|id| {
+        keys.iter().find(|key| {
+            key.reference_list.as_ref().is_some_and(|list| {
+                list.data_references
+                    .iter()
+                    .any(|reference| reference.uri.strip_prefix('#') == Some(id))
+            })
+        })
+    });
+    match (referencing, keys) {
+        (Some(key), _) => Some(key),
+        (None, [key]) => Some(key),
+        _ => None,
+    }
+}
+
+/// Decrypt the AES key of an `xenc:EncryptedKey` with the private key
+fn unwrap_key(encrypted_key: &EncryptedKey, credentials: &X509Credentials) -> SoapResult<Vec<u8>> {
+    let method = &encrypted_key.encryption_method;
+    let digest = match &method.digest_method {
+        Some(digest) => digest_method(&digest.algorithm)?,
+        None => MessageDigest::sha1(),
+    };
+    let mgf = match method.algorithm.as_str() {
+        RSA_OAEP_MGF1P => MessageDigest::sha1(),
+        RSA_OAEP => match method.mgf.as_ref().map(|mgf| mgf.algorithm.as_str()) {
+            None | Some(MGF1_SHA1) => MessageDigest::sha1(),
+            Some(MGF1_SHA256) => MessageDigest::sha256(),
+            Some(MGF1_SHA512) => MessageDigest::sha512(),
+            Some(other) => {
+                return Err(SoapError::SecurityError(format!(
+                    "Unsupported mask generation function {}",
+                    other
+                )))
+            }
+        },
+        other => {
+            return Err(SoapError::SecurityError(format!(
+                "Unsupported key transport {}",
+                other
+            )))
+        }
+    };
+
+    let wrapped = decode_base64(&encrypted_key.cipher_data.cipher_value)?;
+    let decrypter = Decrypter::new(credentials.private_key())
+        .and_then(|mut decrypter| {
+            decrypter.set_rsa_padding(Padding::PKCS1_OAEP)?;
+            decrypter.set_rsa_oaep_md(digest)?;
+            decrypter.set_rsa_mgf1_md(mgf)?;
+            Ok(decrypter)
+        })
+        .map_err(openssl_error("Failed to decrypt key"))?;
+    let mut key = vec![0; decrypter.decrypt_len(&wrapped).unwrap_or_default()];
+    let length = decrypter
+        .decrypt(&wrapped, &mut key)
+        .map_err(openssl_error("Failed to decrypt key"))?;
+    key.truncate(length);
+    Ok(key)
+}
+
+/// Get the byte ranges of the `xenc` elements with the given local name
+fn element_spans(xml: &str, local_name: &str) -> SoapResult<Vec<Range<usize>>> {
+    let mut reader = NsReader::from_str(xml);
+    let mut spans = Vec::new();
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event()?;
+        let (Event::Start(e) | Event::Empty(e)) = &event else {
+            if let Event::Eof = event {
+                return Ok(spans);
+            }
+            continue;
+        };
+        let is_match = e.local_name().as_ref() == local_name.as_bytes()
+            && matches!(
+                reader.resolve_element(e.name()).0,
+                ResolveResult::Bound(Namespace(ns)) if ns == XENC_NAMESPACE.as_bytes()
+            );
+        if !is_match {
+            continue;
+        }
+        if let Event::Start(e) = &event {
+            reader.read_to_end(e.name())?;
+        }
+        spans.push(start..reader.buffer_position() as usize);
+    }
+}
+
+fn parse<T>(xml: &str) -> SoapResult<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    quick_xml::de::from_str(xml)
+        .map_err(|e| SoapError::SecurityError(format!("Invalid XML Encryption element: {}", e)))
+}
+
+#[derive(Deserialize)]
+struct EncryptedData {
+    #[serde(rename = "@Id")]
+    id: Option<String>,
+    #[serde(rename = "EncryptionMethod")]
+    encryption_method: EncryptionMethod,
+    #[serde(rename = "KeyInfo")]
+    key_info: Option<KeyInfo>,
+    #[serde(rename = "CipherData")]
+    cipher_data: CipherData,
+}
+
+#[derive(Deserialize)]
+struct EncryptedKey {
+    #[serde(rename = "@Id")]
+    id: Option<String>,
+    #[serde(rename = "EncryptionMethod")]
+    encryption_method: EncryptionMethod,
+    #[serde(rename = "CipherData")]
+    cipher_data: CipherData,
+    #[serde(rename = "ReferenceList")]
+    reference_list: Option<ReferenceList>,
+}
+
+#[derive(Deserialize)]
+struct EncryptionMethod {
+    #[serde(rename = "@Algorithm")]
+    algorithm: String,
+    #[serde(rename = "DigestMethod")]
+    digest_method: Option<Algorithm>,
+    #[serde(rename = "MGF")]
+    mgf: Option<Algorithm>,
+}
+
+#[derive(Deserialize)]
+struct Algorithm {
+    #[serde(rename = "@Algorithm")]
+    algorithm: String,
+}
+
+#[derive(Deserialize)]
+struct KeyInfo {
+    #[serde(rename = "EncryptedKey")]
+    encrypted_key: Option<EncryptedKey>,
+    #[serde(rename = "SecurityTokenReference")]
+    security_token_reference: Option<SecurityTokenReference>,
+}
+
+#[derive(Deserialize)]
+struct SecurityTokenReference {
+    #[serde(rename = "Reference")]
+    reference: Option<UriReference>,
+}
+
+#[derive(Deserialize)]
+struct ReferenceList {
+    #[serde(rename = "DataReference", default)]
+    data_references: Vec<UriReference>,
+}
+
+#[derive(Deserialize)]
+struct UriReference {
+    #[serde(rename = "@URI")]
+    uri: String,
+}
+
+#[derive(Deserialize)]
+struct CipherData {
+    #[serde(rename = "CipherValue")]
+    cipher_value: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envelope::SoapVersion;
+    use crate::security::encryption::encrypt_body;
+    use crate::security::parse_date_time;
+    use crate::security::verification::verify_signature_at;
+    use crate::security::Certificate;
+
+    const CLIENT_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/client.crt");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../../testdata/security/client.key");
+    const SERVER_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/server.crt");
+    const ENCRYPTED_RESPONSE: &str =
+        include_str!("../../../testdata/security/encrypted-response.xml");
+
+    fn envelope() -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope"><env:Header><wsse:Security xmlns:wsse="{}"></wsse:Security></env:Header><env:Body><Ping xmlns="urn:ping"><Id>1 &amp; 2</Id></Ping></env:Body></env:Envelope>"#,
+            super::super::WSSE_NAMESPACE
+        )
+    }
+
+    #[test]
+    fn test_decrypt_encrypted_response() {
+        let client = X509Credentials::from_pem(CLIENT_CERTIFICATE, CLIENT_KEY).unwrap();
+        let decrypted = decrypt(ENCRYPTED_RESPONSE, &client).unwrap();
+        assert!(!decrypted.contains("EncryptedData"));
+        assert!(decrypted.contains("pong &amp; &lt;more&gt;"));
+
+        // The response was signed before it was encrypted
+        let server = Certificate::from_pem(SERVER_CERTIFICATE).unwrap();
+        let before_expiry = parse_date_time("2026-10-16T12:01:00Z").unwrap();
+        verify_signature_at(&decrypted, &server, before_expiry).unwrap();
+    }
+
+    #[test]
+    fn test_check_body_encrypted() {
+        let server = Certificate::from_pem(SERVER_CERTIFICATE).unwrap();
+        let encrypted = encrypt_body(
+            &envelope(),
+            SoapVersion::Soap12,
+            &server,
+            EncryptionAlgorithm::default(),
+        )
+        .unwrap();
+        check_body_encrypted(&encrypted).unwrap();
+        check_body_encrypted(ENCRYPTED_RESPONSE).unwrap();
+
+        // Encrypted data outside of the Body doesn't count
+        let error = check_body_encrypted(&envelope()).unwrap_err();
+        assert!(error.to_string().contains("not encrypted"));
+        let start = encrypted.find("<xenc:EncryptedData").unwrap();
+        let end = encrypted.find("</xenc:EncryptedData>").unwrap() + "</xenc:EncryptedData>".len();
+        let moved = envelope().replace(
+            "</wsse:Security>",
+            &format!("</wsse:Security>{}", &encrypted[start..end]),
+        );
+        assert!(check_body_encrypted(&moved).is_err());
+
+        // Plain content next to encrypted data
+        let mixed = encrypted.replace("</env:Body>", "<Ping xmlns=\"urn:ping\"/></env:Body>");
+        assert!(check_body_encrypted(&mixed).is_err());
+
+        // Faults are not encrypted
+        let fault = r#"<env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope"><env:Body><env:Fault><env:Code><env:Value>env:Receiver</env:Value></env:Code><env:Reason><env:Text xml:lang="en">Failed</env:Text></env:Reason></env:Fault></env:Body></env:Envelope>"#;
+        check_body_encrypted(fault).unwrap();
+    }
+}
@@ -0,0 +1,333 @@
+//! XML Encryption of the SOAP Body (X.509 Token Profile 1.0)
+//!
+//! The content of the Body is encrypted with a random AES key, replaced by an
+//! `xenc:EncryptedData` element. The key is encrypted with RSA-OAEP for the
+//! certificate of the recipient and sent as `xenc:EncryptedKey` in the
+//! `wsse:Security` header block, referencing the encrypted data.
+
+use super::signature::{DSIG_NAMESPACE, SHA1, SIGNATURE_ID};
+use super::x509::openssl_error;
+use super::{Certificate, BASE64_ENCODING};
+use crate::envelope::SoapVersion;
+use crate::error::{SoapError, SoapResult};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use openssl::encrypt::Encrypter;
+use openssl::rand::rand_bytes;
+use openssl::rsa::Padding;
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher, Crypter, Mode};
+
+/// Namespace of the `xenc` elements of XML Encryption
+pub const XENC_NAMESPACE: &str = "http://www.w3.org/2001/04/xmlenc#";
+
+/// `ValueType` of a key identifier holding the SHA-1 digest of a certificate
+pub const THUMBPRINT_TYPE: &str =
+    "http://docs.oasis-open.org/wss/oasis-wss-soap-message-security-1.1#ThumbprintSHA1";
+
+const CONTENT_TYPE: &str = "http://www.w3.org/2001/04/xmlenc#Content";
+pub(super) const RSA_OAEP_MGF1P: &str = "http://www.w3.org/2001/04/xmlenc#rsa-oaep-mgf1p";
+
+/// `Id` of the `xenc:EncryptedData` replacing the content of the Body
+const DATA_ID: &str = "ED-1";
+
+/// `Id` of the `xenc:EncryptedKey` of a request
+const KEY_ID: &str = "EK-1";
+
+/// Length of the authentication tag of AES-GCM in bytes
+const GCM_TAG_LENGTH: usize = 16;
+
+/// Block cipher the content of the Body is encrypted with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EncryptionAlgorithm {
+    /// AES-128 in CBC mode
+    Aes128Cbc,
+    /// AES-256 in CBC mode, supported by all WS-Security implementations
+    #[default]
+    Aes256Cbc,
+    /// AES-128 in GCM mode (XML Encryption 1.1)
+    Aes128Gcm,
+    /// AES-256 in GCM mode (XML Encryption 1.1), preferred where supported
+    Aes256Gcm,
+}
+
+impl EncryptionAlgorithm {
+    /// URI of the `Algorithm` attribute of `xenc:EncryptionMethod`
+    pub fn uri(&self) -> &'static str {
+        match self {
+            EncryptionAlgorithm::Aes128Cbc => "http://www.w3.org/2001/04/xmlenc#aes128-cbc",
+            EncryptionAlgorithm::Aes256Cbc => "http://www.w3.org/2001/04/xmlenc#aes256-cbc",
+            EncryptionAlgorithm::Aes128Gcm => "http://www.w3.org/2009/xmlenc11#aes128-gcm",
+            EncryptionAlgorithm::Aes256Gcm => "http://www.w3.org/2009/xmlenc11#aes256-gcm",
+        }
+    }
+
+    pub(super) fn from_uri(uri: &str) -> SoapResult<Self> {
+        [
+            EncryptionAlgorithm::Aes128Cbc,
+            EncryptionAlgorithm::Aes256Cbc,
+            EncryptionAlgorithm::Aes128Gcm,
+            EncryptionAlgorithm::Aes256Gcm,
+        ]
+        .into_iter()
+        .find(|algorithm| algorithm.uri() == uri)
+        .ok_or_else(|| SoapError::SecurityError(format!("Unsupported encryption method {}", uri)))
+    }
+
+    fn cipher(&self) -> Cipher {
+        match self {
+            EncryptionAlgorithm::Aes128Cbc => Cipher::aes_128_cbc(),
+            EncryptionAlgorithm::Aes256Cbc => Cipher::aes_256_cbc(),
+            EncryptionAlgorithm::Aes128Gcm => Cipher::aes_128_gcm(),
+            EncryptionAlgorithm::Aes256Gcm => Cipher::aes_256_gcm(),
+        }
+    }
+
+    fn is_gcm(&self) -> bool {
+        matches!(
+            self,
+            EncryptionAlgorithm::Aes128Gcm | EncryptionAlgorithm::Aes256Gcm
+        )
+    }
+
+    /// Encrypt data, returning the IV followed by the ciphertext (and the tag of GCM)
+    fn encrypt(&self, key: &[u8], data: &[u8]) -> SoapResult<Vec<u8>> {
+        let cipher = self.cipher();
+        let mut iv = vec![0; cipher.iv_len().unwrap_or_default()];
+        rand_bytes(&mut iv).map_err(openssl_error("Failed to create IV"))?;
+
+        let mut output = iv.clone();
+        if self.is_gcm() {
+            let mut tag = [0; GCM_TAG_LENGTH];
+            let encrypted = encrypt_aead(cipher, key, Some(&iv), &[], data, &mut tag)
+                .map_err(openssl_error("Failed to encrypt"))?;
+            output.extend(encrypted);
+            output.extend(tag);
+        } else {
+            // The PKCS#7 padding of OpenSSL is a valid XML Encryption padding
+            let encrypted = openssl::symm::encrypt(cipher, key, Some(&iv), data)
+                .map_err(openssl_error("Failed to encrypt"))?;
+            output.extend(encrypted);
+        }
+        Ok(output)
+    }
+
+    /// Decrypt the IV and ciphertext of a `xenc:CipherValue`
+    pub(super) fn decrypt(&self, key: &[u8], data: &[u8]) -> SoapResult<Vec<u8>> {
+        let cipher = self.cipher();
+        if key.len() != cipher.key_len() {
+            return Err(SoapError::SecurityError(format!(
+                "Key of {} bytes for {}",
+                key.len(),
+                self.uri()
+            )));
+        }
+        let iv_length = cipher.iv_len().unwrap_or_default();
+        let tag_length = if self.is_gcm() { GCM_TAG_LENGTH } else { 0 };
+        if data.len() < iv_length + tag_length {
+            return Err(SoapError::SecurityError(
+                "Encrypted data is too short".to_string(),
+            ));
+        }
+        let (iv, encrypted) = data.split_at(iv_length);
+
+        if self.is_gcm() {
+            let (encrypted, tag) = encrypted.split_at(encrypted.len() - tag_length);
+            return decrypt_aead(cipher, key, Some(iv), &[], encrypted, tag)
+                .map_err(openssl_error("Failed to decrypt"));
+        }
+
+        // XML Encryption only defines the last byte of the padding, the
+        // others may be arbitrary, so the padding is not checked by OpenSSL
+        let mut crypter = Crypter::new(cipher, Mode::Decrypt, key, Some(iv))
+            .map_err(openssl_error("Failed to decrypt"))?;
+        crypter.pad(false);
+        let mut output = vec![0; encrypted.len() + cipher.block_size()];
+        let mut length = crypter
+            .update(encrypted, &mut output)
+            .map_err(openssl_error("Failed to decrypt"))?;
+        length += crypter
+            .finalize(&mut output[length..])
+            .map_err(openssl_error("Failed to decrypt"))?;
+        output.truncate(length);
+
+        let padding = output.last().copied().unwrap_or_default() as usize;
+        if padding == 0 || padding > cipher.block_size() || padding > output.len() {
+            return Err(SoapError::SecurityError(
+                "Invalid padding of encrypted data".to_string(),
+            ));
+        }
+        output.truncate(output.len() - padding);
+        Ok(output)
+    }
+}
+
+/// Encrypt the content of the Body of an envelope for the owner of `certificate`
+///
+/// The `xenc:EncryptedKey` is added to the `wsse:Security` header block, in
+/// front of a `ds:Signature` so that the Body is decrypted before the
+/// signature is verified.
+pub(crate) fn encrypt_body(
+    envelope: &str,
+    version: SoapVersion,
+    certificate: &Certificate,
+    algorithm: EncryptionAlgorithm,
+) -> SoapResult<String> {
+    let prefix = match version {
+        SoapVersion::Soap11 => "soap",
+        SoapVersion::Soap12 => "env",
+    };
+    let start = envelope
+        .find(&format!("<{}:Body", prefix))
+        .and_then(|start| envelope[start..].find('>').map(|end| start + end + 1));
+    let end = envelope.rfind(&format!("</{}:Body>", prefix));
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(SoapError::SecurityError(
+            "Envelope without Body to encrypt".to_string(),
+        ));
+    };
+
+    let mut key = vec![0; algorithm.cipher().key_len()];
+    rand_bytes(&mut key).map_err(openssl_error("Failed to create key"))?;
+    let encrypted_data = format!(
+        r#"<xenc:EncryptedData xmlns:xenc="{}" Id="{}" Type="{}"><xenc:EncryptionMethod Algorithm="{}"/><xenc:CipherData><xenc:CipherValue>{}</xenc:CipherValue></xenc:CipherData></xenc:EncryptedData>"#,
+        XENC_NAMESPACE,
+        DATA_ID,
+        CONTENT_TYPE,
+        algorithm.uri(),
+        BASE64.encode(algorithm.encrypt(&key, &envelope.as_bytes()[start..end])?)
+    );
+
+    let public_key = certificate.public_key()?;
+    let encrypter = Encrypter::new(&public_key)
+        .and_then(|mut encrypter| {
+            encrypter.set_rsa_padding(Padding::PKCS1_OAEP)?;
+            Ok(encrypter)
+        })
+        .map_err(openssl_error("Failed to encrypt key"))?;
+    let mut wrapped = vec![0; encrypter.encrypt_len(&key).unwrap_or_default()];
+    let length = encrypter
+        .encrypt(&key, &mut wrapped)
+        .map_err(openssl_error("Failed to encrypt key"))?;
+    wrapped.truncate(length);
+    let encrypted_key = format!(
+        r##"<xenc:EncryptedKey xmlns:xenc="{}" Id="{}"><xenc:EncryptionMethod Algorithm="{}"><ds:DigestMethod xmlns:ds="{}" Algorithm="{}"/></xenc:EncryptionMethod><ds:KeyInfo xmlns:ds="{}"><wsse:SecurityTokenReference><wsse:KeyIdentifier EncodingType="{}" ValueType="{}">{}</wsse:KeyIdentifier></wsse:SecurityTokenReference></ds:KeyInfo><xenc:CipherData><xenc:CipherValue>{}</xenc:CipherValue></xenc:CipherData><xenc:ReferenceList><xenc:DataReference URI="#{}"/></xenc:ReferenceList></xenc:EncryptedKey>"##,
+        XENC_NAMESPACE,
+        KEY_ID,
+        RSA_OAEP_MGF1P,
+        DSIG_NAMESPACE,
+        SHA1,
+        DSIG_NAMESPACE,
+        BASE64_ENCODING,
+        THUMBPRINT_TYPE,
+        BASE64.encode(openssl::sha::sha1(certificate.der())),
+        BASE64.encode(wrapped),
+        DATA_ID
+    );
+
+    let envelope = format!(
+        "{}{}{}",
+        &envelope[..start],
+        encrypted_data,
+        &envelope[end..]
+    );
+    // Before the signature of the request, not one of a SAML assertion
+    let signature = format!(
+        r#"<ds:Signature xmlns:ds="{}" Id="{}">"#,
+        DSIG_NAMESPACE, SIGNATURE_ID
+    );
+    let Some(pos) = envelope
+        .find(&signature)
+        .or_else(|| envelope.find("</wsse:Security>"))
+    else {
+        return Err(SoapError::SecurityError(
+            "Envelope without wsse:Security header".to_string(),
+        ));
+    };
+    Ok(format!(
+        "{}{}{}",
+        &envelope[..pos],
+        encrypted_key,
+        &envelope[pos..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{decrypt, X509Credentials};
+
+    const CLIENT_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/client.crt");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../../testdata/security/client.key");
+    const SERVER_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/server.crt");
+    const SERVER_KEY: &[u8] = include_bytes!("../../../testdata/security/server.key");
+
+    fn envelope() -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><env:Envelope xmlns:env="http://www.w3.org/2003/05/soap-envelope"><env:Header><wsse:Security xmlns:wsse="{}"></wsse:Security></env:Header><env:Body><Ping xmlns="urn:ping"><Id>1 &amp; 2</Id></Ping></env:Body></env:Envelope>"#,
+            super::super::WSSE_NAMESPACE
+        )
+    }
+
+    #[test]
+    fn test_encrypt_and_decrypt() {
+        let server = X509Credentials::from_pem(SERVER_CERTIFICATE, SERVER_KEY).unwrap();
+        for algorithm in [
+            EncryptionAlgorithm::Aes128Cbc,
+            EncryptionAlgorithm::Aes256Cbc,
+            EncryptionAlgorithm::Aes128Gcm,
+            EncryptionAlgorithm::Aes256Gcm,
+        ] {
+            let encrypted = encrypt_body(
+                &envelope(),
+                SoapVersion::Soap12,
+                server.certificate(),
+                algorithm,
+            )
+            .unwrap();
+            assert!(!encrypted.contains("Ping"));
+            assert!(encrypted.contains(algorithm.uri()));
+            assert!(encrypted.contains(r##"<xenc:DataReference URI="#ED-1"/>"##));
+
+            let decrypted = decrypt(&encrypted, &server).unwrap();
+            assert!(decrypted.contains(
+                r#"<env:Body><Ping xmlns="urn:ping"><Id>1 &amp; 2</Id></Ping></env:Body>"#
+            ));
+        }
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_key() {
+        let server = Certificate::from_pem(SERVER_CERTIFICATE).unwrap();
+        let client = X509Credentials::from_pem(CLIENT_CERTIFICATE, CLIENT_KEY).unwrap();
+        let encrypted = encrypt_body(
+            &envelope(),
+            SoapVersion::Soap12,
+            &server,
+            EncryptionAlgorithm::default(),
+        )
+        .unwrap();
+        assert!(decrypt(&encrypted, &client).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_padding() {
+        // XML Encryption padding with arbitrary bytes before the length
+        let key = [7u8; 16];
+        let iv = [1u8; 16];
+        let mut padded = b"<a/>".to_vec();
+        padded.extend([0xAB; 11]);
+        padded.push(12);
+        let mut crypter =
+            Crypter::new(Cipher::aes_128_cbc(), Mode::Encrypt, &key, Some(&iv)).unwrap();
+        crypter.pad(false);
+        let mut encrypted = vec![0; 32];
+        let length = crypter.update(&padded, &mut encrypted).unwrap();
+        encrypted.truncate(length);
+
+        let data = [iv.to_vec(), encrypted].concat();
+        assert_eq!(
+            EncryptionAlgorithm::Aes128Cbc.decrypt(&key, &data).unwrap(),
+            b"<a/>"
+        );
+    }
+}
@@ -0,0 +1,435 @@
+//! WS-Security (OASIS Web Services Security 1.0)
+//!
+//! A client configured with [`WsSecurity`] sends a `wsse:Security` header
+//! block with every request. It holds a `wsu:Timestamp` and a
+//! `wsse:UsernameToken`, both created anew for each request:
+//!
+//! ```xml
+//! <wsse:Security xmlns:wsse="..." xmlns:wsu="..." soap:mustUnderstand="1">
+//!   <wsu:Timestamp wsu:Id="TS-1">
+//!     <wsu:Created>2026-10-16T12:00:00.000Z</wsu:Created>
+//!     <wsu:Expires>2026-10-16T12:05:00.000Z</wsu:Expires>
+//!   </wsu:Timestamp>
+//!   <wsse:UsernameToken wsu:Id="UsernameToken-1">
+//!     <wsse:Username>admin</wsse:Username>
+//!     <wsse:Password Type="...#PasswordDigest">...</wsse:Password>
+//!     <wsse:Nonce EncodingType="...#Base64Binary">...</wsse:Nonce>
+//!     <wsu:Created>2026-10-16T12:00:00.000Z</wsu:Created>
+//!   </wsse:UsernameToken>
+//! </wsse:Security>
+//! ```
+//!
+//! With [`X509Credentials`], the Body and the Timestamp are also signed (see
+//! [`WsSecurity::with_signature`]); the certificate is sent as
+//! `wsse:BinarySecurityToken` and the `ds:Signature` is appended to the
+//! header block. Signatures of responses are checked with
+//! [`WsSecurity::with_response_verification`] or [`verify_signature`].
+//!
+//! The content of the Body can be encrypted for the certificate of the
+//! service after it is signed ([`WsSecurity::with_encryption`]), encrypted
+//! responses are decrypted with [`WsSecurity::with_response_decryption`] or
+//! [`decrypt`].
+//!
+//! SAML 2.0 assertions issued by an identity provider are inserted into the
+//! header unchanged, see [`WsSecurity::with_saml_assertion`] and
+//! [`WsSecurity::with_saml_provider`].
+//!
+//! Generated clients check these settings against the WS-SecurityPolicy of
+//! an operation, see [`SecurityPolicy`].
+//!
+//! This module is only available with the `ws-security` feature.
+
+mod c14n;
+mod date_time;
+mod decryption;
+mod encryption;
+mod policy;
+mod saml;
+mod signature;
+mod username_token;
+mod verification;
+mod x509;
+
+pub use c14n::EXC_C14N;
+pub use decryption::decrypt;
+pub use encryption::{EncryptionAlgorithm, THUMBPRINT_TYPE, XENC_NAMESPACE};
+pub use policy::{SecurityPolicy, DEFAULT_TIMESTAMP_TTL};
+pub use saml::{SamlAssertion, SamlProvider, SAML2_NAMESPACE, SAML_REFRESH_MARGIN};
+pub use signature::{DSIG_NAMESPACE, X509_TOKEN_TYPE};
+pub use username_token::{password_digest, PasswordType, UsernameToken};
+pub use verification::verify_signature;
+pub use x509::{Certificate, X509Credentials};
+
+use crate::envelope::SoapVersion;
+use crate::error::{SoapError, SoapResult};
+use crate::header::HeaderBlock;
+pub(crate) use date_time::{format_date_time, parse_date_time};
+pub(crate) use saml::SamlSource;
+use std::time::{Duration, SystemTime};
+
+/// Namespace of the `wsse` elements
+pub const WSSE_NAMESPACE: &str =
+    "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-secext-1.0.xsd";
+
+/// Namespace of the `wsu` elements and attributes
+pub const WSU_NAMESPACE: &str =
+    "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-utility-1.0.xsd";
+
+/// URI of the `EncodingType` of Base64 encoded tokens and nonces
+const BASE64_ENCODING: &str =
+    "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-soap-message-security-1.0#Base64Binary";
+
+/// `wsu:Id` of the `wsu:Timestamp`
+const TIMESTAMP_ID: &str = "TS-1";
+
+/// Length of the nonce of a `UsernameToken` in bytes
+const NONCE_LENGTH: usize = 16;
+
+/// WS-Security settings of a client
+///
+/// # Example
+///
+/// ```
+/// use soapus_runtime::security::{PasswordType, UsernameToken, WsSecurity};
+/// use soapus_runtime::SoapClient;
+/// use std::time::Duration;
+///
+/// let client = SoapClient::builder("http://example.com/soap")
+///     .ws_security(
+///         WsSecurity::new()
+///             .with_username_token(UsernameToken::new("admin", "secret", PasswordType::Digest))
+///             .with_timestamp(Duration::from_secs(300)),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WsSecurity {
+    username_token: Option<UsernameToken>,
+    timestamp_ttl: Option<Duration>,
+    signature: Option<X509Credentials>,
+    response_certificate: Option<Certificate>,
+    encryption: Option<(Certificate, EncryptionAlgorithm)>,
+    response_decryption: Option<X509Credentials>,
+    saml: Option<SamlSource>,
+}
+
+impl WsSecurity {
+    /// Create settings without any token
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticate with a `UsernameToken`
+    pub fn with_username_token(self, username_token: UsernameToken) -> Self {
+        Self {
+            username_token: Some(username_token),
+            ..self
+        }
+    }
+
+    /// Add a `wsu:Timestamp` expiring `ttl` after the request is built
+    pub fn with_timestamp(self, ttl: Duration) -> Self {
+        Self {
+            timestamp_ttl: Some(ttl),
+            ..self
+        }
+    }
+
+    /// Sign the Body and the Timestamp of requests
+    ///
+    /// The certificate of `credentials` is sent as `wsse:BinarySecurityToken`.
+    /// The Timestamp is only signed if one is added with
+    /// [`with_timestamp`](Self::with_timestamp).
+    pub fn with_signature(self, credentials: X509Credentials) -> Self {
+        Self {
+            signature: Some(credentials),
+            ..self
+        }
+    }
+
+    /// Require responses to be signed with the key of `certificate`
+    ///
+    /// SOAP faults are reported without checking a signature.
+    pub fn with_response_verification(self, certificate: Certificate) -> Self {
+        Self {
+            response_certificate: Some(certificate),
+            ..self
+        }
+    }
+
+    /// Encrypt the content of the Body of requests for the owner of `certificate`
+    ///
+    /// A signed Body is encrypted after it is signed. The AES key is encrypted
+    /// with RSA-OAEP and identified by the SHA-1 thumbprint of the certificate.
+    pub fn with_encryption(self, certificate: Certificate, algorithm: EncryptionAlgorithm) -> Self {
+        Self {
+            encryption: Some((certificate, algorithm)),
+            ..self
+        }
+    }
+
+    /// Decrypt encrypted content of responses with the key of `credentials`
+    ///
+    /// Responses are decrypted before they are checked for faults and before
+    /// their signature is verified. Responses whose Body is not encrypted are
+    /// rejected; SOAP faults are reported without being encrypted.
+    pub fn with_response_decryption(self, credentials: X509Credentials) -> Self {
+        Self {
+            response_decryption: Some(credentials),
+            ..self
+        }
+    }
+
+    /// Send a SAML 2.0 assertion, e.g. a bearer token of a single sign-on
+    ///
+    /// The assertion is inserted into the header as it is, following the
+    /// Timestamp and the `wsse:BinarySecurityToken`.
+    pub fn with_saml_assertion(self, assertion: SamlAssertion) -> Self {
+        self.with_saml(SamlSource::Assertion(assertion))
+    }
+
+    /// Send SAML 2.0 assertions of `provider`
+    ///
+    /// The provider is asked for an assertion by the first request and again
+    /// by the first request within [`SAML_REFRESH_MARGIN`] of its
+    /// `NotOnOrAfter` time. Clones of these settings share the assertion.
+    pub fn with_saml_provider(self, provider: impl SamlProvider + 'static) -> Self {
+        self.with_saml(SamlSource::provider(provider))
+    }
+
+    pub(crate) fn with_saml(self, saml: SamlSource) -> Self {
+        Self {
+            saml: Some(saml),
+            ..self
+        }
+    }
+
+    /// Get the `UsernameToken`, if any
+    pub fn username_token(&self) -> Option<&UsernameToken> {
+        self.username_token.as_ref()
+    }
+
+    /// Get the lifetime of the `wsu:Timestamp`, if one is added
+    pub fn timestamp_ttl(&self) -> Option<Duration> {
+        self.timestamp_ttl
+    }
+
+    /// Get the credentials requests are signed with, if any
+    pub fn signature(&self) -> Option<&X509Credentials> {
+        self.signature.as_ref()
+    }
+
+    /// Get the certificate responses must be signed with, if any
+    pub fn response_certificate(&self) -> Option<&Certificate> {
+        self.response_certificate.as_ref()
+    }
+
+    /// Get the certificate and algorithm requests are encrypted with, if any
+    pub fn encryption(&self) -> Option<(&Certificate, EncryptionAlgorithm)> {
+        self.encryption
+            .as_ref()
+            .map(|(certificate, algorithm)| (certificate, *algorithm))
+    }
+
+    /// Get the credentials responses are decrypted with, if any
+    pub fn response_decryption(&self) -> Option<&X509Credentials> {
+        self.response_decryption.as_ref()
+    }
+
+    /// Get the SAML assertion to send now, if any
+    ///
+    /// The assertion of a provider is renewed if it expires soon.
+    pub async fn saml_assertion(&self) -> SoapResult<Option<SamlAssertion>> {
+        match &self.saml {
+            Some(saml) => Ok(Some(saml.assertion(SystemTime::now()).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Build the `wsse:Security` header block for a request
+    ///
+    /// Creation times are the current time, the nonce of a password digest
+    /// is random.
+    pub async fn header(&self) -> SoapResult<HeaderBlock> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        getrandom::getrandom(&mut nonce)
+            .map_err(|e| SoapError::SecurityError(format!("Failed to create nonce: {}", e)))?;
+        let assertion = self.saml_assertion().await?;
+        Ok(self.header_at(SystemTime::now(), &nonce, assertion.as_ref()))
+    }
+
+    /// Build the `wsse:Security` header block with the given creation time, nonce and assertion
+    pub(crate) fn header_at(
+        &self,
+        now: SystemTime,
+        nonce: &[u8],
+        assertion: Option<&SamlAssertion>,
+    ) -> HeaderBlock {
+        let created = format_date_time(now);
+        let mut xml = format!(
+            r#"<wsse:Security xmlns:wsse="{}" xmlns:wsu="{}">"#,
+            WSSE_NAMESPACE, WSU_NAMESPACE
+        );
+        if let Some(ttl) = self.timestamp_ttl {
+            xml.push_str(&format!(
+                r#"<wsu:Timestamp wsu:Id="{}"><wsu:Created>{}</wsu:Created><wsu:Expires>{}</wsu:Expires></wsu:Timestamp>"#,
+                TIMESTAMP_ID,
+                created,
+                format_date_time(now + ttl)
+            ));
+        }
+        if let Some(credentials) = &self.signature {
+            xml.push_str(&credentials.certificate().binary_security_token());
+        }
+        if let Some(assertion) = assertion {
+            xml.push_str(assertion.xml());
+        }
+        if let Some(token) = &self.username_token {
+            xml.push_str(&token.to_xml(&created, nonce));
+        }
+        xml.push_str("</wsse:Security>");
+        HeaderBlock::from_xml(xml).with_must_understand(true)
+    }
+
+    /// Sign, then encrypt an envelope holding the header block of [`header`](Self::header)
+    ///
+    /// Envelopes are returned unchanged without signature credentials and
+    /// encryption certificate.
+    pub(crate) fn secure_envelope(
+        &self,
+        mut envelope: String,
+        version: SoapVersion,
+    ) -> SoapResult<String> {
+        if let Some(credentials) = &self.signature {
+            let ids: &[&str] = if self.timestamp_ttl.is_some() {
+                &[TIMESTAMP_ID]
+            } else {
+                &[]
+            };
+            envelope = signature::sign_envelope(&envelope, version, credentials, ids)?;
+        }
+        if let Some((certificate, algorithm)) = &self.encryption {
+            envelope = encryption::encrypt_body(&envelope, version, certificate, *algorithm)?;
+        }
+        Ok(envelope)
+    }
+
+    /// Decrypt a response, if responses are decrypted
+    ///
+    /// The Body of the response must be encrypted, unless it holds a fault.
+    pub(crate) fn decrypt_response(&self, xml: String) -> SoapResult<String> {
+        match &self.response_decryption {
+            Some(credentials) => {
+                decryption::check_body_encrypted(&xml)?;
+                decrypt(&xml, credentials)
+            }
+            None => Ok(xml),
+        }
+    }
+
+    /// Check the signature of a response, if responses must be signed
+    pub(crate) fn verify_response(&self, xml: &str) -> SoapResult<()> {
+        match &self.response_certificate {
+            Some(certificate) => verify_signature(xml, certificate),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envelope::SoapVersion;
+    use std::time::UNIX_EPOCH;
+
+    /// 2026-10-16T12:00:00Z
+    fn noon() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_792_152_000)
+    }
+
+    #[test]
+    fn test_security_header() {
+        let nonce: Vec<u8> = (0..16).collect();
+        let security = WsSecurity::new()
+            .with_username_token(UsernameToken::new("admin", "secret", PasswordType::Digest))
+            .with_timestamp(Duration::from_secs(300));
+
+        let header = security.header_at(noon(), &nonce, None);
+        assert_eq!(header.name(), "Security");
+        assert!(header.must_understand());
+
+        let xml = header.to_xml(SoapVersion::Soap11);
+        assert!(xml.starts_with(&format!(
+            r#"<wsse:Security xmlns:wsse="{}" xmlns:wsu="{}" soap:mustUnderstand="1">"#,
+            WSSE_NAMESPACE, WSU_NAMESPACE
+        )));
+        assert!(xml.contains(
+            "<wsu:Timestamp wsu:Id=\"TS-1\"><wsu:Created>2026-10-16T12:00:00.000Z</wsu:Created>\
+             <wsu:Expires>2026-10-16T12:05:00.000Z</wsu:Expires></wsu:Timestamp>"
+        ));
+        assert!(xml.contains(">hOH2tOVEqKegF7ds+J2fvQcfJ8M=</wsse:Password>"));
+    }
+
+    #[tokio::test]
+    async fn test_security_header_without_timestamp() {
+        let security = WsSecurity::new().with_username_token(UsernameToken::new(
+            "admin",
+            "secret",
+            PasswordType::Text,
+        ));
+
+        let header = security.header().await.unwrap();
+        assert!(!header.xml().contains("wsu:Timestamp"));
+        assert!(header
+            .xml()
+            .contains("<wsse:Username>admin</wsse:Username>"));
+    }
+
+    #[test]
+    fn test_security_header_with_saml_assertion() {
+        let xml = include_str!("../../../testdata/security/saml-assertion.xml");
+        let assertion = SamlAssertion::new(xml).unwrap();
+        let security = WsSecurity::new()
+            .with_username_token(UsernameToken::new("admin", "secret", PasswordType::Text))
+            .with_timestamp(Duration::from_secs(300))
+            .with_saml_assertion(assertion.clone());
+
+        let xml = security
+            .header_at(noon(), &[0; 16], Some(&assertion))
+            .to_xml(SoapVersion::Soap11);
+        let position = xml.find(assertion.xml()).unwrap();
+        assert!(xml.find("</wsu:Timestamp>").unwrap() < position);
+        assert!(position < xml.find("<wsse:UsernameToken").unwrap());
+    }
+
+    #[test]
+    fn test_decrypt_response_requires_encrypted_body() {
+        let credentials = X509Credentials::from_pem(
+            include_bytes!("../../../testdata/security/client.crt"),
+            include_bytes!("../../../testdata/security/client.key"),
+        )
+        .unwrap();
+        let server =
+            Certificate::from_pem(include_bytes!("../../../testdata/security/server.crt")).unwrap();
+        let policy = SecurityPolicy {
+            encrypt_body: true,
+            ..Default::default()
+        };
+        let security = WsSecurity::new()
+            .with_signature(credentials)
+            .with_encryption(server, EncryptionAlgorithm::default())
+            .with_policy(&policy)
+            .unwrap();
+
+        let encrypted = include_str!("../../../testdata/security/encrypted-response.xml");
+        let decrypted = security.decrypt_response(encrypted.to_string()).unwrap();
+        assert!(decrypted.contains("pong &amp; &lt;more&gt;"));
+
+        // A Body sent in plain text is rejected instead of being accepted as it is
+        let plain = include_str!("../../../testdata/security/signed-response.xml");
+        let error = security.decrypt_response(plain.to_string()).unwrap_err();
+        assert!(
+            matches!(error, SoapError::SecurityError(message) if message.contains("not encrypted"))
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Requirements of a WS-SecurityPolicy on the settings of a client
+
+use super::{EncryptionAlgorithm, PasswordType, WsSecurity};
+use crate::error::{SoapError, SoapResult};
+use std::time::Duration;
+
+/// Lifetime of a `wsu:Timestamp` added because a policy requires one
+pub const DEFAULT_TIMESTAMP_TTL: Duration = Duration::from_secs(300);
+
+/// What the WS-SecurityPolicy of an operation requires of requests
+///
+/// Generated clients describe the policy attached to an operation in the
+/// WSDL and apply it with [`SoapClient::with_security_policy`]. Settings that
+/// follow from the policy are added to the [`WsSecurity`] of the client,
+/// missing credentials are reported as errors (see
+/// [`WsSecurity::with_policy`]).
+///
+/// [`SoapClient::with_security_policy`]: crate::SoapClient::with_security_policy
+/// [`WsSecurity`]: super::WsSecurity
+/// [`WsSecurity::with_policy`]: super::WsSecurity::with_policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SecurityPolicy {
+    /// A `UsernameToken` is sent (`sp:UsernameToken`)
+    pub username_token: bool,
+    /// The password is sent as digest (`sp:HashPassword`)
+    pub password_digest: bool,
+    /// A `wsu:Timestamp` is sent (`sp:IncludeTimestamp`)
+    pub timestamp: bool,
+    /// The Body is signed (`sp:SignedParts` of an `sp:AsymmetricBinding`)
+    pub sign_body: bool,
+    /// The Body is encrypted (`sp:EncryptedParts` of an `sp:AsymmetricBinding`)
+    pub encrypt_body: bool,
+    /// Responses are signed by the service
+    pub signed_response: bool,
+    /// Algorithm of the `sp:AlgorithmSuite` to encrypt with, if supported
+    pub encryption_algorithm: Option<EncryptionAlgorithm>,
+}
+
+impl WsSecurity {
+    /// Complete these settings to meet a [`SecurityPolicy`]
+    ///
+    /// Settings following from the policy are added: a timestamp lasting
+    /// [`DEFAULT_TIMESTAMP_TTL`], a password digest instead of the plain
+    /// password. With an asymmetric binding, responses are decrypted with the
+    /// credentials requests are signed with and verified with the certificate
+    /// they are encrypted for, unless set otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoapError::SecurityError`] if the policy requires a
+    /// `UsernameToken`, a signature or encryption that is not configured, or
+    /// encryption with another algorithm than the configured one.
+    pub fn with_policy(mut self, policy: &SecurityPolicy) -> SoapResult<Self> {
+        let missing = |what: &str| {
+            Err(SoapError::SecurityError(format!(
+                "The security policy requires {}",
+                what
+            )))
+        };
+        if policy.username_token {
+            let Some(token) = self.username_token.take() else {
+                return missing("a UsernameToken");
+            };
+            self.username_token = Some(if policy.password_digest {
+                token.with_password_type(PasswordType::Digest)
+            } else {
+                token
+            });
+        }
+        if policy.timestamp && self.timestamp_ttl.is_none() {
+            self.timestamp_ttl = Some(DEFAULT_TIMESTAMP_TTL);
+        }
+        if policy.sign_body && self.signature.is_none() {
+            return missing("X.509 credentials to sign the Body");
+        }
+        if policy.encrypt_body {
+            let Some((_, algorithm)) = &self.encryption else {
+                return missing("the certificate of the service to encrypt the Body");
+            };
+            if let Some(required) = policy.encryption_algorithm {
+                if required != *algorithm {
+                    return Err(SoapError::SecurityError(format!(
+                        "The security policy requires encryption with {:?}, not {:?}",
+                        required, algorithm
+                    )));
+                }
+            }
+            if self.response_decryption.is_none() {
+                self.response_decryption = self.signature.clone();
+            }
+        }
+        if policy.signed_response && self.response_certificate.is_none() {
+            let Some((certificate, _)) = &self.encryption else {
+                return missing("the certificate of the service to verify responses");
+            };
+            self.response_certificate = Some(certificate.clone());
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{Certificate, UsernameToken, X509Credentials};
+
+    #[test]
+    fn test_security_policy() {
+        let token = UsernameToken::new("admin", "secret", PasswordType::Text);
+        let policy = SecurityPolicy {
+            username_token: true,
+            password_digest: true,
+            timestamp: true,
+            ..Default::default()
+        };
+
+        // Missing credentials are errors
+        let error = WsSecurity::new().with_policy(&policy).unwrap_err();
+        assert!(
+            matches!(error, SoapError::SecurityError(message) if message.contains("UsernameToken"))
+        );
+
+        // Settings following from the policy are added, others are kept
+        let security = WsSecurity::new()
+            .with_username_token(token.clone())
+            .with_policy(&policy)
+            .unwrap();
+        assert_eq!(
+            security.username_token().unwrap().password_type(),
+            PasswordType::Digest
+        );
+        assert_eq!(security.timestamp_ttl(), Some(DEFAULT_TIMESTAMP_TTL));
+        let security = WsSecurity::new()
+            .with_username_token(token)
+            .with_timestamp(Duration::from_secs(60))
+            .with_policy(&policy)
+            .unwrap();
+        assert_eq!(security.timestamp_ttl(), Some(Duration::from_secs(60)));
+
+        // An asymmetric binding uses the tokens of both sides for responses
+        let credentials = X509Credentials::from_pem(
+            include_bytes!("../../../testdata/security/client.crt"),
+            include_bytes!("../../../testdata/security/client.key"),
+        )
+        .unwrap();
+        let server =
+            Certificate::from_pem(include_bytes!("../../../testdata/security/server.crt")).unwrap();
+        let policy = SecurityPolicy {
+            sign_body: true,
+            encrypt_body: true,
+            signed_response: true,
+            encryption_algorithm: Some(EncryptionAlgorithm::Aes128Cbc),
+            ..Default::default()
+        };
+        let error = WsSecurity::new()
+            .with_signature(credentials.clone())
+            .with_policy(&policy)
+            .unwrap_err();
+        assert!(matches!(error, SoapError::SecurityError(message) if message.contains("encrypt")));
+
+        // The algorithm of the policy doesn't replace the configured one
+        let error = WsSecurity::new()
+            .with_signature(credentials.clone())
+            .with_encryption(server.clone(), EncryptionAlgorithm::Aes256Gcm)
+            .with_policy(&policy)
+            .unwrap_err();
+        assert!(
+            matches!(error, SoapError::SecurityError(message) if message.contains("Aes128Cbc, not Aes256Gcm"))
+        );
+        let security = WsSecurity::new()
+            .with_signature(credentials.clone())
+            .with_encryption(server.clone(), EncryptionAlgorithm::Aes128Cbc)
+            .with_policy(&policy)
+            .unwrap();
+        assert_eq!(
+            security.encryption(),
+            Some((&server, EncryptionAlgorithm::Aes128Cbc))
+        );
+        assert_eq!(security.response_certificate(), Some(&server));
+        assert_eq!(
+            security
+                .response_decryption()
+                .map(X509Credentials::certificate),
+            Some(credentials.certificate())
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! SAML 2.0 bearer assertions (SAML Token Profile 1.1)
+//!
+//! Assertions are issued and signed by an identity provider. They are
+//! inserted into the `wsse:Security` header exactly as received, since any
+//! change to their text may break the signature of the issuer.
+
+use super::parse_date_time;
+use crate::error::{SoapError, SoapResult};
+use quick_xml::events::Event;
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
+
+/// Namespace of SAML 2.0 assertions
+pub const SAML2_NAMESPACE: &str = "urn:oasis:names:tc:SAML:2.0:assertion";
+
+/// Time before its expiry at which the assertion of a [`SamlProvider`] is renewed
+pub const SAML_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// A signed `saml2:Assertion`, kept as the text it was issued as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SamlAssertion {
+    xml: String,
+    id: String,
+    expires: Option<SystemTime>,
+}
+
+impl SamlAssertion {
+    /// Take an assertion as issued by the identity provider
+    ///
+    /// A byte order mark, an XML declaration and surrounding whitespace are
+    /// removed, the element itself is kept unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SoapError::SecurityError`] if the text is not a single
+    /// well-formed `saml2:Assertion` element with an `ID`.
+    pub fn new(xml: impl Into<String>) -> SoapResult<Self> {
+        let xml = xml.into();
+        let mut text = xml.trim_start_matches('\u{feff}').trim();
+        if text.starts_with("<?xml") {
+            let end = text
+                .find("?>")
+                .ok_or_else(|| invalid("unterminated XML declaration"))?;
+            text = text[end + 2..].trim();
+        }
+
+        let mut reader = NsReader::from_str(text);
+        let mut id = None;
+        let mut expires = None;
+        let mut depth = 0usize;
+        let mut complete = false;
+        loop {
+            let (namespace, event) = reader.read_resolved_event()?;
+            match &event {
+                Event::Start(_) | Event::Empty(_) if complete => {
+                    return Err(invalid("content after the assertion"));
+                }
+                Event::Start(e) | Event::Empty(e) => {
+                    let is_saml = matches!(
+                        namespace,
+                        ResolveResult::Bound(Namespace(ns)) if ns == SAML2_NAMESPACE.as_bytes()
+                    );
+                    let attribute = |name: &[u8]| -> SoapResult<Option<String>> {
+                        for attribute in e.attributes() {
+                            let attribute = attribute.map_err(quick_xml::Error::from)?;
+                            if attribute.key.as_ref() == name {
+                                return Ok(Some(attribute.unescape_value()?.into_owned()));
+                            }
+                        }
+                        Ok(None)
+                    };
+                    if depth == 0 {
+                        if !is_saml || e.local_name().as_ref() != b"Assertion" {
+                            return Err(invalid("the root element is not saml2:Assertion"));
+                        }
+                        id = attribute(b"ID")?;
+                    } else if depth == 1 && is_saml && e.local_name().as_ref() == b"Conditions" {
+                        if let Some(value) = attribute(b"NotOnOrAfter")? {
+                            expires = Some(parse_date_time(&value).ok_or_else(|| {
+                                invalid(&format!("invalid NotOnOrAfter '{}'", value))
+                            })?);
+                        }
+                    }
+                    if matches!(event, Event::Start(_)) {
+                        depth += 1;
+                    } else if depth == 0 {
+                        complete = true;
+                    }
+                }
+                Event::End(_) => {
+                    depth -= 1;
+                    complete = depth == 0;
+                }
+                Event::Text(e) if depth == 0 && !e.unescape()?.trim().is_empty() => {
+                    return Err(invalid("text outside of the assertion"));
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        if !complete {
+            return Err(invalid("the assertion is incomplete"));
+        }
+        let id = id.ok_or_else(|| invalid("the assertion has no ID"))?;
+
+        Ok(Self {
+            xml: text.to_string(),
+            id,
+            expires,
+        })
+    }
+
+    /// Get the `saml2:Assertion` element as sent in the header
+    pub fn xml(&self) -> &str {
+        &self.xml
+    }
+
+    /// Get the `ID` of the assertion
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the `NotOnOrAfter` time of its `saml2:Conditions`, if any
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.expires
+    }
+
+    /// Whether the assertion is due for renewal at `now`
+    ///
+    /// Assertions without expiry time are never renewed.
+    fn expires_soon(&self, now: SystemTime) -> bool {
+        self.expires
+            .is_some_and(|expires| now + SAML_REFRESH_MARGIN >= expires)
+    }
+}
+
+fn invalid(reason: &str) -> SoapError {
+    SoapError::SecurityError(format!("Invalid SAML assertion: {}", reason))
+}
+
+/// Source of SAML assertions, asked for a new one before the current expires
+///
+/// Closures returning a future implement this trait:
+///
+/// ```
+/// use soapus_runtime::security::{SamlAssertion, SamlProvider};
+/// use soapus_runtime::SoapResult;
+///
+/// async fn fetch_from_sso() -> SoapResult<String> {
+///     // Request an assertion from the identity provider
+/// #   Ok(String::new())
+/// }
+///
+/// fn provider() -> impl SamlProvider {
+///     || async { SamlAssertion::new(fetch_from_sso().await?) }
+/// }
+/// ```
+pub trait SamlProvider: Send + Sync {
+    /// Get a new assertion
+    fn assertion(&self) -> Pin<Box<dyn Future<Output = SoapResult<SamlAssertion>> + Send + '_>>;
+}
+
+impl<F, Fut> SamlProvider for F
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = SoapResult<SamlAssertion>> + Send + 'static,
+{
+    fn assertion(&self) -> Pin<Box<dyn Future<Output = SoapResult<SamlAssertion>> + Send + '_>> {
+        Box::pin(self())
+    }
+}
+
+/// Where the assertion of a client comes from
+///
+/// Clones of a provider share the assertion last received from it.
+#[derive(Clone)]
+pub(crate) enum SamlSource {
+    Assertion(SamlAssertion),
+    Provider(Arc<CachingProvider>),
+}
+
+/// A provider with the assertion last received from it
+pub(crate) struct CachingProvider {
+    provider: Box<dyn SamlProvider>,
+    current: Mutex<Option<SamlAssertion>>,
+}
+
+impl SamlSource {
+    pub(crate) fn provider(provider: impl SamlProvider + 'static) -> Self {
+        SamlSource::Provider(Arc::new(CachingProvider {
+            provider: Box::new(provider),
+            current: Mutex::new(None),
+        }))
+    }
+
+    /// Get the assertion to send at `now`, renewing it if it expires soon
+    ///
+    /// Concurrent requests wait for a single renewal.
+    pub(crate) async fn assertion(&self, now: SystemTime) -> SoapResult<SamlAssertion> {
+        match self {
+            SamlSource::Assertion(assertion) => Ok(assertion.clone()),
+            SamlSource::Provider(cache) => {
+                let mut current = cache.current.lock().await;
+                match &*current {
+                    Some(assertion) if !assertion.expires_soon(now) => Ok(assertion.clone()),
+                    _ => {
+                        let assertion = cache.provider.assertion().await?;
+                        *current = Some(assertion.clone());
+                        Ok(assertion)
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SamlSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SamlSource::Assertion(assertion) => {
+                f.debug_tuple("Assertion").field(&assertion.id).finish()
+            }
+            SamlSource::Provider(_) => f.write_str("Provider"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::UNIX_EPOCH;
+
+    const ASSERTION: &str = include_str!("../../../testdata/security/saml-assertion.xml");
+
+    /// 2026-10-16T12:00:00Z, when the assertion was issued
+    fn noon() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_792_152_000)
+    }
+
+    #[test]
+    fn test_parse_assertion() {
+        let assertion = SamlAssertion::new(ASSERTION).unwrap();
+        assert_eq!(assertion.id(), "_a75adf55-01d7-40cc-929f-dbd8372ebdfc");
+        assert_eq!(
+            assertion.expires(),
+            Some(noon() + Duration::from_secs(3600))
+        );
+        assert!(assertion.xml().starts_with("<saml2:Assertion "));
+        assert!(assertion.xml().ends_with("</saml2:Assertion>"));
+        assert!(ASSERTION.contains(assertion.xml()));
+    }
+
+    #[test]
+    fn test_reject_invalid_assertions() {
+        for xml in [
+            "",
+            "<Assertion ID=\"a\"/>",
+            "<saml2:Assertion xmlns:saml2=\"urn:oasis:names:tc:SAML:2.0:assertion\"/>",
+            "<saml2:Assertion xmlns:saml2=\"urn:oasis:names:tc:SAML:2.0:assertion\" ID=\"a\">",
+            "<saml2:Assertion xmlns:saml2=\"urn:oasis:names:tc:SAML:2.0:assertion\" ID=\"a\"/><x/>",
+        ] {
+            let error = SamlAssertion::new(xml).unwrap_err();
+            assert!(
+                matches!(error, SoapError::SecurityError(_) | SoapError::XmlError(_)),
+                "{:?}",
+                error
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provider_renews_expiring_assertion() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let source = SamlSource::provider(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { SamlAssertion::new(ASSERTION) }
+        });
+
+        source.assertion(noon()).await.unwrap();
+        source.clone().assertion(noon()).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Within the margin before NotOnOrAfter a new assertion is requested
+        let renewal = noon() + Duration::from_secs(3600) - SAML_REFRESH_MARGIN;
+        source.assertion(renewal).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,190 @@
+//! XML Signature over the SOAP Body and Timestamp (X.509 Token Profile 1.0)
+//!
+//! Requests are signed with RSA-SHA256 and SHA-256 digests of the
+//! exclusively canonicalized Body and `wsu:Timestamp`. The certificate is sent
+//! as `wsse:BinarySecurityToken` and referenced from the `ds:KeyInfo`.
+
+use super::c14n::{canonicalize, Target, EXC_C14N};
+use super::x509::openssl_error;
+use super::{X509Credentials, WSU_NAMESPACE};
+use crate::envelope::SoapVersion;
+use crate::error::{SoapError, SoapResult};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use openssl::hash::{hash, MessageDigest};
+use openssl::sign::Signer;
+
+/// Namespace of the `ds` elements of XML Signature
+pub const DSIG_NAMESPACE: &str = "http://www.w3.org/2000/09/xmldsig#";
+
+/// `ValueType` of a `BinarySecurityToken` holding an X.509 certificate
+pub const X509_TOKEN_TYPE: &str =
+    "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-x509-token-profile-1.0#X509v3";
+
+pub(super) const RSA_SHA1: &str = "http://www.w3.org/2000/09/xmldsig#rsa-sha1";
+pub(super) const RSA_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#rsa-sha256";
+pub(super) const RSA_SHA512: &str = "http://www.w3.org/2001/04/xmldsig-more#rsa-sha512";
+pub(super) const SHA1: &str = "http://www.w3.org/2000/09/xmldsig#sha1";
+const SHA256: &str = "http://www.w3.org/2001/04/xmlenc#sha256";
+const SHA512: &str = "http://www.w3.org/2001/04/xmlenc#sha512";
+
+/// `wsu:Id` given to the SOAP Body of a signed request
+pub(crate) const BODY_ID: &str = "Body-1";
+
+/// `wsu:Id` of the `BinarySecurityToken` of a signed request
+pub(crate) const TOKEN_ID: &str = "X509-1";
+
+/// `Id` of the `ds:Signature` of a signed request
+pub(crate) const SIGNATURE_ID: &str = "SIG-1";
+
+/// Sign the Body of an envelope and the elements with the given `wsu:Id`s
+///
+/// The Body gets the `wsu:Id` [`BODY_ID`]. The `ds:Signature` is appended to
+/// the `wsse:Security` header block, which must hold the token
+/// [`TOKEN_ID`] of the credentials' certificate.
+pub(crate) fn sign_envelope(
+    envelope: &str,
+    version: SoapVersion,
+    credentials: &X509Credentials,
+    ids: &[&str],
+) -> SoapResult<String> {
+    let prefix = match version {
+        SoapVersion::Soap11 => "soap",
+        SoapVersion::Soap12 => "env",
+    };
+    let body_tag = format!("<{}:Body>", prefix);
+    if !envelope.contains(&body_tag) {
+        return Err(SoapError::SecurityError(
+            "Envelope without Body to sign".to_string(),
+        ));
+    }
+    let envelope = envelope.replacen(
+        &body_tag,
+        &format!(
+            r#"<{}:Body xmlns:wsu="{}" wsu:Id="{}">"#,
+            prefix, WSU_NAMESPACE, BODY_ID
+        ),
+        1,
+    );
+
+    let mut references = String::new();
+    for id in std::iter::once(BODY_ID).chain(ids.iter().copied()) {
+        let canonical = canonicalize(&envelope, Target::Id(id), &[])?;
+        let digest = hash(MessageDigest::sha256(), canonical.as_bytes())
+            .map_err(openssl_error("Failed to compute digest"))?;
+        references.push_str(&format!(
+            r##"<ds:Reference URI="#{}"><ds:Transforms><ds:Transform Algorithm="{}"/></ds:Transforms><ds:DigestMethod Algorithm="{}"/><ds:DigestValue>{}</ds:DigestValue></ds:Reference>"##,
+            id,
+            EXC_C14N,
+            SHA256,
+            BASE64.encode(digest)
+        ));
+    }
+
+    let signature = format!(
+        r##"<ds:Signature xmlns:ds="{}" Id="{}"><ds:SignedInfo><ds:CanonicalizationMethod Algorithm="{}"/><ds:SignatureMethod Algorithm="{}"/>{}</ds:SignedInfo><ds:SignatureValue></ds:SignatureValue><ds:KeyInfo><wsse:SecurityTokenReference><wsse:Reference URI="#{}" ValueType="{}"/></wsse:SecurityTokenReference></ds:KeyInfo></ds:Signature>"##,
+        DSIG_NAMESPACE, SIGNATURE_ID, EXC_C14N, RSA_SHA256, references, TOKEN_ID, X509_TOKEN_TYPE
+    );
+    // The signature value is not part of the signed info. It is canonicalized
+    // on its own, the header may hold other signatures, e.g. of a SAML assertion
+    let signed_info = canonicalize(&signature, signed_info_target(), &[])?;
+
+    let Some(pos) = envelope.find("</wsse:Security>") else {
+        return Err(SoapError::SecurityError(
+            "Envelope without wsse:Security header".to_string(),
+        ));
+    };
+    let envelope = format!("{}{}{}", &envelope[..pos], signature, &envelope[pos..]);
+
+    let mut signer = Signer::new(MessageDigest::sha256(), credentials.private_key())
+        .map_err(openssl_error("Failed to sign"))?;
+    let value = signer
+        .sign_oneshot_to_vec(signed_info.as_bytes())
+        .map_err(openssl_error("Failed to sign"))?;
+
+    Ok(envelope.replacen(
+        "<ds:SignatureValue></ds:SignatureValue>",
+        &format!(
+            "<ds:SignatureValue>{}</ds:SignatureValue>",
+            BASE64.encode(value)
+        ),
+        1,
+    ))
+}
+
+pub(super) fn signed_info_target() -> Target<'static> {
+    Target::Element {
+        namespace: DSIG_NAMESPACE,
+        local_name: "SignedInfo",
+    }
+}
+
+/// Get the digest of a `ds:DigestMethod` algorithm URI
+pub(super) fn digest_method(uri: &str) -> SoapResult<MessageDigest> {
+    match uri {
+        SHA1 => Ok(MessageDigest::sha1()),
+        SHA256 => Ok(MessageDigest::sha256()),
+        SHA512 => Ok(MessageDigest::sha512()),
+        other => Err(SoapError::SecurityError(format!(
+            "Unsupported digest method {}",
+            other
+        ))),
+    }
+}
+
+/// Decode Base64 content, which may be broken into lines
+pub(super) fn decode_base64(text: &str) -> SoapResult<Vec<u8>> {
+    let text: String = text.split_whitespace().collect();
+    BASE64
+        .decode(text)
+        .map_err(|e| SoapError::SecurityError(format!("Invalid Base64 value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::envelope::SOAP_11_ENVELOPE_NS;
+    use crate::security::{verify_signature, Certificate};
+
+    const CLIENT_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/client.crt");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../../testdata/security/client.key");
+    const SERVER_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/server.crt");
+
+    fn client_credentials() -> X509Credentials {
+        X509Credentials::from_pem(CLIENT_CERTIFICATE, CLIENT_KEY).unwrap()
+    }
+
+    fn envelope(credentials: &X509Credentials) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><soap:Envelope xmlns:soap="{}"><soap:Header><wsse:Security xmlns:wsse="{}" xmlns:wsu="{}">{}</wsse:Security></soap:Header><soap:Body><Ping xmlns="urn:ping"><Id>1</Id></Ping></soap:Body></soap:Envelope>"#,
+            SOAP_11_ENVELOPE_NS,
+            super::super::WSSE_NAMESPACE,
+            WSU_NAMESPACE,
+            credentials.certificate().binary_security_token()
+        )
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let credentials = client_credentials();
+        let signed = sign_envelope(
+            &envelope(&credentials),
+            SoapVersion::Soap11,
+            &credentials,
+            &[],
+        )
+        .unwrap();
+        assert!(signed.contains(r#"<soap:Body xmlns:wsu=""#));
+        assert!(signed.contains(r##"<ds:Reference URI="#Body-1">"##));
+        assert!(signed.contains(r##"<wsse:Reference URI="#X509-1""##));
+
+        verify_signature(&signed, credentials.certificate()).unwrap();
+
+        let server = Certificate::from_pem(SERVER_CERTIFICATE).unwrap();
+        assert!(verify_signature(&signed, &server).is_err());
+
+        let tampered = signed.replace("<Id>1</Id>", "<Id>2</Id>");
+        let error = verify_signature(&tampered, credentials.certificate()).unwrap_err();
+        assert!(error.to_string().contains("Digest of reference #Body-1"));
+    }
+}
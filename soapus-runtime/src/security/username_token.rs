@@ -0,0 +1,163 @@
+//! `wsse:UsernameToken` (Username Token Profile 1.0)
+
+use super::BASE64_ENCODING;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use quick_xml::escape::escape;
+use sha1::{Digest, Sha1};
+use std::fmt;
+
+/// How the password of a `UsernameToken` is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PasswordType {
+    /// The password in plain text, only safe over TLS
+    #[default]
+    Text,
+    /// `Base64(SHA-1(nonce + created + password))`, along with nonce and creation time
+    Digest,
+}
+
+impl PasswordType {
+    /// URI of the `Type` attribute of `wsse:Password`
+    pub fn uri(&self) -> String {
+        let name = match self {
+            PasswordType::Text => "PasswordText",
+            PasswordType::Digest => "PasswordDigest",
+        };
+        format!("{}#{}", USERNAME_TOKEN_PROFILE, name)
+    }
+}
+
+/// Base URI of the Username Token Profile 1.0
+const USERNAME_TOKEN_PROFILE: &str =
+    "http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-username-token-profile-1.0";
+
+/// Credentials sent as `wsse:UsernameToken`
+///
+/// The password is left out of the `Debug` output.
+#[derive(Clone, PartialEq, Eq)]
+pub struct UsernameToken {
+    username: String,
+    password: String,
+    password_type: PasswordType,
+}
+
+impl UsernameToken {
+    /// Create a token sending the password as `password_type`
+    pub fn new(
+        username: impl Into<String>,
+        password: impl Into<String>,
+        password_type: PasswordType,
+    ) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+            password_type,
+        }
+    }
+
+    /// Send the password as `password_type` instead
+    pub fn with_password_type(self, password_type: PasswordType) -> Self {
+        Self {
+            password_type,
+            ..self
+        }
+    }
+
+    /// Get the username
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Get how the password is sent
+    pub fn password_type(&self) -> PasswordType {
+        self.password_type
+    }
+
+    /// Get the XML of the token
+    ///
+    /// The nonce and creation time are only sent with a password digest. The
+    /// namespace prefixes are the ones declared by the `wsse:Security` element.
+    pub(crate) fn to_xml(&self, created: &str, nonce: &[u8]) -> String {
+        let mut xml = format!(
+            r#"<wsse:UsernameToken wsu:Id="UsernameToken-1"><wsse:Username>{}</wsse:Username>"#,
+            escape(self.username.as_str())
+        );
+        match self.password_type {
+            PasswordType::Text => xml.push_str(&format!(
+                r#"<wsse:Password Type="{}">{}</wsse:Password>"#,
+                self.password_type.uri(),
+                escape(self.password.as_str())
+            )),
+            PasswordType::Digest => xml.push_str(&format!(
+                r#"<wsse:Password Type="{}">{}</wsse:Password><wsse:Nonce EncodingType="{}">{}</wsse:Nonce><wsu:Created>{}</wsu:Created>"#,
+                self.password_type.uri(),
+                password_digest(nonce, created, &self.password),
+                BASE64_ENCODING,
+                BASE64.encode(nonce),
+                created
+            )),
+        }
+        xml.push_str("</wsse:UsernameToken>");
+        xml
+    }
+}
+
+impl fmt::Debug for UsernameToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsernameToken")
+            .field("username", &self.username)
+            .field("password", &"***")
+            .field("password_type", &self.password_type)
+            .finish()
+    }
+}
+
+/// Compute a password digest: `Base64(SHA-1(nonce + created + password))`
+///
+/// `nonce` are the raw bytes, not their Base64 encoding, `created` is the
+/// `wsu:Created` value as sent.
+pub fn password_digest(nonce: &[u8], created: &str, password: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(nonce);
+    hasher.update(created.as_bytes());
+    hasher.update(password.as_bytes());
+    BASE64.encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_digest() {
+        let nonce: Vec<u8> = (0..16).collect();
+        assert_eq!(
+            password_digest(&nonce, "2026-10-16T12:00:00.000Z", "secret"),
+            "hOH2tOVEqKegF7ds+J2fvQcfJ8M="
+        );
+    }
+
+    #[test]
+    fn test_password_text() {
+        let token = UsernameToken::new("admin", "s<cr>t", PasswordType::Text);
+        assert_eq!(
+            token.to_xml("2026-10-16T12:00:00.000Z", &[0; 16]),
+            format!(
+                r#"<wsse:UsernameToken wsu:Id="UsernameToken-1"><wsse:Username>admin</wsse:Username><wsse:Password Type="{}#PasswordText">s&lt;cr&gt;t</wsse:Password></wsse:UsernameToken>"#,
+                USERNAME_TOKEN_PROFILE
+            )
+        );
+        assert!(!format!("{:?}", token).contains("s<cr>t"));
+    }
+
+    #[test]
+    fn test_password_digest_token() {
+        let nonce: Vec<u8> = (0..16).collect();
+        let token = UsernameToken::new("admin", "secret", PasswordType::Digest);
+        let xml = token.to_xml("2026-10-16T12:00:00.000Z", &nonce);
+        assert!(xml.contains("#PasswordDigest\">hOH2tOVEqKegF7ds+J2fvQcfJ8M=</wsse:Password>"));
+        assert!(xml.contains("#Base64Binary\">AAECAwQFBgcICQoLDA0ODw==</wsse:Nonce>"));
+        assert!(xml.contains("<wsu:Created>2026-10-16T12:00:00.000Z</wsu:Created>"));
+    }
+}
@@ -0,0 +1,352 @@
+//! Verification of XML Signatures of SOAP responses
+//!
+//! The signature must cover the Body of the Envelope, so that a signed
+//! element moved elsewhere in the document doesn't pass as the Body.
+
+use super::c14n::{canonicalize, is_id_attribute, Target, EXC_C14N};
+use super::signature::{
+    decode_base64, digest_method, signed_info_target, DSIG_NAMESPACE, RSA_SHA1, RSA_SHA256,
+    RSA_SHA512,
+};
+use super::x509::openssl_error;
+use super::{format_date_time, parse_date_time, Certificate, WSU_NAMESPACE};
+use crate::envelope::{SOAP_11_ENVELOPE_NS, SOAP_12_ENVELOPE_NS};
+use crate::error::{SoapError, SoapResult};
+use openssl::hash::{hash, MessageDigest};
+use openssl::sign::Verifier;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+use serde::Deserialize;
+use std::time::SystemTime;
+
+/// Verify the XML Signature of a SOAP envelope
+///
+/// The first `ds:Signature` of the envelope must be made with the key of
+/// `certificate`, one of its references must be the Body that is a child of
+/// the Envelope and the digests of all referenced elements must match. If
+/// the `wsu:Timestamp` is signed, its `wsu:Expires` time must not have
+/// passed. Exclusive canonicalization with RSA-SHA1, RSA-SHA256 or
+/// RSA-SHA512 signatures and SHA-1, SHA-256 or SHA-512 digests are supported.
+pub fn verify_signature(xml: &str, certificate: &Certificate) -> SoapResult<()> {
+    verify_signature_at(xml, certificate, SystemTime::now())
+}
+
+/// Verify the XML Signature of a SOAP envelope at the given time
+pub(crate) fn verify_signature_at(
+    xml: &str,
+    certificate: &Certificate,
+    now: SystemTime,
+) -> SoapResult<()> {
+    let signature = canonicalize(xml, signature_target(), &[])?;
+    let signature: SignatureElement = quick_xml::de::from_str(&signature)
+        .map_err(|e| SoapError::SecurityError(format!("Invalid ds:Signature: {}", e)))?;
+    let signed_info = &signature.signed_info;
+
+    let body_id = body_id(xml)?.ok_or_else(|| {
+        SoapError::SecurityError("The SOAP Body of the response is not signed".to_string())
+    })?;
+    if !signed_info
+        .references
+        .iter()
+        .any(|reference| reference.uri.strip_prefix('#') == Some(body_id.as_str()))
+    {
+        return Err(SoapError::SecurityError(
+            "The SOAP Body of the response is not signed".to_string(),
+        ));
+    }
+
+    for reference in &signed_info.references {
+        verify_reference(xml, reference)?;
+    }
+
+    let signed_ids: Vec<&str> = signed_info
+        .references
+        .iter()
+        .filter_map(|reference| reference.uri.strip_prefix('#'))
+        .collect();
+    if let Some(expires) = timestamp_expiry(xml, &signed_ids)? {
+        if now > expires {
+            return Err(SoapError::SecurityError(format!(
+                "The Timestamp of the response expired at {}",
+                format_date_time(expires)
+            )));
+        }
+    }
+
+    let prefixes = exc_c14n_prefixes(&signed_info.canonicalization_method)?;
+    let canonical = canonicalize(xml, signed_info_target(), &prefixes)?;
+    let digest = match signed_info.signature_method.algorithm.as_str() {
+        RSA_SHA1 => MessageDigest::sha1(),
+        RSA_SHA256 => MessageDigest::sha256(),
+        RSA_SHA512 => MessageDigest::sha512(),
+        other => {
+            return Err(SoapError::SecurityError(format!(
+                "Unsupported signature method {}",
+                other
+            )))
+        }
+    };
+    let public_key = certificate.public_key()?;
+    let value = decode_base64(&signature.signature_value)?;
+    let valid = Verifier::new(digest, &public_key)
+        .and_then(|mut verifier| verifier.verify_oneshot(&value, canonical.as_bytes()))
+        .map_err(openssl_error("Failed to verify signature"))?;
+    if !valid {
+        return Err(SoapError::SecurityError(
+            "Signature value does not match".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check the digest of a `ds:Reference` to an element of the envelope
+fn verify_reference(xml: &str, reference: &Reference) -> SoapResult<()> {
+    let Some(id) = reference.uri.strip_prefix('#') else {
+        return Err(SoapError::SecurityError(format!(
+            "Unsupported reference URI \"{}\"",
+            reference.uri
+        )));
+    };
+    let transforms = reference
+        .transforms
+        .as_ref()
+        .map(|transforms| transforms.transforms.as_slice())
+        .unwrap_or_default();
+    let [transform] = transforms else {
+        return Err(SoapError::SecurityError(format!(
+            "Reference #{} must have exactly one transform",
+            id
+        )));
+    };
+    let prefixes = exc_c14n_prefixes(transform)?;
+    let canonical = canonicalize(xml, Target::Id(id), &prefixes)?;
+
+    let digest = digest_method(&reference.digest_method.algorithm)?;
+    let actual =
+        hash(digest, canonical.as_bytes()).map_err(openssl_error("Failed to compute digest"))?;
+    let expected = decode_base64(&reference.digest_value)?;
+    if expected.len() != actual.len() || !openssl::memcmp::eq(&expected, &actual) {
+        return Err(SoapError::SecurityError(format!(
+            "Digest of reference #{} does not match",
+            id
+        )));
+    }
+    Ok(())
+}
+
+/// Get the inclusive namespace prefixes of an exclusive canonicalization method
+fn exc_c14n_prefixes(method: &Method) -> SoapResult<Vec<&str>> {
+    if method.algorithm != EXC_C14N {
+        return Err(SoapError::SecurityError(format!(
+            "Unsupported canonicalization method {}",
+            method.algorithm
+        )));
+    }
+    Ok(method
+        .inclusive_namespaces
+        .as_ref()
+        .map(|inclusive| inclusive.prefix_list.split_whitespace().collect())
+        .unwrap_or_default())
+}
+
+/// Get the Id attribute of the Body of a SOAP 1.1 or 1.2 envelope
+///
+/// Only a Body that is a child of the Envelope counts, so that a signed Body
+/// moved elsewhere in the document (signature wrapping) is not taken for it.
+fn body_id(xml: &str) -> SoapResult<Option<String>> {
+    let mut reader = NsReader::from_str(xml);
+    let mut depth = 0;
+    loop {
+        let (namespace, event) = reader.read_resolved_event()?;
+        let in_soap_namespace = matches!(
+            namespace,
+            ResolveResult::Bound(Namespace(ns))
+                if ns == SOAP_11_ENVELOPE_NS.as_bytes() || ns == SOAP_12_ENVELOPE_NS.as_bytes()
+        );
+        match event {
+            Event::Start(e) | Event::Empty(e)
+                if depth == 1 && in_soap_namespace && e.local_name().as_ref() == b"Body" =>
+            {
+                return id_attribute(&e);
+            }
+            Event::Start(e)
+                if depth == 0 && !(in_soap_namespace && e.local_name().as_ref() == b"Envelope") =>
+            {
+                return Ok(None);
+            }
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Get the `wsu:Expires` time of a `wsu:Timestamp` with one of the given Ids
+fn timestamp_expiry(xml: &str, ids: &[&str]) -> SoapResult<Option<SystemTime>> {
+    let mut reader = NsReader::from_str(xml);
+    let mut in_timestamp = false;
+    loop {
+        let (namespace, event) = reader.read_resolved_event()?;
+        let in_wsu_namespace = matches!(
+            namespace,
+            ResolveResult::Bound(Namespace(ns)) if ns == WSU_NAMESPACE.as_bytes()
+        );
+        match event {
+            Event::Start(e) if in_wsu_namespace && e.local_name().as_ref() == b"Timestamp" => {
+                in_timestamp = id_attribute(&e)?.is_some_and(|id| ids.contains(&id.as_str()));
+            }
+            Event::Start(e)
+                if in_timestamp && in_wsu_namespace && e.local_name().as_ref() == b"Expires" =>
+            {
+                let text = reader.read_text(e.name())?;
+                return match parse_date_time(&text) {
+                    Some(expires) => Ok(Some(expires)),
+                    None => Err(SoapError::SecurityError(format!(
+                        "Invalid wsu:Expires \"{}\"",
+                        text.trim()
+                    ))),
+                };
+            }
+            Event::End(e) if in_wsu_namespace && e.local_name().as_ref() == b"Timestamp" => {
+                in_timestamp = false;
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Get the value of the attribute identifying an element (`Id`, `wsu:Id`, `ID`), if any
+fn id_attribute(e: &BytesStart<'_>) -> SoapResult<Option<String>> {
+    for attribute in e.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        let name = String::from_utf8_lossy(attribute.key.as_ref());
+        if is_id_attribute(&name) {
+            return Ok(Some(attribute.unescape_value()?.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+fn signature_target() -> Target<'static> {
+    Target::Element {
+        namespace: DSIG_NAMESPACE,
+        local_name: "Signature",
+    }
+}
+
+#[derive(Deserialize)]
+struct SignatureElement {
+    #[serde(rename = "SignedInfo")]
+    signed_info: SignedInfo,
+    #[serde(rename = "SignatureValue")]
+    signature_value: String,
+}
+
+#[derive(Deserialize)]
+struct SignedInfo {
+    #[serde(rename = "CanonicalizationMethod")]
+    canonicalization_method: Method,
+    #[serde(rename = "SignatureMethod")]
+    signature_method: Method,
+    #[serde(rename = "Reference", default)]
+    references: Vec<Reference>,
+}
+
+#[derive(Deserialize)]
+struct Reference {
+    #[serde(rename = "@URI")]
+    uri: String,
+    #[serde(rename = "Transforms")]
+    transforms: Option<Transforms>,
+    #[serde(rename = "DigestMethod")]
+    digest_method: Method,
+    #[serde(rename = "DigestValue")]
+    digest_value: String,
+}
+
+#[derive(Deserialize)]
+struct Transforms {
+    #[serde(rename = "Transform", default)]
+    transforms: Vec<Method>,
+}
+
+/// An element naming an algorithm, e.g. `ds:Transform`
+#[derive(Deserialize)]
+struct Method {
+    #[serde(rename = "@Algorithm")]
+    algorithm: String,
+    #[serde(rename = "InclusiveNamespaces")]
+    inclusive_namespaces: Option<InclusiveNamespaces>,
+}
+
+#[derive(Deserialize)]
+struct InclusiveNamespaces {
+    #[serde(rename = "@PrefixList", default)]
+    prefix_list: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/server.crt");
+    const SIGNED_RESPONSE: &str = include_str!("../../../testdata/security/signed-response.xml");
+
+    /// A time before the Timestamp of the signed response expires
+    fn before_expiry() -> SystemTime {
+        parse_date_time("2026-10-16T12:01:00Z").unwrap()
+    }
+
+    #[test]
+    fn test_verify_signed_response() {
+        let server = Certificate::from_pem(SERVER_CERTIFICATE).unwrap();
+        verify_signature_at(SIGNED_RESPONSE, &server, before_expiry()).unwrap();
+
+        let tampered = SIGNED_RESPONSE.replace("2026-10-16T12:00:00Z", "2026-10-16T13:00:00Z");
+        let error = verify_signature_at(&tampered, &server, before_expiry()).unwrap_err();
+        assert!(error.to_string().contains("Digest of reference #TS-1"));
+    }
+
+    #[test]
+    fn test_expired_timestamp() {
+        let server = Certificate::from_pem(SERVER_CERTIFICATE).unwrap();
+        let expired = parse_date_time("2026-10-16T12:05:01Z").unwrap();
+        let error = verify_signature_at(SIGNED_RESPONSE, &server, expired).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("expired at 2026-10-16T12:05:00.000Z"));
+    }
+
+    #[test]
+    fn test_unsigned_body() {
+        let server = Certificate::from_pem(SERVER_CERTIFICATE).unwrap();
+        let unsigned = SIGNED_RESPONSE.replace("wsu:Id=\"Body-1\"", "");
+        let error = verify_signature_at(&unsigned, &server, before_expiry()).unwrap_err();
+        assert!(error.to_string().contains("not signed"));
+    }
+
+    #[test]
+    fn test_signature_wrapping() {
+        // The signed Body is moved into the header and replaced by another
+        let server = Certificate::from_pem(SERVER_CERTIFICATE).unwrap();
+        let start = SIGNED_RESPONSE.find("<S:Body").unwrap();
+        let end = SIGNED_RESPONSE.find("</S:Body>").unwrap() + "</S:Body>".len();
+        let signed_body = &SIGNED_RESPONSE[start..end];
+        let wrapped = SIGNED_RESPONSE
+            .replacen(
+                signed_body,
+                r#"<S:Body><m:PingResponse xmlns:m="urn:ping"><m:Result>forged</m:Result></m:PingResponse></S:Body>"#,
+                1,
+            )
+            .replacen(
+                "</wsse:Security>",
+                &format!("</wsse:Security><Wrapper>{}</Wrapper>", signed_body),
+                1,
+            );
+        let error = verify_signature_at(&wrapped, &server, before_expiry()).unwrap_err();
+        assert!(error.to_string().contains("not signed"));
+    }
+}
@@ -0,0 +1,179 @@
+//! X.509 certificates and credentials to sign and encrypt with
+
+use super::signature::{TOKEN_ID, X509_TOKEN_TYPE};
+use super::BASE64_ENCODING;
+use crate::error::{SoapError, SoapResult};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use openssl::error::ErrorStack;
+use openssl::pkcs12::Pkcs12;
+use openssl::pkey::{Id, PKey, Private, Public};
+use openssl::x509::X509;
+use std::fmt;
+
+/// An X.509 certificate
+#[derive(Clone)]
+pub struct Certificate {
+    x509: X509,
+    der: Vec<u8>,
+}
+
+impl Certificate {
+    /// Load a certificate from PEM (`-----BEGIN CERTIFICATE-----`)
+    pub fn from_pem(pem: &[u8]) -> SoapResult<Self> {
+        let x509 = X509::from_pem(pem).map_err(openssl_error("Invalid PEM certificate"))?;
+        Self::new(x509)
+    }
+
+    /// Load a DER encoded certificate
+    pub fn from_der(der: &[u8]) -> SoapResult<Self> {
+        let x509 = X509::from_der(der).map_err(openssl_error("Invalid DER certificate"))?;
+        Self::new(x509)
+    }
+
+    fn new(x509: X509) -> SoapResult<Self> {
+        let der = x509
+            .to_der()
+            .map_err(openssl_error("Invalid certificate"))?;
+        Ok(Self { x509, der })
+    }
+
+    /// Get the DER encoding of the certificate
+    pub fn der(&self) -> &[u8] {
+        &self.der
+    }
+
+    /// Get the public key of the certificate
+    pub(super) fn public_key(&self) -> SoapResult<PKey<Public>> {
+        self.x509
+            .public_key()
+            .map_err(openssl_error("Invalid certificate"))
+    }
+
+    /// Get the `wsse:BinarySecurityToken` holding the certificate
+    pub(crate) fn binary_security_token(&self) -> String {
+        format!(
+            r#"<wsse:BinarySecurityToken EncodingType="{}" ValueType="{}" wsu:Id="{}">{}</wsse:BinarySecurityToken>"#,
+            BASE64_ENCODING,
+            X509_TOKEN_TYPE,
+            TOKEN_ID,
+            BASE64.encode(&self.der)
+        )
+    }
+}
+
+impl fmt::Debug for Certificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Certificate")
+            .field("subject", &self.x509.subject_name())
+            .finish()
+    }
+}
+
+impl PartialEq for Certificate {
+    fn eq(&self, other: &Self) -> bool {
+        self.der == other.der
+    }
+}
+
+/// An X.509 certificate together with its RSA private key
+///
+/// The private key is left out of the `Debug` output.
+#[derive(Clone)]
+pub struct X509Credentials {
+    certificate: Certificate,
+    private_key: PKey<Private>,
+}
+
+impl X509Credentials {
+    /// Load the certificate and the private key from PEM
+    ///
+    /// The key may be PKCS#8 (`BEGIN PRIVATE KEY`) or PKCS#1 (`BEGIN RSA
+    /// PRIVATE KEY`), unencrypted.
+    pub fn from_pem(certificate: &[u8], private_key: &[u8]) -> SoapResult<Self> {
+        let private_key = PKey::private_key_from_pem(private_key)
+            .map_err(openssl_error("Invalid PEM private key"))?;
+        Self::new(Certificate::from_pem(certificate)?, private_key)
+    }
+
+    /// Load the certificate and the private key from a PKCS#12 (`.p12`, `.pfx`) file
+    pub fn from_pkcs12(der: &[u8], password: &str) -> SoapResult<Self> {
+        let parsed = Pkcs12::from_der(der)
+            .and_then(|pkcs12| pkcs12.parse2(password))
+            .map_err(openssl_error("Invalid PKCS#12 file"))?;
+        match (parsed.cert, parsed.pkey) {
+            (Some(certificate), Some(private_key)) => {
+                Self::new(Certificate::new(certificate)?, private_key)
+            }
+            _ => Err(SoapError::SecurityError(
+                "PKCS#12 file without certificate or private key".to_string(),
+            )),
+        }
+    }
+
+    fn new(certificate: Certificate, private_key: PKey<Private>) -> SoapResult<Self> {
+        if private_key.id() != Id::RSA {
+            return Err(SoapError::SecurityError(
+                "Only RSA keys are supported".to_string(),
+            ));
+        }
+        let public_key = certificate.public_key()?;
+        if !public_key.public_eq(&private_key) {
+            return Err(SoapError::SecurityError(
+                "Private key does not belong to the certificate".to_string(),
+            ));
+        }
+        Ok(Self {
+            certificate,
+            private_key,
+        })
+    }
+
+    /// Get the certificate
+    pub fn certificate(&self) -> &Certificate {
+        &self.certificate
+    }
+
+    /// Get the private key
+    pub(super) fn private_key(&self) -> &PKey<Private> {
+        &self.private_key
+    }
+}
+
+impl fmt::Debug for X509Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("X509Credentials")
+            .field("certificate", &self.certificate)
+            .field("private_key", &"***")
+            .finish()
+    }
+}
+
+pub(super) fn openssl_error(context: &'static str) -> impl Fn(ErrorStack) -> SoapError {
+    move |e| SoapError::SecurityError(format!("{}: {}", context, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/client.crt");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../../testdata/security/client.key");
+    const CLIENT_PKCS12: &[u8] = include_bytes!("../../../testdata/security/client.p12");
+    const SERVER_CERTIFICATE: &[u8] = include_bytes!("../../../testdata/security/server.crt");
+
+    fn client_credentials() -> X509Credentials {
+        X509Credentials::from_pem(CLIENT_CERTIFICATE, CLIENT_KEY).unwrap()
+    }
+
+    #[test]
+    fn test_load_credentials() {
+        let from_pem = client_credentials();
+        let from_pkcs12 = X509Credentials::from_pkcs12(CLIENT_PKCS12, "changeit").unwrap();
+        assert_eq!(from_pem.certificate(), from_pkcs12.certificate());
+        assert!(!format!("{:?}", from_pem).contains("PRIVATE"));
+
+        assert!(X509Credentials::from_pkcs12(CLIENT_PKCS12, "wrong").is_err());
+        assert!(X509Credentials::from_pem(SERVER_CERTIFICATE, CLIENT_KEY).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+//! Polymorphism via `xsi:type`
+//!
+//! Where a schema expects a base type, a derived type may be sent instead.
+//! The element then names its actual type in the `xsi:type` attribute:
+//!
+//! ```xml
+//! <employee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+//!           xsi:type="tns:Manager">
+//!   <name>Jane</name>
+//!   <budget>1000</budget>
+//! </employee>
+//! ```
+//!
+//! Generated code represents such a base type as an enum with one variant per
+//! known type. Its `Deserialize` implementation uses [`deserialize_typed`] to
+//! select the variant from `xsi:type`, its `Serialize` implementation wraps
+//! derived variants in [`XsiTyped`] to emit the attribute.
+
+use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+use serde::{Deserializer, Serialize};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// XML Schema instance namespace
+pub const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Key of the `xsi:type` attribute as seen by serde
+///
+/// quick-xml strips the prefix from attribute names, so a `type` attribute
+/// without namespace is indistinguishable from `xsi:type`.
+const TYPE_KEY: &str = "@type";
+
+/// A type whose variants are selected by `xsi:type`
+pub trait XsiTypeDispatch: Sized {
+    /// Name of the base type, used in error messages
+    const BASE_TYPE: &'static str;
+
+    /// Serde names of the fields of all variants
+    ///
+    /// Passed to the deserializer, which uses them to tell `$value` content
+    /// apart from named fields.
+    const FIELDS: &'static [&'static str];
+
+    /// Deserialize the variant for a type name
+    ///
+    /// `type_name` is the local name of the `xsi:type` attribute, or `None`
+    /// if the element has none.
+    fn deserialize_variant<'de, D>(
+        type_name: Option<&str>,
+        deserializer: D,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// Deserialize a polymorphic type, dispatching on its `xsi:type` attribute
+pub fn deserialize_typed<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: XsiTypeDispatch,
+{
+    deserializer.deserialize_struct(T::BASE_TYPE, T::FIELDS, TypedVisitor(PhantomData))
+}
+
+/// Error for an `xsi:type` that names no known variant
+pub fn unknown_type<E: de::Error>(type_name: Option<&str>, base_type: &str) -> E {
+    match type_name {
+        Some(type_name) => E::custom(format!(
+            "unknown xsi:type `{}` for type `{}`",
+            type_name, base_type
+        )),
+        None => E::custom(format!(
+            "missing xsi:type for abstract type `{}`",
+            base_type
+        )),
+    }
+}
+
+/// Visitor reading attributes until `xsi:type` is found, then handing the rest to the variant
+struct TypedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: XsiTypeDispatch> Visitor<'de> for TypedVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an element of type {}", T::BASE_TYPE)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let mut attributes = Vec::new();
+        let mut pending_key = None;
+        let mut type_name = None;
+
+        // Attributes come before child elements, so stop at the first element
+        while let Some(key) = map.next_key::<String>()? {
+            if key == TYPE_KEY {
+                let value: String = map.next_value()?;
+                type_name = Some(match value.rsplit_once(':') {
+                    Some((_, local_name)) => local_name.to_string(),
+                    None => value,
+                });
+                break;
+            } else if key.starts_with('@') {
+                attributes.push((key, map.next_value::<String>()?));
+            } else {
+                pending_key = Some(key);
+                break;
+            }
+        }
+
+        let replay = ReplayMap {
+            attributes: attributes.into_iter(),
+            value: None,
+            pending_key,
+            map,
+        };
+        T::deserialize_variant(
+            type_name.as_deref(),
+            de::value::MapAccessDeserializer::new(replay),
+        )
+    }
+}
+
+/// Map access replaying the entries read while looking for `xsi:type`
+struct ReplayMap<A> {
+    attributes: std::vec::IntoIter<(String, String)>,
+    /// Value of the replayed attribute whose key was returned last
+    value: Option<String>,
+    /// Key of the first child element, whose value is still in `map`
+    pending_key: Option<String>,
+    map: A,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for ReplayMap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if let Some((key, value)) = self.attributes.next() {
+            self.value = Some(value);
+            seed.deserialize(key.into_deserializer()).map(Some)
+        } else if let Some(key) = self.pending_key.take() {
+            seed.deserialize(key.into_deserializer()).map(Some)
+        } else {
+            self.map.next_key_seed(seed)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(AttributeValue {
+                value,
+                marker: PhantomData,
+            }),
+            None => self.map.next_value_seed(seed),
+        }
+    }
+}
+
+/// Deserializer for a replayed attribute value, parsing primitives from text
+struct AttributeValue<E> {
+    value: String,
+    marker: PhantomData<E>,
+}
+
+impl<E: de::Error> AttributeValue<E> {
+    fn invalid(&self, expected: &dyn de::Expected) -> E {
+        E::invalid_value(de::Unexpected::Str(&self.value), expected)
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+                match self.value.trim().parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(self.invalid(&visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for AttributeValue<E> {
+    type Error = E;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_string(self.value)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        match self.value.trim() {
+            "true" | "1" => visitor.visit_bool(true),
+            "false" | "0" => visitor.visit_bool(false),
+            _ => Err(self.invalid(&visitor)),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, E> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, E> {
+        visitor.visit_enum(self.value.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// A value serialized with an `xsi:type` attribute naming its type
+///
+/// The attribute and the namespace declarations it needs are added to the
+/// element the value is serialized into.
+#[derive(Serialize)]
+pub struct XsiTyped<'a, T> {
+    #[serde(rename = "@xmlns:xsi")]
+    xsi_namespace: &'static str,
+    #[serde(rename = "@xmlns:tns", skip_serializing_if = "Option::is_none")]
+    type_namespace: Option<&'a str>,
+    #[serde(rename = "@xsi:type")]
+    type_name: String,
+    #[serde(flatten)]
+    value: &'a T,
+}
+
+impl<'a, T> XsiTyped<'a, T> {
+    /// Wrap a value of the type `type_name` from the namespace `namespace`
+    pub fn new(type_name: &str, namespace: Option<&'a str>, value: &'a T) -> Self {
+        let type_name = match namespace {
+            Some(_) => format!("tns:{}", type_name),
+            None => type_name.to_string(),
+        };
+        Self {
+            xsi_namespace: XSI_NAMESPACE,
+            type_namespace: namespace,
+            type_name,
+            value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Shape {
+        name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Circle {
+        #[serde(rename = "@unit", skip_serializing_if = "Option::is_none")]
+        unit: Option<String>,
+        #[serde(rename = "@precision", skip_serializing_if = "Option::is_none")]
+        precision: Option<u8>,
+        name: String,
+        radius: f64,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum AnyShape {
+        Shape(Shape),
+        Circle(Circle),
+    }
+
+    impl XsiTypeDispatch for AnyShape {
+        const BASE_TYPE: &'static str = "Shape";
+        const FIELDS: &'static [&'static str] = &["@unit", "@precision", "name", "radius"];
+
+        fn deserialize_variant<'de, D>(
+            type_name: Option<&str>,
+            deserializer: D,
+        ) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match type_name {
+                None | Some("Shape") => Shape::deserialize(deserializer).map(Self::Shape),
+                Some("Circle") => Circle::deserialize(deserializer).map(Self::Circle),
+                _ => Err(unknown_type(type_name, Self::BASE_TYPE)),
+            }
+        }
+    }
+
+    impl Serialize for AnyShape {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                Self::Shape(value) => value.serialize(serializer),
+                Self::Circle(value) => {
+                    XsiTyped::new("Circle", Some("urn:shapes"), value).serialize(serializer)
+                }
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for AnyShape {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserialize_typed(deserializer)
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Drawing {
+        shape: Vec<AnyShape>,
+    }
+
+    #[test]
+    fn test_dispatch_on_xsi_type() {
+        let xml = r#"<Drawing xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:s="urn:shapes">
+            <shape><name>plain</name></shape>
+            <shape unit="cm" xsi:type="s:Circle" precision="2"><name>round</name><radius>1.5</radius></shape>
+        </Drawing>"#;
+
+        let drawing: Drawing = quick_xml::de::from_str(xml).unwrap();
+        assert_eq!(
+            drawing.shape,
+            vec![
+                AnyShape::Shape(Shape {
+                    name: "plain".to_string()
+                }),
+                AnyShape::Circle(Circle {
+                    unit: Some("cm".to_string()),
+                    precision: Some(2),
+                    name: "round".to_string(),
+                    radius: 1.5,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_xsi_type() {
+        let xml = r#"<Drawing><shape xsi:type="Square"><name>x</name></shape></Drawing>"#;
+
+        let error = quick_xml::de::from_str::<Drawing>(xml).unwrap_err();
+        assert!(error.to_string().contains("unknown xsi:type `Square`"));
+    }
+
+    #[test]
+    fn test_serialize_xsi_type() {
+        let drawing = Drawing {
+            shape: vec![
+                AnyShape::Shape(Shape {
+                    name: "plain".to_string(),
+                }),
+                AnyShape::Circle(Circle {
+                    unit: Some("cm".to_string()),
+                    precision: None,
+                    name: "round".to_string(),
+                    radius: 1.5,
+                }),
+            ],
+        };
+
+        let xml = quick_xml::se::to_string(&drawing).unwrap();
+        assert!(xml.contains("<shape><name>plain</name></shape>"));
+        assert!(xml.contains(
+            "<shape xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xmlns:tns=\"urn:shapes\" xsi:type=\"tns:Circle\" unit=\"cm\">"
+        ));
+
+        let parsed: Drawing = quick_xml::de::from_str(&xml).unwrap();
+        assert_eq!(parsed, drawing);
+    }
+}